@@ -0,0 +1,304 @@
+//! Comment storage and the rules for posting, editing and moderating.
+
+use super::session::is_owner;
+use crate::models::comments::{
+    display_name, render_comment, Comment, CommentStatus, EDIT_WINDOW_SECS, MAX_COMMENT_CHARS,
+    RATE_LIMIT, RATE_WINDOW_SECS,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommentError {
+    Empty,
+    TooLong,
+    RateLimited,
+    /// Replies go to a top-level comment on the same post.
+    InvalidParent,
+    NotFound,
+    Forbidden,
+    EditWindowClosed,
+    Database(String),
+}
+
+impl std::fmt::Display for CommentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommentError::Empty => write!(f, "The comment is empty"),
+            CommentError::TooLong => write!(
+                f,
+                "Comments can be at most {} characters long",
+                MAX_COMMENT_CHARS
+            ),
+            CommentError::RateLimited => write!(
+                f,
+                "You can post {} comments every {} minutes, please wait a little",
+                RATE_LIMIT,
+                RATE_WINDOW_SECS / 60
+            ),
+            CommentError::InvalidParent => write!(f, "This comment cannot be replied to"),
+            CommentError::NotFound => write!(f, "Comment not found"),
+            CommentError::Forbidden => write!(f, "This is not your comment"),
+            CommentError::EditWindowClosed => write!(
+                f,
+                "Comments can only be changed in the first {} minutes",
+                EDIT_WINDOW_SECS / 60
+            ),
+            CommentError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for CommentError {
+    fn from(e: rusqlite::Error) -> Self {
+        CommentError::Database(e.to_string())
+    }
+}
+
+/// A row of the `comments` table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredComment {
+    pub id: i64,
+    pub post_slug: String,
+    pub parent_id: Option<i64>,
+    /// Email of the commenter.
+    pub author: String,
+    pub body: String,
+    pub status: CommentStatus,
+    pub created_at: i64,
+    pub edited_at: Option<i64>,
+    pub deleted_at: Option<i64>,
+}
+
+impl StoredComment {
+    /// The comment as `viewer` (an email) sees it. Authors see their own
+    /// spam as pending.
+    pub fn to_comment(&self, viewer: Option<&str>) -> Comment {
+        let mine = viewer.is_some_and(|viewer| viewer.eq_ignore_ascii_case(&self.author));
+        let deleted = self.deleted_at.is_some();
+        Comment {
+            id: self.id,
+            parent_id: self.parent_id,
+            author: if deleted {
+                String::new()
+            } else {
+                display_name(&self.author)
+            },
+            by_owner: is_owner(&self.author),
+            html: if deleted {
+                String::new()
+            } else {
+                render_comment(&self.body)
+            },
+            body: if mine && !deleted {
+                self.body.clone()
+            } else {
+                String::new()
+            },
+            status: match self.status {
+                CommentStatus::Spam if mine => CommentStatus::Pending,
+                status => status,
+            },
+            created_at: self.created_at,
+            edited: self.edited_at.is_some(),
+            deleted,
+            mine,
+        }
+    }
+
+    /// The comment as the moderation queue shows it.
+    pub fn to_moderated_comment(&self) -> Comment {
+        Comment {
+            body: self.body.clone(),
+            status: self.status,
+            mine: false,
+            ..self.to_comment(Some(&self.author))
+        }
+    }
+}
+
+const COLUMNS: &str =
+    "id, post_slug, parent_id, author, body, status, created_at, edited_at, deleted_at";
+
+fn row_to_comment(row: &rusqlite::Row) -> rusqlite::Result<StoredComment> {
+    let status: String = row.get(5)?;
+    Ok(StoredComment {
+        id: row.get(0)?,
+        post_slug: row.get(1)?,
+        parent_id: row.get(2)?,
+        author: row.get(3)?,
+        body: row.get(4)?,
+        status: CommentStatus::parse(&status).unwrap_or_default(),
+        created_at: row.get(6)?,
+        edited_at: row.get(7)?,
+        deleted_at: row.get(8)?,
+    })
+}
+
+/// The body without surrounding whitespace, if it is acceptable.
+fn checked_body(body: &str) -> Result<&str, CommentError> {
+    let body = body.trim();
+    if body.is_empty() {
+        Err(CommentError::Empty)
+    } else if body.chars().count() > MAX_COMMENT_CHARS {
+        Err(CommentError::TooLong)
+    } else {
+        Ok(body)
+    }
+}
+
+pub fn get(conn: &Connection, id: i64) -> Result<Option<StoredComment>, CommentError> {
+    Ok(conn
+        .query_row(
+            &format!("SELECT {} FROM comments WHERE id = ?1", COLUMNS),
+            [id],
+            row_to_comment,
+        )
+        .optional()?)
+}
+
+/// Every comment on a post, deleted ones included, oldest first.
+pub fn for_post(conn: &Connection, slug: &str) -> Result<Vec<StoredComment>, CommentError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM comments WHERE post_slug = ?1 ORDER BY id",
+        COLUMNS
+    ))?;
+    let comments = stmt
+        .query_map([slug], row_to_comment)?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(comments)
+}
+
+/// Comments with `status`, newest first.
+pub fn with_status(
+    conn: &Connection,
+    status: CommentStatus,
+) -> Result<Vec<StoredComment>, CommentError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM comments WHERE status = ?1 AND deleted_at IS NULL ORDER BY id DESC",
+        COLUMNS
+    ))?;
+    let comments = stmt
+        .query_map([status.as_str()], row_to_comment)?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(comments)
+}
+
+/// What `viewer` may see of a post's comments: approved comments and their
+/// own. Deleted comments are kept as placeholders for `threads` to place
+/// replies under.
+pub fn visible(comments: Vec<StoredComment>, viewer: Option<&str>) -> Vec<Comment> {
+    comments
+        .into_iter()
+        .map(|stored| stored.to_comment(viewer))
+        .filter(|comment| comment.status == CommentStatus::Approved || comment.mine)
+        .collect()
+}
+
+/// Fails once `author` has posted [`RATE_LIMIT`] comments in the last
+/// [`RATE_WINDOW_SECS`]. Deleting comments does not reset the count.
+pub fn check_rate(conn: &Connection, author: &str, now: i64) -> Result<(), CommentError> {
+    let recent: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM comments WHERE author = ?1 AND created_at > ?2",
+        params![author, now - RATE_WINDOW_SECS],
+        |row| row.get(0),
+    )?;
+    if recent as usize >= RATE_LIMIT {
+        Err(CommentError::RateLimited)
+    } else {
+        Ok(())
+    }
+}
+
+/// Stores a new comment and returns its id.
+pub fn add(
+    conn: &Connection,
+    slug: &str,
+    parent_id: Option<i64>,
+    author: &str,
+    body: &str,
+    status: CommentStatus,
+    now: i64,
+) -> Result<i64, CommentError> {
+    let body = checked_body(body)?;
+    if let Some(parent_id) = parent_id {
+        match get(conn, parent_id)? {
+            Some(parent)
+                if parent.post_slug == slug
+                    && parent.parent_id.is_none()
+                    && parent.deleted_at.is_none() => {}
+            _ => return Err(CommentError::InvalidParent),
+        }
+    }
+    conn.execute(
+        "INSERT INTO comments (post_slug, parent_id, author, body, status, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![slug, parent_id, author, body, status.as_str(), now],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// A comment `author` may still change at `now`.
+fn own_comment(
+    conn: &Connection,
+    id: i64,
+    author: &str,
+    now: i64,
+) -> Result<StoredComment, CommentError> {
+    let comment = get(conn, id)?
+        .filter(|comment| comment.deleted_at.is_none())
+        .ok_or(CommentError::NotFound)?;
+    if !comment.author.eq_ignore_ascii_case(author) {
+        return Err(CommentError::Forbidden);
+    }
+    if now >= comment.created_at + EDIT_WINDOW_SECS {
+        return Err(CommentError::EditWindowClosed);
+    }
+    Ok(comment)
+}
+
+/// Replaces the body of one of `author`'s comments. Unless `approved` is
+/// set (the owner's comments), the edit goes back to moderation; spam stays
+/// spam.
+pub fn edit(
+    conn: &Connection,
+    id: i64,
+    author: &str,
+    body: &str,
+    approved: bool,
+    now: i64,
+) -> Result<(), CommentError> {
+    let comment = own_comment(conn, id, author, now)?;
+    let body = checked_body(body)?;
+    let status = match comment.status {
+        _ if approved => CommentStatus::Approved,
+        CommentStatus::Spam => CommentStatus::Spam,
+        _ => CommentStatus::Pending,
+    };
+    conn.execute(
+        "UPDATE comments SET body = ?1, status = ?2, edited_at = ?3 WHERE id = ?4",
+        params![body, status.as_str(), now, id],
+    )?;
+    Ok(())
+}
+
+/// Deletes one of `author`'s comments. The row stays so replies keep their
+/// place, but the text is dropped.
+pub fn delete(conn: &Connection, id: i64, author: &str, now: i64) -> Result<(), CommentError> {
+    own_comment(conn, id, author, now)?;
+    conn.execute(
+        "UPDATE comments SET body = '', deleted_at = ?1 WHERE id = ?2",
+        params![now, id],
+    )?;
+    Ok(())
+}
+
+pub fn set_status(conn: &Connection, id: i64, status: CommentStatus) -> Result<(), CommentError> {
+    let changed = conn.execute(
+        "UPDATE comments SET status = ?1 WHERE id = ?2",
+        params![status.as_str(), id],
+    )?;
+    if changed == 0 {
+        return Err(CommentError::NotFound);
+    }
+    Ok(())
+}
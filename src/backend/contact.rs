@@ -0,0 +1,53 @@
+use crate::models::contact::{qr_svg, vcard};
+use crate::models::cv::master;
+use dioxus::server::axum::{
+    extract::Query,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+pub async fn vcf() -> Response {
+    let basics = master().full().without_private().basics;
+    let filename = basics.full_name().to_lowercase().replace(' ', "-");
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/vcard; charset=utf-8".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.vcf\"", filename),
+            ),
+        ],
+        vcard(&basics, &super::site_url()),
+    )
+        .into_response()
+}
+
+#[derive(Deserialize)]
+pub struct QrQuery {
+    #[serde(default)]
+    target: Option<String>,
+}
+
+/// `/qr.svg?target=site` (default) or `/qr.svg?target=vcard`.
+pub async fn qr(Query(query): Query<QrQuery>) -> Response {
+    let url = match query.target.as_deref() {
+        None | Some("site") => super::site_url(),
+        Some("vcard") => format!("{}/contact.vcf", super::site_url()),
+        Some(_) => return (StatusCode::BAD_REQUEST, "Unknown QR target").into_response(),
+    };
+    match qr_svg(&url) {
+        Ok(svg) => (
+            [
+                (header::CONTENT_TYPE, "image/svg+xml"),
+                (header::CACHE_CONTROL, "public, max-age=86400"),
+            ],
+            svg,
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
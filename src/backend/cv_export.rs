@@ -0,0 +1,57 @@
+use crate::models::cv::{master, TailoredCv};
+use dioxus::server::axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+fn download(variant: &str, filename: &str, content_type: &str, body: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}-{}\"", variant, filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn unknown_variant() -> Response {
+    (StatusCode::NOT_FOUND, "Unknown CV variant").into_response()
+}
+
+/// Public variants only, without private contact details.
+fn public_variant(variant: &str) -> Option<TailoredCv> {
+    let cv = master();
+    cv.variant(variant)
+        .filter(|v| v.public)
+        .and_then(|v| cv.tailor(&v.slug))
+        .map(TailoredCv::without_private)
+}
+
+pub async fn json_resume(Path(variant): Path<String>) -> Response {
+    match public_variant(&variant) {
+        Some(cv) => download(
+            &variant,
+            "resume.json",
+            "application/json",
+            serde_json::to_string_pretty(&cv.to_json_resume()).unwrap_or_default(),
+        ),
+        None => unknown_variant(),
+    }
+}
+
+pub async fn markdown(Path(variant): Path<String>) -> Response {
+    match public_variant(&variant) {
+        Some(cv) => download(
+            &variant,
+            "cv.md",
+            "text/markdown; charset=utf-8",
+            cv.to_markdown(),
+        ),
+        None => unknown_variant(),
+    }
+}
@@ -0,0 +1,139 @@
+//! SQLite storage shared by the server functions.
+
+use rusqlite::Connection;
+
+/// Table definitions, applied in order on every connection.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS share_links (
+        id TEXT PRIMARY KEY,
+        variant TEXT NOT NULL,
+        label TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        expires_at INTEGER NOT NULL,
+        revoked INTEGER NOT NULL DEFAULT 0,
+        views INTEGER NOT NULL DEFAULT 0
+    )",
+    "CREATE TABLE IF NOT EXISTS share_views (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        link_id TEXT NOT NULL REFERENCES share_links(id),
+        viewed_at INTEGER NOT NULL,
+        referrer TEXT
+    )",
+    // Posts written in the editor, as full Markdown files with front matter
+    "CREATE TABLE IF NOT EXISTS posts (
+        slug TEXT PRIMARY KEY,
+        source TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    )",
+    // Unsaved editor state (a JSON `PostDraft`), kept until saved or discarded
+    "CREATE TABLE IF NOT EXISTS post_autosaves (
+        slug TEXT PRIMARY KEY,
+        draft TEXT NOT NULL,
+        saved_at INTEGER NOT NULL
+    )",
+    // Every version of a post saved through the site
+    "CREATE TABLE IF NOT EXISTS post_revisions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        slug TEXT NOT NULL,
+        source TEXT NOT NULL,
+        hash TEXT NOT NULL,
+        author TEXT NOT NULL,
+        created_at INTEGER NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS post_revisions_slug ON post_revisions (slug, id)",
+    // Search index over posts and CV entries, rebuilt by `search::ensure_index`
+    "CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+        kind UNINDEXED,
+        path UNINDEXED,
+        title,
+        body,
+        tokenize = 'porter unicode61 remove_diacritics 2'
+    )",
+    "CREATE TABLE IF NOT EXISTS search_meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    )",
+    // Reader comments on posts; `author` is the commenter's email
+    "CREATE TABLE IF NOT EXISTS comments (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        post_slug TEXT NOT NULL,
+        parent_id INTEGER REFERENCES comments(id),
+        author TEXT NOT NULL,
+        body TEXT NOT NULL,
+        status TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        edited_at INTEGER,
+        deleted_at INTEGER
+    )",
+    "CREATE INDEX IF NOT EXISTS comments_post ON comments (post_slug, id)",
+    "CREATE INDEX IF NOT EXISTS comments_author ON comments (author, created_at)",
+    // One row per reader, post and day (days since the epoch); `reader` is a
+    // hash of the user or visitor id
+    "CREATE TABLE IF NOT EXISTS post_views (
+        post_slug TEXT NOT NULL,
+        reader TEXT NOT NULL,
+        day INTEGER NOT NULL,
+        PRIMARY KEY (post_slug, reader, day)
+    )",
+    // At most one reaction per reader and post
+    "CREATE TABLE IF NOT EXISTS post_reactions (
+        post_slug TEXT NOT NULL,
+        reader TEXT NOT NULL,
+        reaction TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        PRIMARY KEY (post_slug, reader)
+    )",
+    // Mentions of posts received from other sites
+    "CREATE TABLE IF NOT EXISTS webmentions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        source TEXT NOT NULL,
+        target TEXT NOT NULL,
+        post_slug TEXT NOT NULL,
+        status TEXT NOT NULL,
+        title TEXT NOT NULL,
+        received_at INTEGER NOT NULL,
+        verified_at INTEGER,
+        UNIQUE (source, target)
+    )",
+    "CREATE INDEX IF NOT EXISTS webmentions_post ON webmentions (post_slug, status)",
+    // Posts whose outgoing mentions have been sent, and what each send did
+    "CREATE TABLE IF NOT EXISTS webmention_posts (
+        post_slug TEXT PRIMARY KEY,
+        handled_at INTEGER NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS webmention_sends (
+        post_slug TEXT NOT NULL,
+        target TEXT NOT NULL,
+        result TEXT NOT NULL,
+        sent_at INTEGER NOT NULL,
+        PRIMARY KEY (post_slug, target)
+    )",
+    "CREATE TABLE IF NOT EXISTS webmention_meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    )",
+];
+
+/// Opens the database at `DATABASE_PATH` (default `cv.db`) and makes sure the
+/// schema is up to date.
+pub fn connect() -> rusqlite::Result<Connection> {
+    let path = std::env::var("DATABASE_PATH").unwrap_or_else(|_| "cv.db".to_string());
+    let conn = Connection::open(path)?;
+    migrate(&conn)?;
+    Ok(conn)
+}
+
+pub fn migrate(conn: &Connection) -> rusqlite::Result<()> {
+    for migration in MIGRATIONS {
+        conn.execute_batch(migration)?;
+    }
+    Ok(())
+}
+
+pub fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,92 @@
+use crate::models::blog::{listed_posts, Post};
+use crate::models::cv::master;
+use crate::models::feed::{atom, rss, FeedInfo};
+use crate::models::tags::{tag_counts, tag_slug};
+use dioxus::server::axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+enum Format {
+    Rss,
+    Atom,
+}
+
+impl Format {
+    fn file(&self) -> &'static str {
+        match self {
+            Format::Rss => "feed.xml",
+            Format::Atom => "atom.xml",
+        }
+    }
+}
+
+fn feed(format: Format, tag: Option<String>) -> Response {
+    let posts: Vec<Post> = match listed_posts() {
+        Ok(posts) => posts,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let site_url = super::site_url();
+    let author = master().basics.full_name();
+
+    let (posts, info) = match tag {
+        Some(tag) => {
+            let slug = tag_slug(&tag);
+            let tagged: Vec<Post> = posts
+                .into_iter()
+                .filter(|post| post.meta.has_tag(&slug))
+                .collect();
+            let Some(tag) = tag_counts(tagged.iter().map(|post| &post.meta))
+                .into_iter()
+                .find(|t| t.slug == slug)
+                .map(|t| t.name)
+            else {
+                return (StatusCode::NOT_FOUND, "Unknown tag").into_response();
+            };
+            let page_url = format!("{}/blog/tag/{}", site_url, slug);
+            let info = FeedInfo {
+                title: format!("{} — #{}", author, tag),
+                description: format!("Posts tagged #{} by {}", tag, author),
+                author,
+                feed_url: format!("{}/{}", page_url, format.file()),
+                page_url,
+                site_url,
+            };
+            (tagged, info)
+        }
+        None => {
+            let info = FeedInfo {
+                title: format!("{} — Blog", author),
+                description: master().basics.label.clone(),
+                author,
+                feed_url: format!("{}/{}", site_url, format.file()),
+                page_url: format!("{}/blog", site_url),
+                site_url,
+            };
+            (posts, info)
+        }
+    };
+
+    let (content_type, body) = match format {
+        Format::Rss => ("application/rss+xml; charset=utf-8", rss(&info, &posts)),
+        Format::Atom => ("application/atom+xml; charset=utf-8", atom(&info, &posts)),
+    };
+    ([(header::CONTENT_TYPE, content_type)], body).into_response()
+}
+
+pub async fn rss_feed() -> Response {
+    feed(Format::Rss, None)
+}
+
+pub async fn atom_feed() -> Response {
+    feed(Format::Atom, None)
+}
+
+pub async fn tag_rss_feed(Path(tag): Path<String>) -> Response {
+    feed(Format::Rss, Some(tag))
+}
+
+pub async fn tag_atom_feed(Path(tag): Path<String>) -> Response {
+    feed(Format::Atom, Some(tag))
+}
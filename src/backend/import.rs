@@ -0,0 +1,614 @@
+//! Importing posts from other blogging platforms: a dev.to JSON export
+//! (`articles.json`, alone or in the export zip) or a Medium export zip
+//! (`posts/*.html`). Posts become editor drafts, so they are stored exactly
+//! like posts written here.
+
+use super::media;
+use crate::models::blog::{PostMeta, PostStatus};
+use crate::models::editor::PostDraft;
+use crate::models::media::MAX_UPLOAD_BYTES;
+use crate::models::webmentions::decode_entities;
+use chrono::{DateTime, NaiveDate};
+use reqwest::Url;
+use rusqlite::Connection;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use std::ops::Range;
+use std::path::Path;
+
+/// Longest slug made from a title.
+const MAX_SLUG_LEN: usize = 80;
+/// Largest file read from an export zip.
+const MAX_ENTRY_BYTES: u64 = 20 * 1024 * 1024;
+/// Most bytes decompressed from one export zip, over all files.
+const MAX_ARCHIVE_BYTES: u64 = 100 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportError {
+    /// Neither a dev.to nor a Medium export.
+    UnknownFormat,
+    Archive(String),
+    Json(String),
+    Database(String),
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::UnknownFormat => write!(
+                f,
+                "Expected a Medium export zip or a dev.to articles.json export"
+            ),
+            ImportError::Archive(e) => write!(f, "Could not read the zip file: {}", e),
+            ImportError::Json(e) => write!(f, "Could not read the dev.to export: {}", e),
+            ImportError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<zip::result::ZipError> for ImportError {
+    fn from(e: zip::result::ZipError) -> Self {
+        ImportError::Archive(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ImportError {
+    fn from(e: serde_json::Error) -> Self {
+        ImportError::Json(e.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for ImportError {
+    fn from(e: rusqlite::Error) -> Self {
+        ImportError::Database(e.to_string())
+    }
+}
+
+/// The posts found in an export, and why others were left out.
+#[derive(Debug, Default)]
+pub struct Export {
+    pub drafts: Vec<PostDraft>,
+    pub skipped: Vec<String>,
+}
+
+/// A slug for a post titled `title`: lowercase ASCII letters and digits
+/// separated by single dashes.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if c != '\'' && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let mut slug = slug.trim_matches('-').to_string();
+    if slug.len() > MAX_SLUG_LEN {
+        let cut = slug[..MAX_SLUG_LEN].rfind('-').unwrap_or(MAX_SLUG_LEN);
+        slug.truncate(cut);
+    }
+    if slug.is_empty() {
+        "imported-post".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Reads an uploaded export. `today` dates posts that have no date.
+pub fn read_export(bytes: &[u8], today: NaiveDate) -> Result<Export, ImportError> {
+    if bytes.starts_with(b"PK") {
+        read_zip(bytes, today)
+    } else if bytes.trim_ascii_start().starts_with(b"[")
+        || bytes.trim_ascii_start().starts_with(b"{")
+    {
+        read_devto(bytes, today)
+    } else {
+        Err(ImportError::UnknownFormat)
+    }
+}
+
+fn read_zip(bytes: &[u8], today: NaiveDate) -> Result<Export, ImportError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut medium_posts = Vec::new();
+    let mut total: u64 = 0;
+    // The sizes in the zip are only claims, so reads are capped as well
+    let mut read_entry = |entry: zip::read::ZipFile<'_, _>| {
+        let mut contents = String::new();
+        entry
+            .take(MAX_ENTRY_BYTES + 1)
+            .read_to_string(&mut contents)
+            .map_err(|e| ImportError::Archive(e.to_string()))?;
+        if contents.len() as u64 > MAX_ENTRY_BYTES {
+            return Ok(None);
+        }
+        total += contents.len() as u64;
+        if total > MAX_ARCHIVE_BYTES {
+            return Err(ImportError::Archive(
+                "The export is too large once unpacked".to_string(),
+            ));
+        }
+        Ok(Some(contents))
+    };
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i)?;
+        if !entry.is_file() || entry.size() > MAX_ENTRY_BYTES {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let file_name = name.rsplit('/').next().unwrap_or_default().to_string();
+        if file_name == "articles.json" {
+            if let Some(contents) = read_entry(entry)? {
+                return read_devto(contents.as_bytes(), today);
+            }
+        } else if name.starts_with("posts/") && file_name.ends_with(".html") {
+            if let Some(contents) = read_entry(entry)? {
+                medium_posts.push((file_name, contents));
+            }
+        }
+    }
+    if medium_posts.is_empty() {
+        return Err(ImportError::UnknownFormat);
+    }
+    let mut export = Export::default();
+    for (file_name, html) in medium_posts {
+        match medium_post(&file_name, &html, today) {
+            Some(draft) => export.drafts.push(draft),
+            None => export
+                .skipped
+                .push(format!("{}: no title or body found", file_name)),
+        }
+    }
+    Ok(export)
+}
+
+/// The byte ranges of the whole element whose start tag contains `marker`
+/// (such as `class="p-name"`) and of its contents.
+fn find_element(html: &str, marker: &str) -> Option<(Range<usize>, Range<usize>)> {
+    let at = html.find(marker)?;
+    let start = html[..at].rfind('<')?;
+    let name_len = html[start + 1..].find(|c: char| !c.is_ascii_alphanumeric())?;
+    let name = html[start + 1..start + 1 + name_len].to_ascii_lowercase();
+    let content_start = at + html[at..].find('>')? + 1;
+
+    let lower = html.to_ascii_lowercase();
+    let open = format!("<{}", name);
+    let close = format!("</{}", name);
+    let mut depth = 1;
+    let mut pos = content_start;
+    loop {
+        let next = lower[pos..].find('<')? + pos;
+        let tag = &lower[next..];
+        let is_boundary = |rest: &str| {
+            rest.starts_with(|c: char| c == '>' || c == '/' || c.is_ascii_whitespace())
+        };
+        if tag.starts_with(&close) && is_boundary(&tag[close.len()..]) {
+            depth -= 1;
+            if depth == 0 {
+                let end = next + tag.find('>')? + 1;
+                return Some((start..end, content_start..next));
+            }
+        } else if tag.starts_with(&open) && is_boundary(&tag[open.len()..]) {
+            depth += 1;
+        }
+        pos = next + 1;
+    }
+}
+
+/// The value of `attribute` on the start tag containing `marker`.
+fn start_tag_attribute(html: &str, marker: &str, attribute: &str) -> Option<String> {
+    let at = html.find(marker)?;
+    let start = html[..at].rfind('<')?;
+    let tag = &html[start..at + html[at..].find('>')?];
+    let prefix = format!(" {}=\"", attribute);
+    let value_start = tag.find(&prefix)? + prefix.len();
+    let value_len = tag[value_start..].find('"')?;
+    Some(decode_entities(&tag[value_start..value_start + value_len]))
+}
+
+/// The text of an HTML fragment, with whitespace collapsed.
+fn text_content(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn html_to_markdown(html: &str) -> String {
+    let converter = htmd::HtmlToMarkdown::builder()
+        .options(htmd::options::Options {
+            bullet_list_marker: htmd::options::BulletListMarker::Dash,
+            hr_style: htmd::options::HrStyle::Dashes,
+            ul_bullet_spacing: 1,
+            ol_number_spacing: 1,
+            ..Default::default()
+        })
+        .skip_tags(vec!["script", "style"])
+        .build();
+    converter.convert(html).unwrap_or_default()
+}
+
+/// Converts one post of a Medium export. Drafts are files named
+/// `draft_<title>.html`; published posts are named `<date>_<title>.html`.
+pub fn medium_post(file_name: &str, html: &str, today: NaiveDate) -> Option<PostDraft> {
+    let title = find_element(html, "class=\"p-name\"")
+        .map(|(_, inner)| text_content(&html[inner]))
+        .filter(|title| !title.is_empty())?;
+    let description = find_element(html, "data-field=\"subtitle\"")
+        .map(|(_, inner)| text_content(&html[inner]))
+        .unwrap_or_default();
+    let (_, body_range) = find_element(html, "data-field=\"body\"")?;
+
+    // The body repeats the title and subtitle, and starts each section with
+    // a divider
+    let mut body = html[body_range].to_string();
+    for marker in ["graf--title", "graf--subtitle"] {
+        if let Some((range, _)) = find_element(&body, marker) {
+            body.replace_range(range, "");
+        }
+    }
+    while let Some((range, _)) = find_element(&body, "class=\"section-divider\"") {
+        body.replace_range(range, "");
+    }
+
+    let draft = file_name.starts_with("draft_");
+    let date = start_tag_attribute(html, "class=\"dt-published\"", "datetime")
+        .and_then(|at| DateTime::parse_from_rfc3339(&at).ok())
+        .map(|at| at.date_naive())
+        .or_else(|| {
+            file_name
+                .get(..10)
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        })
+        .unwrap_or(today);
+    Some(PostDraft {
+        slug: slugify(&title),
+        title,
+        date: date.to_string(),
+        description,
+        status: if draft {
+            PostStatus::Draft
+        } else {
+            PostStatus::Published
+        },
+        canonical_url: start_tag_attribute(html, "class=\"p-canonical\"", "href")
+            .unwrap_or_default(),
+        body: html_to_markdown(&body),
+        ..Default::default()
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DevToExport {
+    Articles(Vec<DevToArticle>),
+    Wrapped { articles: Vec<DevToArticle> },
+}
+
+/// dev.to has exported tags both as `"rust, webdev"` and as a list.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DevToTags {
+    Text(String),
+    List(Vec<String>),
+}
+
+impl DevToTags {
+    fn into_list(self) -> Vec<String> {
+        match self {
+            DevToTags::Text(text) => text
+                .split(',')
+                .map(|tag| tag.trim().to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect(),
+            DevToTags::List(list) => list,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct DevToArticle {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    body_markdown: Option<String>,
+    #[serde(default)]
+    published: Option<bool>,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default, alias = "cached_tag_list", alias = "tags")]
+    tag_list: Option<DevToTags>,
+    #[serde(default)]
+    canonical_url: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default, alias = "cover_image")]
+    main_image: Option<String>,
+}
+
+/// Front matter dev.to keeps at the top of posts written in its older
+/// editor.
+#[derive(Deserialize, Default)]
+struct DevToFrontMatter {
+    title: Option<String>,
+    description: Option<String>,
+    tags: Option<DevToTags>,
+    canonical_url: Option<String>,
+    cover_image: Option<String>,
+}
+
+/// Splits dev.to front matter off a body.
+fn split_devto_front_matter(body: &str) -> (DevToFrontMatter, &str) {
+    let Some(rest) = body
+        .strip_prefix("---\n")
+        .or_else(|| body.strip_prefix("---\r\n"))
+    else {
+        return (DevToFrontMatter::default(), body);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (DevToFrontMatter::default(), body);
+    };
+    let front = serde_yaml::from_str(&rest[..end]).unwrap_or_default();
+    let after = &rest[end + 4..];
+    (front, after.strip_prefix('\n').unwrap_or(after))
+}
+
+/// Replaces dev.to liquid tags that point at other pages
+/// (`{% embed https://... %}`, `{% youtube id %}`, ...) with plain links.
+/// Tags it does not know are left alone.
+pub fn convert_liquid_tags(body: &str) -> String {
+    let mut out = String::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("{%") {
+        let Some(len) = rest[start..].find("%}") else {
+            break;
+        };
+        let tag = &rest[start..start + len + 2];
+        let mut words = tag[2..tag.len() - 2].split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let argument = words.next().unwrap_or_default();
+        let url = match name {
+            _ if argument.starts_with("https://") || argument.starts_with("http://") => {
+                Some(argument.to_string())
+            }
+            "youtube" => Some(format!("https://www.youtube.com/watch?v={}", argument)),
+            "github" => Some(format!("https://github.com/{}", argument)),
+            "twitter" | "tweet" => Some(format!("https://twitter.com/i/status/{}", argument)),
+            _ => None,
+        };
+        out.push_str(&rest[..start]);
+        match url {
+            Some(url) if !argument.is_empty() => out.push_str(&format!("<{}>", url)),
+            _ => out.push_str(tag),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn read_devto(bytes: &[u8], today: NaiveDate) -> Result<Export, ImportError> {
+    let articles = match serde_json::from_slice::<DevToExport>(bytes)? {
+        DevToExport::Articles(articles) | DevToExport::Wrapped { articles } => articles,
+    };
+    let mut export = Export::default();
+    for article in articles {
+        let source = article.body_markdown.unwrap_or_default();
+        let (front, body) = split_devto_front_matter(&source);
+        let title = Some(article.title)
+            .filter(|title| !title.trim().is_empty())
+            .or(front.title)
+            .unwrap_or_default();
+        if title.trim().is_empty() {
+            export
+                .skipped
+                .push("A dev.to article without a title".to_string());
+            continue;
+        }
+        let date = article
+            .published_at
+            .or(article.created_at)
+            .and_then(|at| DateTime::parse_from_rfc3339(&at).ok())
+            .map(|at| at.date_naive())
+            .unwrap_or(today);
+        let tags = article
+            .tag_list
+            .or(front.tags)
+            .map(DevToTags::into_list)
+            .unwrap_or_default();
+        // A post cross-posted to dev.to keeps pointing at its original
+        let canonical_url = [article.canonical_url, front.canonical_url, article.url]
+            .into_iter()
+            .flatten()
+            .find(|url| !url.trim().is_empty())
+            .unwrap_or_default();
+        let mut body = convert_liquid_tags(body.trim());
+        if let Some(cover) = article
+            .main_image
+            .or(front.cover_image)
+            .filter(|url| !url.trim().is_empty())
+        {
+            body = format!("![]({})\n\n{}", cover.trim(), body);
+        }
+        export.drafts.push(PostDraft {
+            slug: slugify(&title),
+            title: title.trim().to_string(),
+            date: date.to_string(),
+            description: article
+                .description
+                .or(front.description)
+                .unwrap_or_default(),
+            tags: tags.join(", "),
+            status: if article.published == Some(false) {
+                PostStatus::Draft
+            } else {
+                PostStatus::Published
+            },
+            canonical_url,
+            body,
+            ..Default::default()
+        });
+    }
+    Ok(export)
+}
+
+/// Gives each draft a slug no other post has, leaving out posts that
+/// already exist on the site (so importing again adds nothing twice).
+pub fn assign_slugs(export: &mut Export, existing: &[String]) {
+    let mut taken: Vec<String> = existing.to_vec();
+    let mut drafts = Vec::new();
+    for mut draft in std::mem::take(&mut export.drafts) {
+        if existing.contains(&draft.slug) {
+            export.skipped.push(format!(
+                "{}: a post with this slug already exists",
+                draft.title
+            ));
+            continue;
+        }
+        let base = draft.slug.clone();
+        let mut n = 2;
+        while taken.contains(&draft.slug) {
+            draft.slug = format!("{}-{}", base, n);
+            n += 1;
+        }
+        taken.push(draft.slug.clone());
+        drafts.push(draft);
+    }
+    export.drafts = drafts;
+}
+
+/// Absolute URLs of the images a Markdown body shows.
+pub fn image_urls(markdown: &str) -> Vec<String> {
+    let mut urls = Vec::new();
+    for event in pulldown_cmark::Parser::new(markdown) {
+        if let pulldown_cmark::Event::Start(pulldown_cmark::Tag::Image { dest_url, .. }) = event {
+            let url = dest_url.to_string();
+            if (url.starts_with("https://") || url.starts_with("http://")) && !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+    }
+    urls
+}
+
+/// Points image references at new URLs.
+pub fn replace_image_urls(markdown: &str, replacements: &HashMap<String, String>) -> String {
+    let mut markdown = markdown.to_string();
+    for (from, to) in replacements {
+        markdown = markdown
+            .replace(&format!("]({})", from), &format!("]({})", to))
+            .replace(&format!("]({} ", from), &format!("]({} ", to));
+    }
+    markdown
+}
+
+async fn download(
+    client: &reqwest::Client,
+    url: &Url,
+    public_only: bool,
+) -> Result<Vec<u8>, String> {
+    // The client should come from `webmentions::public_client_builder`,
+    // which checks resolved addresses and redirects as well
+    if public_only && !super::webmentions::is_public(url) {
+        return Err("not a public URL".to_string());
+    }
+    let mut response = client
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("the server answered {}", response.status()));
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_UPLOAD_BYTES {
+            return Err("the image is too large".to_string());
+        }
+    }
+    Ok(bytes)
+}
+
+/// Downloads the remote images of `drafts` into the media store at `dir`
+/// and points the posts at the local copies. Images that cannot be
+/// fetched or read keep their remote URL and are reported.
+pub async fn localize_images(
+    client: &reqwest::Client,
+    drafts: &mut [PostDraft],
+    dir: &Path,
+    public_only: bool,
+) -> Vec<String> {
+    let mut stored: HashMap<String, String> = HashMap::new();
+    let mut warnings = Vec::new();
+    for draft in drafts.iter_mut() {
+        for url in image_urls(&draft.body) {
+            if stored.contains_key(&url) {
+                continue;
+            }
+            let Ok(parsed) = Url::parse(&url) else {
+                continue;
+            };
+            let result = match download(client, &parsed, public_only).await {
+                Ok(bytes) => {
+                    let dir = dir.to_path_buf();
+                    tokio::task::spawn_blocking(move || media::store(&dir, &bytes))
+                        .await
+                        .map_err(|e| e.to_string())
+                        .and_then(|stored| stored.map_err(|e| e.to_string()))
+                }
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(image) => {
+                    stored.insert(url, image.url());
+                }
+                Err(e) => {
+                    warnings.push(format!("{}: image {} not copied ({})", draft.title, url, e))
+                }
+            }
+        }
+        draft.body = replace_image_urls(&draft.body, &stored);
+    }
+    warnings
+}
+
+/// Saves the drafts as posts by `author`. Drafts that do not validate are
+/// reported instead. Published posts are marked as handled for outgoing
+/// webmentions, which were sent from their original home if at all.
+pub fn save(
+    conn: &Connection,
+    export: &mut Export,
+    author: &str,
+    now: i64,
+) -> Result<Vec<PostMeta>, ImportError> {
+    let mut saved = Vec::new();
+    for draft in std::mem::take(&mut export.drafts) {
+        match draft.parse() {
+            Ok(post) => {
+                super::posts::save(conn, &draft.slug, &draft.to_source(), author, now)?;
+                if post.meta.status == PostStatus::Published {
+                    super::webmentions::mark_handled(conn, &draft.slug, now)
+                        .map_err(|e| ImportError::Database(e.to_string()))?;
+                }
+                saved.push(post.meta);
+            }
+            Err(e) => export.skipped.push(format!("{}: {}", draft.title, e)),
+        }
+    }
+    Ok(saved)
+}
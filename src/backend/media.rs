@@ -0,0 +1,236 @@
+//! Uploaded images: processing on upload and serving under `/media`.
+//!
+//! Uploads are decoded and encoded again rather than stored as sent. That
+//! drops EXIF and other metadata, GPS positions included, once the
+//! orientation they record has been applied to the pixels.
+
+use crate::models::media::{
+    is_media_file, MediaImage, VariantFormat, MAX_DIMENSION, MAX_UPLOAD_BYTES,
+};
+use dioxus::server::axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+use std::path::{Path as FsPath, PathBuf};
+
+const JPEG_QUALITY: u8 = 85;
+const WEBP_QUALITY: f32 = 80.0;
+const AVIF_QUALITY: u8 = 70;
+/// 1 (slowest, smallest) to 10 (fastest). AV1 encoding dominates upload
+/// time, and the slower settings save little on images this small.
+const AVIF_SPEED: u8 = 10;
+/// Upper bound on the memory a decoder may allocate.
+const MAX_ALLOC: u64 = 512 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaError {
+    TooLarge,
+    UnsupportedType,
+    Invalid(String),
+    Io(String),
+}
+
+impl std::fmt::Display for MediaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaError::TooLarge => write!(
+                f,
+                "Images can be at most {} MB and {} pixels wide or high",
+                MAX_UPLOAD_BYTES / (1024 * 1024),
+                MAX_DIMENSION
+            ),
+            MediaError::UnsupportedType => {
+                write!(f, "Only JPEG, PNG and WebP images can be uploaded")
+            }
+            MediaError::Invalid(e) => write!(f, "This image cannot be read: {}", e),
+            MediaError::Io(e) => write!(f, "Could not store the image: {}", e),
+        }
+    }
+}
+
+impl From<image::ImageError> for MediaError {
+    fn from(e: image::ImageError) -> Self {
+        match e {
+            image::ImageError::Limits(_) => MediaError::TooLarge,
+            image::ImageError::Unsupported(_) => MediaError::UnsupportedType,
+            e => MediaError::Invalid(e.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for MediaError {
+    fn from(e: std::io::Error) -> Self {
+        MediaError::Io(e.to_string())
+    }
+}
+
+/// `MEDIA_DIR` (default `media`).
+pub fn media_dir() -> PathBuf {
+    PathBuf::from(std::env::var("MEDIA_DIR").unwrap_or_else(|_| "media".to_string()))
+}
+
+/// An upload ready to be written: the original without metadata and every
+/// variant, by file name.
+pub struct ProcessedImage {
+    pub image: MediaImage,
+    pub files: Vec<(String, Vec<u8>)>,
+}
+
+/// Checks, cleans and resizes an upload. The type is taken from the
+/// content, not from what the browser claimed.
+pub fn process(bytes: &[u8]) -> Result<ProcessedImage, MediaError> {
+    if bytes.len() > MAX_UPLOAD_BYTES {
+        return Err(MediaError::TooLarge);
+    }
+    let format = image::guess_format(bytes).map_err(|_| MediaError::UnsupportedType)?;
+    let extension = match format {
+        ImageFormat::Jpeg => "jpg",
+        ImageFormat::Png | ImageFormat::WebP => "png",
+        _ => return Err(MediaError::UnsupportedType),
+    };
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    limits.max_alloc = Some(MAX_ALLOC);
+    reader.limits(limits);
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut decoded = DynamicImage::from_decoder(decoder)?;
+    decoded.apply_orientation(orientation);
+    let decoded = if decoded.color().has_alpha() {
+        DynamicImage::ImageRgba8(decoded.to_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(decoded.to_rgb8())
+    };
+
+    let hash: String = Sha256::digest(bytes)
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let image = MediaImage {
+        hash,
+        width: decoded.width(),
+        height: decoded.height(),
+        extension: extension.to_string(),
+    };
+
+    let mut files = vec![(image.file_name(), encode_original(&decoded, format)?)];
+    // Each width is resized and encoded on its own thread
+    let variants = std::thread::scope(|scope| {
+        let workers: Vec<_> = image
+            .widths()
+            .into_iter()
+            .map(|width| {
+                let (image, decoded) = (&image, &decoded);
+                scope.spawn(move || {
+                    let resized = if width == decoded.width() {
+                        decoded.clone()
+                    } else {
+                        decoded.resize(width, u32::MAX, FilterType::Lanczos3)
+                    };
+                    VariantFormat::ALL
+                        .into_iter()
+                        .map(|variant| {
+                            Ok((
+                                image.variant_name(width, variant),
+                                encode_variant(&resized, variant)?,
+                            ))
+                        })
+                        .collect::<Result<Vec<_>, MediaError>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("image encoding panicked"))
+            .collect::<Result<Vec<_>, MediaError>>()
+    })?;
+    files.extend(variants.into_iter().flatten());
+    Ok(ProcessedImage { image, files })
+}
+
+fn encode_original(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, MediaError> {
+    let mut out = Vec::new();
+    match format {
+        ImageFormat::Jpeg => {
+            image.write_with_encoder(JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY))?
+        }
+        _ => image.write_to(&mut Cursor::new(&mut out), ImageFormat::Png)?,
+    }
+    Ok(out)
+}
+
+fn encode_variant(image: &DynamicImage, format: VariantFormat) -> Result<Vec<u8>, MediaError> {
+    match format {
+        VariantFormat::Avif => {
+            let mut out = Vec::new();
+            image.write_with_encoder(AvifEncoder::new_with_speed_quality(
+                &mut out,
+                AVIF_SPEED,
+                AVIF_QUALITY,
+            ))?;
+            Ok(out)
+        }
+        VariantFormat::Webp => {
+            let encoder = match image {
+                DynamicImage::ImageRgba8(pixels) => {
+                    webp::Encoder::from_rgba(pixels, image.width(), image.height())
+                }
+                _ => webp::Encoder::from_rgb(image.as_bytes(), image.width(), image.height()),
+            };
+            Ok(encoder.encode(WEBP_QUALITY).to_vec())
+        }
+    }
+}
+
+/// Processes an upload and writes its files to `dir`. Uploading the same
+/// image again finds the files already there.
+pub fn store(dir: &FsPath, bytes: &[u8]) -> Result<MediaImage, MediaError> {
+    let processed = process(bytes)?;
+    std::fs::create_dir_all(dir)?;
+    for (name, data) in &processed.files {
+        let path = dir.join(name);
+        if !path.exists() {
+            // Written under a temporary name so a reader never sees half a file
+            let partial = dir.join(format!(".{}.part", name));
+            std::fs::write(&partial, data)?;
+            std::fs::rename(&partial, &path)?;
+        }
+    }
+    Ok(processed.image)
+}
+
+pub async fn serve(Path(file): Path<String>) -> Response {
+    if !is_media_file(&file) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let content_type = match file.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("jpg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("webp") => "image/webp",
+        _ => "image/avif",
+    };
+    match std::fs::read(media_dir().join(&file)) {
+        Ok(body) => (
+            [
+                (header::CONTENT_TYPE, content_type),
+                // Names change with the content, so a file never does
+                (header::CACHE_CONTROL, "public, max-age=31536000, immutable"),
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+            ],
+            body,
+        )
+            .into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
@@ -0,0 +1,51 @@
+//! Server-only code: storage, sessions, and the HTTP endpoints that sit next
+//! to the Dioxus application (downloads and other responses that are not
+//! rendered pages).
+
+pub mod comments;
+mod contact;
+mod cv_export;
+pub mod db;
+mod feeds;
+pub mod import;
+pub mod media;
+pub mod og;
+pub mod posts;
+pub mod reactions;
+pub mod search;
+mod seo;
+pub mod session;
+pub mod share;
+pub mod throttle;
+pub mod webmentions;
+
+use dioxus::server::axum::{
+    routing::{get, post},
+    Router,
+};
+
+/// Public base URL of the site (`SITE_URL`), without a trailing slash.
+pub fn site_url() -> String {
+    std::env::var("SITE_URL")
+        .unwrap_or_else(|_| "http://localhost:8080".to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Routes merged into the Dioxus router in `main`.
+pub fn router() -> Router {
+    Router::new()
+        .route("/contact.vcf", get(contact::vcf))
+        .route("/qr.svg", get(contact::qr))
+        .route("/cv/{variant}/resume.json", get(cv_export::json_resume))
+        .route("/cv/{variant}/cv.md", get(cv_export::markdown))
+        .route("/feed.xml", get(feeds::rss_feed))
+        .route("/atom.xml", get(feeds::atom_feed))
+        .route("/blog/tag/{tag}/feed.xml", get(feeds::tag_rss_feed))
+        .route("/blog/tag/{tag}/atom.xml", get(feeds::tag_atom_feed))
+        .route("/sitemap.xml", get(seo::sitemap))
+        .route("/robots.txt", get(seo::robots))
+        .route("/media/{file}", get(media::serve))
+        .route("/og/{kind}/{file}", get(og::serve))
+        .route("/webmention", post(webmentions::endpoint))
+}
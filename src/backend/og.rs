@@ -0,0 +1,411 @@
+//! Link preview images under `/og/<kind>/<slug>.png`: a 1200×630 card with
+//! the title, author, date and tags on the site's dark theme, drawn with the
+//! bundled DejaVu fonts.
+//!
+//! Rendering takes a few tens of milliseconds, so cards are kept in
+//! `MEDIA_DIR/og`. A file's name carries a hash of what it shows, so editing
+//! a post renders it again and the stale file is removed.
+
+use crate::models::seo::{OgKind, MAIN_CV, OG_HEIGHT, OG_WIDTH};
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use dioxus::server::axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use image::{ImageFormat, Rgb, RgbImage};
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+use std::path::{Path as FsPath, PathBuf};
+
+/// Part of every cache key; bump it when the layout changes.
+const RENDER_VERSION: &str = "1";
+
+const REGULAR: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+const BOLD: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf");
+
+pub const BACKGROUND: Rgb<u8> = Rgb([0x11, 0x18, 0x27]);
+const ACCENT: Rgb<u8> = Rgb([0x3b, 0x82, 0xf6]);
+const TEXT: Rgb<u8> = Rgb([0xff, 0xff, 0xff]);
+const MUTED: Rgb<u8> = Rgb([0x9c, 0xa3, 0xaf]);
+const RULE: Rgb<u8> = Rgb([0x1f, 0x29, 0x37]);
+
+const MARGIN: f32 = 80.0;
+const ACCENT_WIDTH: u32 = 16;
+const TITLE_LINES: usize = 3;
+
+/// What a card shows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Card {
+    pub kind: OgKind,
+    pub title: String,
+    pub author: String,
+    pub date: Option<String>,
+    pub tags: Vec<String>,
+    /// The site's host name, shown in the corner.
+    pub site: String,
+}
+
+impl Card {
+    fn kicker(&self) -> &'static str {
+        match self.kind {
+            OgKind::Post => "BLOG",
+            OgKind::Cv => "CV",
+        }
+    }
+
+    /// Changes whenever anything drawn on the card does.
+    fn hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for field in [
+            RENDER_VERSION,
+            self.kind.as_str(),
+            &self.title,
+            &self.author,
+            self.date.as_deref().unwrap_or_default(),
+            &self.tags.join("\n"),
+            &self.site,
+        ] {
+            hasher.update(field.as_bytes());
+            hasher.update([0]);
+        }
+        hasher
+            .finalize()
+            .iter()
+            .take(8)
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+/// The card for a reachable post, or for the full CV (`main`) or a public
+/// variant.
+pub fn card(kind: OgKind, slug: &str) -> Option<Card> {
+    let cv = crate::models::cv::master();
+    let site = crate::backend::site_url()
+        .split_once("://")
+        .map(|(_, host)| host.to_string())
+        .unwrap_or_default();
+    match kind {
+        OgKind::Post => {
+            // Looked up in the cached post set without copying it, as this
+            // runs for every request before the file cache is checked
+            let now = chrono::Utc::now();
+            let meta = crate::models::blog::with_posts(|_, posts| {
+                posts
+                    .iter()
+                    .find(|post| post.meta.slug == slug && post.meta.is_reachable(now, false))
+                    .map(|post| post.meta.clone())
+            })
+            .ok()??;
+            Some(Card {
+                kind,
+                title: meta.title,
+                author: cv.basics.full_name(),
+                date: Some(meta.date.format("%B %-d, %Y").to_string()),
+                tags: meta.tags,
+                site,
+            })
+        }
+        OgKind::Cv => {
+            let tailored = if slug == MAIN_CV {
+                cv.full()
+            } else {
+                cv.variant(slug)
+                    .filter(|variant| variant.public)
+                    .and_then(|variant| cv.tailor(&variant.slug))?
+            };
+            Some(Card {
+                kind,
+                title: tailored.basics.label.clone(),
+                author: tailored.basics.full_name(),
+                date: None,
+                tags: tailored
+                    .skills
+                    .iter()
+                    .take(5)
+                    .map(|skill| skill.name.clone())
+                    .collect(),
+                site,
+            })
+        }
+    }
+}
+
+struct Fonts {
+    regular: FontRef<'static>,
+    bold: FontRef<'static>,
+}
+
+impl Fonts {
+    fn load() -> Self {
+        Fonts {
+            regular: FontRef::try_from_slice(REGULAR).expect("bundled font is valid"),
+            bold: FontRef::try_from_slice(BOLD).expect("bundled font is valid"),
+        }
+    }
+}
+
+fn text_width(font: &FontRef, size: f32, text: &str) -> f32 {
+    let font = font.as_scaled(PxScale::from(size));
+    let mut width = 0.0;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = font.glyph_id(c);
+        if let Some(previous) = previous {
+            width += font.kern(previous, id);
+        }
+        width += font.h_advance(id);
+        previous = Some(id);
+    }
+    width
+}
+
+/// Shortens `text` with an ellipsis until it fits in `width`.
+fn fit(font: &FontRef, size: f32, text: &str, width: f32) -> String {
+    if text_width(font, size, text) <= width {
+        return text.to_string();
+    }
+    let mut chars: Vec<char> = text.chars().collect();
+    while !chars.is_empty() {
+        chars.pop();
+        let shortened = format!("{}…", chars.iter().collect::<String>().trim_end());
+        if text_width(font, size, &shortened) <= width {
+            return shortened;
+        }
+    }
+    String::new()
+}
+
+/// Breaks `text` into lines no wider than `width`, at most `max_lines` of
+/// them; the last one ends in an ellipsis when the text does not fit.
+fn wrap(font: &FontRef, size: f32, text: &str, width: f32, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if text_width(font, size, &format!("{} {}", line, word)) <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    if lines.len() > max_lines {
+        let rest = lines.split_off(max_lines - 1).join(" ");
+        lines.push(rest);
+    }
+    lines
+        .iter()
+        .map(|line| fit(font, size, line, width))
+        .collect()
+}
+
+/// Draws `text` with its baseline at `y`.
+fn draw_text(
+    image: &mut RgbImage,
+    font: &FontRef,
+    size: f32,
+    x: f32,
+    y: f32,
+    color: Rgb<u8>,
+    text: &str,
+) {
+    let scale = PxScale::from(size);
+    let scaled = font.as_scaled(scale);
+    let mut caret = x;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, id);
+        }
+        let glyph = id.with_scale_and_position(scale, point(caret, y));
+        caret += scaled.h_advance(id);
+        previous = Some(id);
+        let Some(outline) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outline.px_bounds();
+        outline.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i32 + gx as i32;
+            let py = bounds.min.y as i32 + gy as i32;
+            if px < 0 || py < 0 || px >= image.width() as i32 || py >= image.height() as i32 {
+                return;
+            }
+            let pixel = image.get_pixel_mut(px as u32, py as u32);
+            let coverage = coverage.clamp(0.0, 1.0);
+            for (channel, target) in pixel.0.iter_mut().zip(color.0) {
+                *channel =
+                    (*channel as f32 + (target as f32 - *channel as f32) * coverage).round() as u8;
+            }
+        });
+    }
+}
+
+fn fill(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: Rgb<u8>) {
+    for py in y..(y + height).min(image.height()) {
+        for px in x..(x + width).min(image.width()) {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+/// Draws a card.
+pub fn render(card: &Card) -> RgbImage {
+    let fonts = Fonts::load();
+    let mut image = RgbImage::from_pixel(OG_WIDTH, OG_HEIGHT, BACKGROUND);
+    fill(&mut image, 0, 0, ACCENT_WIDTH, OG_HEIGHT, ACCENT);
+    let left = MARGIN + ACCENT_WIDTH as f32;
+    let width = OG_WIDTH as f32 - left - MARGIN;
+    let bottom = OG_HEIGHT as f32 - MARGIN;
+
+    draw_text(
+        &mut image,
+        &fonts.bold,
+        26.0,
+        left,
+        MARGIN + 20.0,
+        ACCENT,
+        card.kicker(),
+    );
+
+    // Long titles get a smaller size before they get cut
+    let mut size = 68.0;
+    let mut lines = wrap(&fonts.bold, size, &card.title, width, TITLE_LINES);
+    if lines.len() == TITLE_LINES {
+        size = 56.0;
+        lines = wrap(&fonts.bold, size, &card.title, width, TITLE_LINES);
+    }
+    let line_height = size * 1.2;
+    let mut y = MARGIN + 60.0 + size;
+    for line in &lines {
+        draw_text(&mut image, &fonts.bold, size, left, y, TEXT, line);
+        y += line_height;
+    }
+
+    if !card.tags.is_empty() {
+        let tags = card
+            .tags
+            .iter()
+            .map(|tag| format!("#{}", tag.replace(' ', "")))
+            .collect::<Vec<_>>()
+            .join("   ");
+        let tags = fit(&fonts.regular, 28.0, &tags, width);
+        draw_text(
+            &mut image,
+            &fonts.regular,
+            28.0,
+            left,
+            y + 16.0,
+            ACCENT,
+            &tags,
+        );
+    }
+
+    fill(
+        &mut image,
+        left as u32,
+        (bottom - 64.0) as u32,
+        width as u32,
+        2,
+        RULE,
+    );
+    let site_width = text_width(&fonts.regular, 26.0, &card.site);
+    draw_text(
+        &mut image,
+        &fonts.regular,
+        26.0,
+        left + width - site_width,
+        bottom,
+        MUTED,
+        &card.site,
+    );
+    let byline_width = width - site_width - 40.0;
+    let author = fit(&fonts.bold, 30.0, &card.author, byline_width);
+    draw_text(&mut image, &fonts.bold, 30.0, left, bottom, TEXT, &author);
+    if let Some(date) = &card.date {
+        let offset = text_width(&fonts.bold, 30.0, &author);
+        let date = fit(
+            &fonts.regular,
+            26.0,
+            &format!("  ·  {}", date),
+            byline_width - offset,
+        );
+        draw_text(
+            &mut image,
+            &fonts.regular,
+            26.0,
+            left + offset,
+            bottom,
+            MUTED,
+            &date,
+        );
+    }
+    image
+}
+
+/// A card as PNG.
+pub fn png(card: &Card) -> Result<Vec<u8>, image::ImageError> {
+    let mut out = Vec::new();
+    render(card).write_to(&mut Cursor::new(&mut out), ImageFormat::Png)?;
+    Ok(out)
+}
+
+/// The PNG of a card from `dir`, rendered and written there first when it
+/// is missing. Older renders for the same page are removed.
+pub fn cached(dir: &FsPath, slug: &str, card: &Card) -> std::io::Result<Vec<u8>> {
+    let prefix = format!("{}-{}-", card.kind.as_str(), slug);
+    let name = format!("{}{}.png", prefix, card.hash());
+    let path = dir.join(&name);
+    if let Ok(body) = std::fs::read(&path) {
+        return Ok(body);
+    }
+
+    let body = png(card).map_err(std::io::Error::other)?;
+    std::fs::create_dir_all(dir)?;
+    // Written under a temporary name so a reader never sees half a file
+    let partial = dir.join(format!(".{}.part", name));
+    std::fs::write(&partial, &body)?;
+    std::fs::rename(&partial, &path)?;
+
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let file = entry.file_name().to_string_lossy().to_string();
+        // Only this page's renders: another slug may start with this one
+        let stale = file
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".png"))
+            .is_some_and(|hash| hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()));
+        if stale && file != name {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+    Ok(body)
+}
+
+/// `MEDIA_DIR/og`.
+pub fn cache_dir() -> PathBuf {
+    crate::backend::media::media_dir().join("og")
+}
+
+pub async fn serve(Path((kind, file)): Path<(String, String)>) -> Response {
+    let Some((kind, slug)) = OgKind::parse(&kind).zip(file.strip_suffix(".png")) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let slug = slug.to_string();
+    let rendered = tokio::task::spawn_blocking(move || {
+        card(kind, &slug).map(|card| cached(&cache_dir(), &slug, &card))
+    })
+    .await;
+    match rendered {
+        Ok(Some(Ok(body))) => (
+            [
+                (header::CONTENT_TYPE, "image/png"),
+                (header::CACHE_CONTROL, "public, max-age=86400"),
+            ],
+            body,
+        )
+            .into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        _ => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
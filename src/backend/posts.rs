@@ -0,0 +1,173 @@
+//! Posts saved from the in-browser editor, their revisions and autosaves.
+//! Saved posts are stored as complete Markdown sources so they go through
+//! the same parser as files in `content/posts`.
+
+use crate::models::revisions::{RevisionSummary, FILE_AUTHOR};
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Bumped on every save, so the cached post set knows to reload.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// How many saves this process has made, see [`save`].
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::Acquire)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SavedPost {
+    pub slug: String,
+    pub source: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+fn row_to_post(row: &rusqlite::Row) -> rusqlite::Result<SavedPost> {
+    Ok(SavedPost {
+        slug: row.get(0)?,
+        source: row.get(1)?,
+        created_at: row.get(2)?,
+        updated_at: row.get(3)?,
+    })
+}
+
+pub fn saved(conn: &Connection) -> rusqlite::Result<Vec<SavedPost>> {
+    let mut stmt =
+        conn.prepare("SELECT slug, source, created_at, updated_at FROM posts ORDER BY slug")?;
+    let posts = stmt.query_map([], row_to_post)?.collect();
+    posts
+}
+
+pub fn get(conn: &Connection, slug: &str) -> rusqlite::Result<Option<SavedPost>> {
+    conn.query_row(
+        "SELECT slug, source, created_at, updated_at FROM posts WHERE slug = ?1",
+        [slug],
+        row_to_post,
+    )
+    .optional()
+}
+
+pub fn content_hash(source: &str) -> String {
+    Sha256::digest(source.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn add_revision(
+    conn: &Connection,
+    slug: &str,
+    source: &str,
+    author: &str,
+    now: i64,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO post_revisions (slug, source, hash, author, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![slug, source, content_hash(source), author, now],
+    )?;
+    Ok(())
+}
+
+/// Creates or replaces the post, records the new revision and drops the
+/// autosave, which the save supersedes.
+pub fn save(
+    conn: &Connection,
+    slug: &str,
+    source: &str,
+    author: &str,
+    now: i64,
+) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO posts (slug, source, created_at, updated_at) VALUES (?1, ?2, ?3, ?3)
+         ON CONFLICT(slug) DO UPDATE SET source = excluded.source, updated_at = excluded.updated_at",
+        params![slug, source, now],
+    )?;
+    add_revision(&tx, slug, source, author, now)?;
+    clear_autosave(&tx, slug)?;
+    tx.commit()?;
+    GENERATION.fetch_add(1, Ordering::AcqRel);
+    Ok(())
+}
+
+/// Keeps the file version of a post as its first revision, so the first
+/// save from the editor can be undone. Does nothing once there is history.
+pub fn record_file_revision(
+    conn: &Connection,
+    slug: &str,
+    source: &str,
+    now: i64,
+) -> rusqlite::Result<()> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM post_revisions WHERE slug = ?1",
+        [slug],
+        |row| row.get(0),
+    )?;
+    if count == 0 {
+        add_revision(conn, slug, source, FILE_AUTHOR, now)?;
+    }
+    Ok(())
+}
+
+/// Revisions of `slug`, newest first.
+pub fn revisions(conn: &Connection, slug: &str) -> rusqlite::Result<Vec<RevisionSummary>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, slug, author, created_at, hash FROM post_revisions
+         WHERE slug = ?1 ORDER BY id DESC",
+    )?;
+    let revisions = stmt
+        .query_map([slug], |row| {
+            Ok(RevisionSummary {
+                id: row.get(0)?,
+                slug: row.get(1)?,
+                author: row.get(2)?,
+                created_at: row.get(3)?,
+                hash: row.get(4)?,
+            })
+        })?
+        .collect();
+    revisions
+}
+
+pub fn revision_source(conn: &Connection, slug: &str, id: i64) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT source FROM post_revisions WHERE slug = ?1 AND id = ?2",
+        params![slug, id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+pub fn autosave(conn: &Connection, slug: &str, draft: &str, now: i64) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO post_autosaves (slug, draft, saved_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(slug) DO UPDATE SET draft = excluded.draft, saved_at = excluded.saved_at",
+        params![slug, draft, now],
+    )?;
+    Ok(())
+}
+
+/// The autosaved draft (JSON) of `slug` and when it was saved.
+pub fn autosave_for(conn: &Connection, slug: &str) -> rusqlite::Result<Option<(String, i64)>> {
+    conn.query_row(
+        "SELECT draft, saved_at FROM post_autosaves WHERE slug = ?1",
+        [slug],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .optional()
+}
+
+/// Slugs with an autosave, and when it was saved.
+pub fn autosaves(conn: &Connection) -> rusqlite::Result<Vec<(String, i64)>> {
+    let mut stmt = conn.prepare("SELECT slug, saved_at FROM post_autosaves ORDER BY slug")?;
+    let autosaves = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect();
+    autosaves
+}
+
+pub fn clear_autosave(conn: &Connection, slug: &str) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM post_autosaves WHERE slug = ?1", [slug])?;
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! Storage of post views and reactions, with a short-lived in-memory cache of
+//! the counts so post lists do not query the database for every card.
+//!
+//! Readers are stored as a hash of [`super::session::viewer_id`] for views
+//! and of [`super::session::reader_id`] for reactions, so the tables hold
+//! neither emails nor cookie values.
+
+use crate::models::reactions::{PostStats, Reaction};
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// How long cached counts are served before they are read again. Views and
+/// reactions made through this process update the cache right away, so this
+/// only bounds how stale counts from other processes can get.
+pub const CACHE_TTL_SECS: i64 = 60;
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// Counts per post slug, without the reader specific `mine`.
+pub struct StatsCache {
+    ttl: i64,
+    entries: Mutex<HashMap<String, (i64, PostStats)>>,
+}
+
+impl StatsCache {
+    pub fn new(ttl: i64) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, (i64, PostStats)>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The cached counts of `slug`, or those returned by `load` when there
+    /// are none younger than the TTL. Empty counts are not kept, so requests
+    /// for made up slugs cannot fill the cache.
+    pub fn get_or_load<E>(
+        &self,
+        slug: &str,
+        now: i64,
+        load: impl FnOnce() -> Result<PostStats, E>,
+    ) -> Result<PostStats, E> {
+        if let Some((loaded_at, stats)) = self.entries().get(slug) {
+            if now < loaded_at + self.ttl {
+                return Ok(stats.clone());
+            }
+        }
+        let stats = load()?;
+        if stats.views > 0 || !stats.reactions.is_empty() {
+            self.entries()
+                .insert(slug.to_string(), (now, stats.clone()));
+        } else {
+            self.invalidate(slug);
+        }
+        Ok(stats)
+    }
+
+    /// Counts a new view in the cached counts of `slug`, if there are any.
+    pub fn add_view(&self, slug: &str) {
+        if let Some((_, stats)) = self.entries().get_mut(slug) {
+            stats.views += 1;
+        }
+    }
+
+    pub fn invalidate(&self, slug: &str) {
+        self.entries().remove(slug);
+    }
+}
+
+fn cache() -> &'static StatsCache {
+    static CACHE: std::sync::OnceLock<StatsCache> = std::sync::OnceLock::new();
+    CACHE.get_or_init(|| StatsCache::new(CACHE_TTL_SECS))
+}
+
+/// The key a reader is stored under.
+pub fn reader_key(reader_id: &str) -> String {
+    Sha256::digest(reader_id.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Counts a view of `slug` by `reader`, at most once per day. Returns
+/// whether the view was new.
+pub fn record_view(
+    conn: &Connection,
+    slug: &str,
+    reader: &str,
+    now: i64,
+) -> rusqlite::Result<bool> {
+    let added = conn.execute(
+        "INSERT OR IGNORE INTO post_views (post_slug, reader, day) VALUES (?1, ?2, ?3)",
+        params![slug, reader, now.div_euclid(DAY_SECS)],
+    )? > 0;
+    if added {
+        cache().add_view(slug);
+    }
+    Ok(added)
+}
+
+/// Sets the reaction of `reader` to `slug`, replacing an earlier one, or
+/// removes it when `reaction` is `None`.
+pub fn set_reaction(
+    conn: &Connection,
+    slug: &str,
+    reader: &str,
+    reaction: Option<Reaction>,
+    now: i64,
+) -> rusqlite::Result<()> {
+    match reaction {
+        Some(reaction) => conn.execute(
+            "INSERT INTO post_reactions (post_slug, reader, reaction, created_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (post_slug, reader)
+             DO UPDATE SET reaction = excluded.reaction, created_at = excluded.created_at",
+            params![slug, reader, reaction.as_str(), now],
+        )?,
+        None => conn.execute(
+            "DELETE FROM post_reactions WHERE post_slug = ?1 AND reader = ?2",
+            params![slug, reader],
+        )?,
+    };
+    cache().invalidate(slug);
+    Ok(())
+}
+
+/// The reaction of `reader` to `slug`.
+pub fn reaction_of(
+    conn: &Connection,
+    slug: &str,
+    reader: &str,
+) -> rusqlite::Result<Option<Reaction>> {
+    let reaction: Option<String> = conn
+        .query_row(
+            "SELECT reaction FROM post_reactions WHERE post_slug = ?1 AND reader = ?2",
+            params![slug, reader],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(reaction.as_deref().and_then(Reaction::parse))
+}
+
+/// The counts of `slug` read from the database.
+pub fn load_stats(conn: &Connection, slug: &str) -> rusqlite::Result<PostStats> {
+    let views: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM post_views WHERE post_slug = ?1",
+        [slug],
+        |row| row.get(0),
+    )?;
+    let mut stmt = conn.prepare(
+        "SELECT reaction, COUNT(*) FROM post_reactions WHERE post_slug = ?1 GROUP BY reaction",
+    )?;
+    let counts: HashMap<String, i64> = stmt
+        .query_map([slug], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(PostStats {
+        slug: slug.to_string(),
+        views: views as u64,
+        reactions: Reaction::ALL
+            .into_iter()
+            .filter_map(|reaction| {
+                counts
+                    .get(reaction.as_str())
+                    .map(|count| (reaction, *count as u64))
+            })
+            .collect(),
+        mine: None,
+    })
+}
+
+/// The counts of `slug`, from the cache when possible.
+pub fn stats(conn: &Connection, slug: &str) -> rusqlite::Result<PostStats> {
+    cache().get_or_load(slug, super::db::now(), || load_stats(conn, slug))
+}
+
+/// [`stats`] with the reaction of `reader` filled in.
+pub fn stats_for(conn: &Connection, slug: &str, reader: &str) -> rusqlite::Result<PostStats> {
+    Ok(PostStats {
+        mine: reaction_of(conn, slug, reader)?,
+        ..stats(conn, slug)?
+    })
+}
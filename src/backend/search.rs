@@ -0,0 +1,92 @@
+//! The FTS5 search index. Content lives in files, so the index is a cache:
+//! it is rebuilt whenever the indexed documents change, detected by a
+//! fingerprint stored next to it.
+
+use crate::models::search::{marked_html, SearchDocument, SearchKind, SearchResult};
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+fn fingerprint(documents: &[SearchDocument]) -> String {
+    let mut hasher = Sha256::new();
+    for document in documents {
+        for field in [
+            document.kind.as_str(),
+            &document.path,
+            &document.title,
+            &document.body,
+        ] {
+            hasher.update((field.len() as u64).to_le_bytes());
+            hasher.update(field.as_bytes());
+        }
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Rebuilds the index if `documents` differ from what was indexed last.
+/// Returns whether it was rebuilt.
+pub fn ensure_index(conn: &Connection, documents: &[SearchDocument]) -> rusqlite::Result<bool> {
+    let fingerprint = fingerprint(documents);
+    let indexed: Option<String> = conn
+        .query_row(
+            "SELECT value FROM search_meta WHERE key = 'fingerprint'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if indexed.as_deref() == Some(fingerprint.as_str()) {
+        return Ok(false);
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM search_index", [])?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO search_index (kind, path, title, body) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for document in documents {
+            insert.execute(params![
+                document.kind.as_str(),
+                document.path,
+                document.title,
+                document.body
+            ])?;
+        }
+    }
+    tx.execute(
+        "INSERT INTO search_meta (key, value) VALUES ('fingerprint', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![fingerprint],
+    )?;
+    tx.commit()?;
+    Ok(true)
+}
+
+/// Runs an FTS5 `query` (see `models::search::fts_query`), best matches
+/// first. Title matches weigh ten times as much as body matches.
+pub fn search(conn: &Connection, query: &str, limit: usize) -> rusqlite::Result<Vec<SearchResult>> {
+    let mut statement = conn.prepare(
+        "SELECT kind, path,
+                highlight(search_index, 2, char(2), char(3)),
+                snippet(search_index, 3, char(2), char(3), '…', 24)
+         FROM search_index
+         WHERE search_index MATCH ?1
+         ORDER BY bm25(search_index, 0.0, 0.0, 10.0, 1.0)
+         LIMIT ?2",
+    )?;
+    let rows = statement.query_map(params![query, limit as i64], |row| {
+        let kind: String = row.get(0)?;
+        let title: String = row.get(2)?;
+        let snippet: String = row.get(3)?;
+        Ok(SearchResult {
+            kind: SearchKind::parse(&kind).unwrap_or(SearchKind::Post),
+            path: row.get(1)?,
+            title_html: marked_html(&title),
+            snippet_html: marked_html(&snippet),
+        })
+    })?;
+    rows.collect()
+}
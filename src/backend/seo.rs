@@ -0,0 +1,43 @@
+use crate::models::blog::public_posts;
+use crate::models::cv::master;
+use crate::models::sitemap::{entries, robots_txt, sitemap_xml};
+use dioxus::server::axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+pub async fn sitemap() -> Response {
+    let posts = match public_posts() {
+        Ok(posts) => posts,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let variants: Vec<String> = master()
+        .variants
+        .iter()
+        .filter(|v| v.public)
+        .map(|v| v.slug.clone())
+        .collect();
+
+    (
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        sitemap_xml(&super::site_url(), &entries(&posts, &variants)),
+    )
+        .into_response()
+}
+
+/// `ROBOTS_DISALLOW_ALL=true` blocks all crawlers; rules in the file named
+/// by `ROBOTS_EXTRA` (e.g. for specific user agents) are appended.
+pub async fn robots() -> Response {
+    let disallow_all = std::env::var("ROBOTS_DISALLOW_ALL")
+        .map(|value| matches!(value.to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    let extra = std::env::var("ROBOTS_EXTRA")
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok());
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        robots_txt(&super::site_url(), disallow_all, extra.as_deref()),
+    )
+        .into_response()
+}
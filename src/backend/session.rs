@@ -0,0 +1,298 @@
+//! Signed session cookies, so server functions know who is logged in.
+
+use crate::models::AuthUser;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use dioxus::fullstack::http::{header, Extensions, HeaderMap, HeaderValue};
+use dioxus::fullstack::FullstackContext;
+use dioxus::prelude::ServerFnError;
+use dioxus::server::axum::extract::ConnectInfo;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::net::{IpAddr, SocketAddr};
+
+const COOKIE_NAME: &str = "cv_session";
+const SESSION_TTL: i64 = 30 * 24 * 60 * 60;
+const READER_COOKIE: &str = "cv_reader";
+const READER_TTL: i64 = 365 * 24 * 60 * 60;
+
+/// Key used to sign session cookies and share tokens. Set `SESSION_SECRET` in
+/// production; without it a random key is generated and sessions do not
+/// survive a restart.
+pub fn secret() -> &'static [u8] {
+    static SECRET: std::sync::OnceLock<Vec<u8>> = std::sync::OnceLock::new();
+    SECRET.get_or_init(|| match std::env::var("SESSION_SECRET") {
+        Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+        _ => {
+            dioxus::logger::tracing::warn!("SESSION_SECRET is not set, using a random key");
+            rand::random::<[u8; 32]>().to_vec()
+        }
+    })
+}
+
+pub fn sign(secret: &[u8], payload: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(payload.as_bytes());
+    URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+}
+
+pub fn verify(secret: &[u8], payload: &str, signature: &str) -> bool {
+    let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(payload.as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Encodes `email` into a cookie value valid until `expires_at`.
+pub fn encode(secret: &[u8], email: &str, expires_at: i64) -> String {
+    let payload = format!("{}|{}", URL_SAFE_NO_PAD.encode(email), expires_at);
+    format!("{}.{}", payload, sign(secret, &payload))
+}
+
+/// Returns the email stored in a cookie value if the signature is valid and
+/// the session has not expired.
+pub fn decode(secret: &[u8], value: &str, now: i64) -> Option<String> {
+    let (payload, signature) = value.rsplit_once('.')?;
+    if !verify(secret, payload, signature) {
+        return None;
+    }
+    let (email, expires_at) = payload.split_once('|')?;
+    if expires_at.parse::<i64>().ok()? < now {
+        return None;
+    }
+    String::from_utf8(URL_SAFE_NO_PAD.decode(email).ok()?).ok()
+}
+
+/// Finds a cookie by name in a `Cookie` request header.
+pub fn cookie_value<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    header.split(';').find_map(|pair| {
+        let (key, value) = pair.trim().split_once('=')?;
+        (key == name).then_some(value)
+    })
+}
+
+/// Reads a request header of the current server function or SSR request.
+pub fn request_header(name: header::HeaderName) -> Option<String> {
+    let ctx = FullstackContext::current()?;
+    let parts = ctx.parts_mut();
+    parts
+        .headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Cookie attributes shared by every cookie the site sets. `Secure` is left
+/// out in development, which runs over plain HTTP.
+fn cookie_attributes() -> &'static str {
+    if is_dev() {
+        "Path=/; HttpOnly; SameSite=Lax"
+    } else {
+        "Path=/; HttpOnly; SameSite=Lax; Secure"
+    }
+}
+
+fn set_cookie(value: String) {
+    if let (Some(ctx), Ok(value)) = (FullstackContext::current(), HeaderValue::from_str(&value)) {
+        ctx.add_response_header(header::SET_COOKIE, value);
+    }
+}
+
+/// Starts a session for `user` by setting the session cookie on the response.
+pub fn start(user: &AuthUser) {
+    let value = encode(secret(), &user.email, super::db::now() + SESSION_TTL);
+    set_cookie(format!(
+        "{}={}; {}; Max-Age={}",
+        COOKIE_NAME,
+        value,
+        cookie_attributes(),
+        SESSION_TTL
+    ));
+}
+
+pub fn end() {
+    set_cookie(format!(
+        "{}=; {}; Max-Age=0",
+        COOKIE_NAME,
+        cookie_attributes()
+    ));
+}
+
+/// The user of the current request, if their session cookie is valid.
+pub fn current_user() -> Option<AuthUser> {
+    let cookies = request_header(header::COOKIE)?;
+    let value = cookie_value(&cookies, COOKIE_NAME)?;
+    let email = decode(secret(), value, super::db::now())?;
+    Some(AuthUser { email })
+}
+
+/// Whether `TRUSTED_PROXY` is set to `true`, meaning the site runs behind a
+/// reverse proxy whose forwarding headers can be believed.
+pub fn trusts_proxy() -> bool {
+    let trusted = std::env::var("TRUSTED_PROXY")
+        .unwrap_or_default()
+        .to_lowercase();
+    trusted == "true" || trusted == "1"
+}
+
+/// Address of the client of the current server function or SSR request
+/// (see [`client_ip_from`]).
+pub fn client_ip() -> Option<IpAddr> {
+    let ctx = FullstackContext::current()?;
+    let parts = ctx.parts_mut();
+    client_ip_from(&parts.headers, &parts.extensions)
+}
+
+/// Address of the client of a request. Behind a trusted proxy this is the
+/// last `X-Forwarded-For` entry (the one the proxy added) or `X-Real-IP`;
+/// otherwise, or when the proxy sent neither, it is the peer address of the
+/// connection. `None` only when the server was not started with connection
+/// info.
+pub fn client_ip_from(headers: &HeaderMap, extensions: &Extensions) -> Option<IpAddr> {
+    let peer = extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if !trusts_proxy() {
+        return peer;
+    }
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|v: &HeaderValue| v.to_str().ok())
+    };
+    header("x-forwarded-for")
+        .and_then(|forwarded| {
+            forwarded
+                .rsplit(',')
+                .map(str::trim)
+                .find(|ip| !ip.is_empty())
+        })
+        .or_else(|| header("x-real-ip").map(str::trim))
+        .and_then(|ip| ip.parse().ok())
+        .or(peer)
+}
+
+/// The anonymous reader key for a client address and user agent on `day`
+/// (days since the epoch). Salted with the session secret, so it cannot be
+/// reversed into an address, and it changes every day.
+pub fn visitor_id(secret: &[u8], ip: &str, user_agent: &str, day: i64) -> String {
+    sign(secret, &format!("{}|{}|{}", ip, user_agent, day))
+}
+
+/// Who is viewing: `user:<email>` when logged in, otherwise `visitor:<key>`
+/// from [`visitor_id`]. Views are not told apart by cookie, so dropping
+/// cookies does not count a new view.
+pub fn viewer_id() -> String {
+    if let Some(user) = current_user() {
+        return format!("user:{}", user.email.to_lowercase());
+    }
+    let user_agent = request_header(header::USER_AGENT).unwrap_or_default();
+    let day = super::db::now().div_euclid(24 * 60 * 60);
+    format!(
+        "visitor:{}",
+        visitor_id(
+            secret(),
+            &client_ip().map(|ip| ip.to_string()).unwrap_or_default(),
+            &user_agent,
+            day
+        )
+    )
+}
+
+/// Signs an anonymous reader id into a reader cookie value.
+pub fn encode_reader(secret: &[u8], id: &str) -> String {
+    format!("{}.{}", id, sign(secret, &format!("reader|{}", id)))
+}
+
+/// The reader id stored in a reader cookie value, if the signature is valid.
+pub fn decode_reader(secret: &[u8], value: &str) -> Option<String> {
+    let (id, signature) = value.rsplit_once('.')?;
+    verify(secret, &format!("reader|{}", id), signature).then(|| id.to_string())
+}
+
+/// Who is reacting: `user:<email>` when logged in, otherwise `reader:<id>`
+/// from the signed reader cookie. `None` for anonymous readers without one.
+pub fn reader_id() -> Option<String> {
+    if let Some(user) = current_user() {
+        return Some(format!("user:{}", user.email.to_lowercase()));
+    }
+    let cookies = request_header(header::COOKIE)?;
+    let id = decode_reader(secret(), cookie_value(&cookies, READER_COOKIE)?)?;
+    Some(format!("reader:{}", id))
+}
+
+/// [`reader_id`], setting a new reader cookie for anonymous readers who do
+/// not have one yet.
+pub fn start_reader() -> String {
+    if let Some(reader) = reader_id() {
+        return reader;
+    }
+    let id: String = rand::random::<[u8; 16]>()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    set_cookie(format!(
+        "{}={}; {}; Max-Age={}",
+        READER_COOKIE,
+        encode_reader(secret(), &id),
+        cookie_attributes(),
+        READER_TTL
+    ));
+    format!("reader:{}", id)
+}
+
+/// Account used by the mock login, which only exists in development.
+pub const DEV_USER_EMAIL: &str = "dev.user@example.com";
+
+/// Whether `APP_ENV` is explicitly set to `development` (or `dev`).
+pub fn is_dev() -> bool {
+    let app_env = std::env::var("APP_ENV").unwrap_or_default().to_lowercase();
+    app_env == "development" || app_env == "dev"
+}
+
+/// Whether `email` belongs to the site owner (`OWNER_EMAIL`). Without an owner
+/// configured nobody is the owner, except the mock development account when
+/// `APP_ENV` is explicitly `development`.
+pub fn is_owner(email: &str) -> bool {
+    match std::env::var("OWNER_EMAIL") {
+        Ok(owner) if !owner.is_empty() && !owner.starts_with("your_") => {
+            owner.eq_ignore_ascii_case(email)
+        }
+        _ => is_dev() && email.eq_ignore_ascii_case(DEV_USER_EMAIL),
+    }
+}
+
+/// Whether `email` may see and edit unpublished posts: the owner and anyone
+/// listed in `EDITOR_EMAILS` (comma separated).
+pub fn is_editor(email: &str) -> bool {
+    is_owner(email)
+        || std::env::var("EDITOR_EMAILS").is_ok_and(|editors| {
+            editors
+                .split(',')
+                .any(|editor| editor.trim().eq_ignore_ascii_case(email))
+        })
+}
+
+pub fn require_user() -> Result<AuthUser, ServerFnError> {
+    current_user().ok_or_else(|| ServerFnError::new("Not authenticated"))
+}
+
+pub fn require_editor() -> Result<AuthUser, ServerFnError> {
+    let user = require_user()?;
+    if is_editor(&user.email) {
+        Ok(user)
+    } else {
+        Err(ServerFnError::new("Only editors can do this"))
+    }
+}
+
+pub fn require_owner() -> Result<AuthUser, ServerFnError> {
+    let user = require_user()?;
+    if is_owner(&user.email) {
+        Ok(user)
+    } else {
+        Err(ServerFnError::new("Only the site owner can do this"))
+    }
+}
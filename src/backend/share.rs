@@ -0,0 +1,160 @@
+//! Share links: signed, expiring tokens that unlock one CV variant.
+//!
+//! A token is `<id>.<expires_at>.<signature>`. The signature lets us reject
+//! forged or tampered tokens before touching the database, which holds the
+//! variant, revocation flag and view counter.
+
+use super::session::{sign, verify};
+use crate::models::share::ShareLink;
+use rusqlite::{params, Connection, OptionalExtension};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShareError {
+    Invalid,
+    Expired,
+    Revoked,
+    Database(String),
+}
+
+impl std::fmt::Display for ShareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareError::Invalid => write!(f, "This share link is not valid"),
+            ShareError::Expired => write!(f, "This share link has expired"),
+            ShareError::Revoked => write!(f, "This share link has been revoked"),
+            ShareError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for ShareError {
+    fn from(e: rusqlite::Error) -> Self {
+        ShareError::Database(e.to_string())
+    }
+}
+
+fn token(secret: &[u8], id: &str, expires_at: i64) -> String {
+    let payload = format!("{}.{}", id, expires_at);
+    format!(
+        "{}.{}",
+        payload,
+        sign(secret, &format!("share:{}", payload))
+    )
+}
+
+fn row_to_link(secret: &[u8], row: &rusqlite::Row) -> rusqlite::Result<ShareLink> {
+    let id: String = row.get(0)?;
+    let expires_at: i64 = row.get(4)?;
+    Ok(ShareLink {
+        token: token(secret, &id, expires_at),
+        id,
+        variant: row.get(1)?,
+        label: row.get(2)?,
+        created_at: row.get(3)?,
+        expires_at,
+        revoked: row.get(5)?,
+        views: row.get(6)?,
+    })
+}
+
+pub fn create(
+    conn: &Connection,
+    secret: &[u8],
+    variant: &str,
+    label: &str,
+    now: i64,
+    expires_at: i64,
+) -> Result<ShareLink, ShareError> {
+    let id = hex(&rand::random::<[u8; 12]>());
+    conn.execute(
+        "INSERT INTO share_links (id, variant, label, created_at, expires_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![id, variant, label, now, expires_at],
+    )?;
+    Ok(ShareLink {
+        token: token(secret, &id, expires_at),
+        id,
+        variant: variant.to_string(),
+        label: label.to_string(),
+        created_at: now,
+        expires_at,
+        revoked: false,
+        views: 0,
+    })
+}
+
+pub fn list(conn: &Connection, secret: &[u8]) -> Result<Vec<ShareLink>, ShareError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, variant, label, created_at, expires_at, revoked, views
+         FROM share_links ORDER BY created_at DESC",
+    )?;
+    let links = stmt
+        .query_map([], |row| row_to_link(secret, row))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(links)
+}
+
+pub fn revoke(conn: &Connection, id: &str) -> Result<(), ShareError> {
+    let updated = conn.execute("UPDATE share_links SET revoked = 1 WHERE id = ?1", [id])?;
+    if updated == 0 {
+        return Err(ShareError::Invalid);
+    }
+    Ok(())
+}
+
+/// Checks a token and records the view. Returns the unlocked variant.
+pub fn open(
+    conn: &Connection,
+    secret: &[u8],
+    token: &str,
+    now: i64,
+    referrer: Option<&str>,
+) -> Result<String, ShareError> {
+    let (payload, signature) = token.rsplit_once('.').ok_or(ShareError::Invalid)?;
+    if !verify(secret, &format!("share:{}", payload), signature) {
+        return Err(ShareError::Invalid);
+    }
+    let (id, expires_at) = payload.split_once('.').ok_or(ShareError::Invalid)?;
+    let expires_at: i64 = expires_at.parse().map_err(|_| ShareError::Invalid)?;
+
+    let (variant, revoked): (String, bool) = conn
+        .query_row(
+            "SELECT variant, revoked FROM share_links WHERE id = ?1 AND expires_at = ?2",
+            params![id, expires_at],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .ok_or(ShareError::Invalid)?;
+
+    if revoked {
+        return Err(ShareError::Revoked);
+    }
+    if expires_at < now {
+        return Err(ShareError::Expired);
+    }
+
+    conn.execute(
+        "UPDATE share_links SET views = views + 1 WHERE id = ?1",
+        [id],
+    )?;
+    conn.execute(
+        "INSERT INTO share_views (link_id, viewed_at, referrer) VALUES (?1, ?2, ?3)",
+        params![id, now, referrer],
+    )?;
+
+    Ok(variant)
+}
+
+/// Logged views of a link as `(viewed_at, referrer)`, newest first.
+pub fn views(conn: &Connection, id: &str) -> Result<Vec<(i64, Option<String>)>, ShareError> {
+    let mut stmt = conn.prepare(
+        "SELECT viewed_at, referrer FROM share_views WHERE link_id = ?1 ORDER BY id DESC",
+    )?;
+    let views = stmt
+        .query_map([id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(views)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,48 @@
+//! In-memory rate limits for endpoints anyone can call, keyed by client
+//! address or by what is being requested.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Keys tracked before idle ones are dropped, so a flood of distinct keys
+/// cannot grow the map without bound.
+const MAX_KEYS: usize = 10_000;
+
+/// Allows at most `limit` hits per key in any `window` seconds.
+pub struct Throttle {
+    limit: usize,
+    window: i64,
+    hits: Mutex<HashMap<String, VecDeque<i64>>>,
+}
+
+impl Throttle {
+    pub fn new(limit: usize, window: i64) -> Self {
+        Self {
+            limit,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records a hit for `key` at `now`, unless the key is over its limit.
+    /// Returns whether the hit is allowed.
+    pub fn allow(&self, key: &str, now: i64) -> bool {
+        let mut hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+        let since = now - self.window;
+        if hits.len() >= MAX_KEYS && !hits.contains_key(key) {
+            hits.retain(|_, times| times.back().is_some_and(|&t| t > since));
+            if hits.len() >= MAX_KEYS {
+                return false;
+            }
+        }
+        let times = hits.entry(key.to_string()).or_default();
+        while times.front().is_some_and(|&t| t <= since) {
+            times.pop_front();
+        }
+        if times.len() >= self.limit {
+            return false;
+        }
+        times.push_back(now);
+        true
+    }
+}
@@ -0,0 +1,630 @@
+//! Webmention storage, the `/webmention` endpoint and the sender.
+//!
+//! Incoming mentions are stored as pending and answered with `202 Accepted`;
+//! the source is fetched afterwards to check it really links to the post.
+//! Outgoing mentions are sent once for each post that becomes listed, to
+//! every page it links to that advertises an endpoint.
+
+use super::site_url;
+use super::throttle::Throttle;
+use crate::models::blog::Post;
+use crate::models::webmentions::{
+    discover_endpoint, links, links_to, page_title, parse_http_url, Webmention, WebmentionStatus,
+};
+use dioxus::server::axum::{
+    http::{Extensions, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Form,
+};
+use reqwest::Url;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+/// Most bytes read of a fetched page.
+pub const MAX_FETCH_BYTES: usize = 1024 * 1024;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+/// Most redirects followed for one fetch.
+const MAX_REDIRECTS: usize = 5;
+/// Mentions accepted per source host, and per client address, in
+/// [`RATE_WINDOW_SECS`]. Each one starts an outgoing fetch.
+const SOURCE_RATE_LIMIT: usize = 5;
+const CLIENT_RATE_LIMIT: usize = 20;
+const RATE_WINDOW_SECS: i64 = 60 * 60;
+/// How often listed posts are checked for mentions to send, which is when
+/// scheduled posts get theirs.
+const SEND_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebmentionError {
+    InvalidUrl,
+    SameUrl,
+    /// The target is not a post on this site.
+    UnknownTarget,
+    Database(String),
+}
+
+impl std::fmt::Display for WebmentionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebmentionError::InvalidUrl => write!(f, "source and target must be http(s) URLs"),
+            WebmentionError::SameUrl => write!(f, "source and target must differ"),
+            WebmentionError::UnknownTarget => write!(f, "target is not a post on this site"),
+            WebmentionError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for WebmentionError {
+    fn from(e: rusqlite::Error) -> Self {
+        WebmentionError::Database(e.to_string())
+    }
+}
+
+/// A row of the `webmentions` table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredWebmention {
+    pub id: i64,
+    pub source: String,
+    pub target: String,
+    pub post_slug: String,
+    pub status: WebmentionStatus,
+    pub title: String,
+    pub received_at: i64,
+    pub verified_at: Option<i64>,
+}
+
+const COLUMNS: &str = "id, source, target, post_slug, status, title, received_at, verified_at";
+
+fn row_to_webmention(row: &rusqlite::Row) -> rusqlite::Result<StoredWebmention> {
+    let status: String = row.get(4)?;
+    Ok(StoredWebmention {
+        id: row.get(0)?,
+        source: row.get(1)?,
+        target: row.get(2)?,
+        post_slug: row.get(3)?,
+        status: WebmentionStatus::parse(&status).unwrap_or_default(),
+        title: row.get(5)?,
+        received_at: row.get(6)?,
+        verified_at: row.get(7)?,
+    })
+}
+
+/// The post slug `target` points at, when it is `<site_url>/blog/<slug>`.
+pub fn target_slug(site_url: &str, target: &Url) -> Option<String> {
+    let site = parse_http_url(site_url)?;
+    if target.origin() != site.origin() {
+        return None;
+    }
+    let prefix = format!("{}/blog/", site.path().trim_end_matches('/'));
+    let slug = target.path().strip_prefix(&prefix)?.trim_end_matches('/');
+    (!slug.is_empty() && !slug.contains('/')).then(|| slug.to_string())
+}
+
+/// Checks an incoming mention and returns the parsed source and target and
+/// the slug of the mentioned post. `posts` are the posts that can be
+/// mentioned.
+pub fn validate(
+    source: &str,
+    target: &str,
+    site_url: &str,
+    posts: &[Post],
+) -> Result<(Url, Url, String), WebmentionError> {
+    let (Some(source), Some(target)) = (parse_http_url(source), parse_http_url(target)) else {
+        return Err(WebmentionError::InvalidUrl);
+    };
+    if source == target {
+        return Err(WebmentionError::SameUrl);
+    }
+    let slug = target_slug(site_url, &target)
+        .filter(|slug| posts.iter().any(|post| &post.meta.slug == slug))
+        .ok_or(WebmentionError::UnknownTarget)?;
+    Ok((source, target, slug))
+}
+
+/// Stores a received mention as pending and returns its id. A repeated
+/// mention from the same source to the same target is checked again.
+pub fn receive(
+    conn: &Connection,
+    source: &str,
+    target: &str,
+    slug: &str,
+    now: i64,
+) -> Result<i64, WebmentionError> {
+    Ok(conn.query_row(
+        "INSERT INTO webmentions (source, target, post_slug, status, title, received_at)
+         VALUES (?1, ?2, ?3, 'pending', '', ?4)
+         ON CONFLICT (source, target) DO UPDATE SET received_at = excluded.received_at
+         RETURNING id",
+        params![source, target, slug, now],
+        |row| row.get(0),
+    )?)
+}
+
+pub fn get(conn: &Connection, id: i64) -> Result<Option<StoredWebmention>, WebmentionError> {
+    Ok(conn
+        .query_row(
+            &format!("SELECT {} FROM webmentions WHERE id = ?1", COLUMNS),
+            [id],
+            row_to_webmention,
+        )
+        .optional()?)
+}
+
+/// Records the outcome of checking a mention. `title` is the source's page
+/// title when it links to the post, `None` when it does not.
+pub fn set_verified(
+    conn: &Connection,
+    id: i64,
+    title: Option<&str>,
+    now: i64,
+) -> Result<(), WebmentionError> {
+    match title {
+        Some(title) => conn.execute(
+            "UPDATE webmentions SET status = 'verified', title = ?1, verified_at = ?2 WHERE id = ?3",
+            params![title, now, id],
+        )?,
+        None => conn.execute(
+            "UPDATE webmentions SET status = 'rejected', verified_at = ?1 WHERE id = ?2",
+            params![now, id],
+        )?,
+    };
+    Ok(())
+}
+
+/// Verified mentions of a post, oldest first.
+pub fn for_post(conn: &Connection, slug: &str) -> Result<Vec<Webmention>, WebmentionError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM webmentions WHERE post_slug = ?1 AND status = 'verified' ORDER BY verified_at, id",
+        COLUMNS
+    ))?;
+    let mentions = stmt
+        .query_map([slug], row_to_webmention)?
+        .map(|row| {
+            row.map(|stored| Webmention {
+                source: stored.source,
+                title: stored.title,
+                verified_at: stored.verified_at.unwrap_or(stored.received_at),
+            })
+        })
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(mentions)
+}
+
+/// Marks a post as handled by the sender. Returns false when it already
+/// was, so each post's mentions are sent once.
+pub fn mark_handled(conn: &Connection, slug: &str, now: i64) -> Result<bool, WebmentionError> {
+    Ok(conn.execute(
+        "INSERT OR IGNORE INTO webmention_posts (post_slug, handled_at) VALUES (?1, ?2)",
+        params![slug, now],
+    )? > 0)
+}
+
+/// Records the result of sending a mention of `target` from a post.
+pub fn log_send(
+    conn: &Connection,
+    slug: &str,
+    target: &str,
+    result: &str,
+    now: i64,
+) -> Result<(), WebmentionError> {
+    conn.execute(
+        "INSERT INTO webmention_sends (post_slug, target, result, sent_at) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT (post_slug, target) DO UPDATE SET result = excluded.result, sent_at = excluded.sent_at",
+        params![slug, target, result, now],
+    )?;
+    Ok(())
+}
+
+/// The posts among `listed` the sender has not handled yet. The first time
+/// this runs every listed post is taken as handled, so turning webmentions
+/// on does not notify every site the archive ever linked to.
+pub fn unsent<'a>(
+    conn: &Connection,
+    listed: &'a [Post],
+    now: i64,
+) -> Result<Vec<&'a Post>, WebmentionError> {
+    let started: Option<i64> = conn
+        .query_row(
+            "SELECT value FROM webmention_meta WHERE key = 'started_at'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?
+        .and_then(|value| value.parse().ok());
+    if started.is_none() {
+        for post in listed {
+            mark_handled(conn, &post.meta.slug, now)?;
+        }
+        conn.execute(
+            "INSERT OR REPLACE INTO webmention_meta (key, value) VALUES ('started_at', ?1)",
+            [now.to_string()],
+        )?;
+        return Ok(Vec::new());
+    }
+    let mut unsent = Vec::new();
+    for post in listed {
+        let handled: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM webmention_posts WHERE post_slug = ?1)",
+            [&post.meta.slug],
+            |row| row.get(0),
+        )?;
+        if !handled {
+            unsent.push(post);
+        }
+    }
+    Ok(unsent)
+}
+
+/// The external pages a post at `source` links to, each once.
+pub fn outgoing_links(post: &Post, source: &Url) -> Vec<Url> {
+    let mut targets: Vec<Url> = Vec::new();
+    for mut link in links(&post.html(), source) {
+        link.set_fragment(None);
+        if matches!(link.scheme(), "http" | "https")
+            && link.origin() != source.origin()
+            && !targets.contains(&link)
+        {
+            targets.push(link);
+        }
+    }
+    targets
+}
+
+/// Whether fetching `url` stays off the local network, as far as the URL
+/// tells: literal addresses and `localhost` are caught here, other names
+/// when they are resolved (see [`PublicResolver`]).
+pub fn is_public(url: &Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    match host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    {
+        Ok(ip) => is_public_ip(ip),
+        Err(_) => {
+            let host = host.trim_end_matches('.').to_ascii_lowercase();
+            host != "localhost" && !host.ends_with(".localhost")
+        }
+    }
+}
+
+/// Whether `ip` is reachable on the public internet: not loopback, private,
+/// link local, shared (CGNAT), multicast or otherwise reserved.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                // "This network", shared address space (CGNAT), IETF
+                // protocol assignments, benchmarking and reserved
+                || a == 0
+                || (a == 100 && (b & 0xc0) == 64)
+                || ip.octets()[..3] == [192, 0, 0]
+                || (a == 198 && (b & 0xfe) == 18)
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // Unique local and link local
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+                && ip
+                    .to_ipv4_mapped()
+                    .is_none_or(|ip| is_public_ip(IpAddr::V4(ip)))
+                // NAT64 addresses stand for the IPv4 address in their tail
+                && (ip.segments()[..6] != [0x64, 0xff9b, 0, 0, 0, 0] || {
+                    let [.., a, b, c, d] = ip.octets();
+                    is_public_ip(IpAddr::V4(Ipv4Addr::new(a, b, c, d)))
+                })
+        }
+    }
+}
+
+/// Resolves host names with the system resolver but only hands out public
+/// addresses, so a name pointing into the local network cannot be fetched.
+/// Checking at connection time also covers names that change between a
+/// check and the request.
+pub struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", host).into());
+            }
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// A client builder for fetching URLs that come from outside: names only
+/// resolve to public addresses, and every redirect is checked with
+/// [`is_public`] before it is followed.
+pub fn public_client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .no_proxy()
+        .dns_resolver(PublicResolver)
+        .redirect(reqwest::redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if !is_public(attempt.url()) {
+                attempt.error("redirect to a non-public URL")
+            } else {
+                attempt.follow()
+            }
+        }))
+}
+
+/// The client used for every webmention request.
+pub fn client() -> reqwest::Client {
+    static CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            public_client_builder()
+                .timeout(FETCH_TIMEOUT)
+                .user_agent(concat!("cv-webmention/", env!("CARGO_PKG_VERSION")))
+                .build()
+                .expect("the webmention client has a valid configuration")
+        })
+        .clone()
+}
+
+/// A fetched page: status, `Link` headers, and the body up to
+/// [`MAX_FETCH_BYTES`].
+struct Fetched {
+    status: reqwest::StatusCode,
+    links: Vec<String>,
+    body: String,
+}
+
+async fn fetch(client: &reqwest::Client, url: &Url) -> Result<Fetched, reqwest::Error> {
+    let mut response = client.get(url.clone()).send().await?;
+    let status = response.status();
+    let links = response
+        .headers()
+        .get_all(reqwest::header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .map(str::to_string)
+        .collect();
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        if body.len() >= MAX_FETCH_BYTES {
+            body.truncate(MAX_FETCH_BYTES);
+            break;
+        }
+    }
+    Ok(Fetched {
+        status,
+        links,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// Fetches `source` and returns its title (possibly empty) if it links to
+/// `target`. `None` when it does not, or the page is gone.
+pub async fn verify(
+    client: &reqwest::Client,
+    source: &Url,
+    target: &Url,
+) -> Result<Option<String>, reqwest::Error> {
+    let page = fetch(client, source).await?;
+    if !page.status.is_success() {
+        return Ok(None);
+    }
+    Ok(links_to(&page.body, source, target).then(|| page_title(&page.body).unwrap_or_default()))
+}
+
+/// Looks up the endpoint of `target` and tells it that `source` links
+/// there. Returns what happened, for the send log.
+pub async fn send(
+    client: &reqwest::Client,
+    source: &Url,
+    target: &Url,
+    public_only: bool,
+) -> String {
+    let page = match fetch(client, target).await {
+        Ok(page) => page,
+        Err(e) => return format!("failed: {}", e),
+    };
+    let Some(endpoint) = discover_endpoint(target, &page.links, &page.body) else {
+        return "no endpoint".to_string();
+    };
+    if public_only && !is_public(&endpoint) {
+        return "endpoint not public".to_string();
+    }
+    let response = client
+        .post(endpoint)
+        .form(&[("source", source.as_str()), ("target", target.as_str())])
+        .send()
+        .await;
+    match response {
+        Ok(response) if response.status().is_success() => {
+            format!("sent ({})", response.status().as_u16())
+        }
+        Ok(response) => format!("failed: endpoint answered {}", response.status()),
+        Err(e) => format!("failed: {}", e),
+    }
+}
+
+/// Checks a received mention and records the outcome.
+async fn check(id: i64, source: Url, target: Url) {
+    let verified = match verify(&client(), &source, &target).await {
+        Ok(verified) => verified,
+        Err(e) => {
+            dioxus::logger::tracing::warn!("Could not fetch webmention source {}: {}", source, e);
+            None
+        }
+    };
+    let result = super::db::connect()
+        .map_err(WebmentionError::from)
+        .and_then(|conn| set_verified(&conn, id, verified.as_deref(), super::db::now()));
+    if let Err(e) = result {
+        dioxus::logger::tracing::warn!("Could not store webmention {}: {}", id, e);
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MentionForm {
+    source: String,
+    target: String,
+}
+
+/// Whether another mention from `source`, sent by `client`, may be
+/// accepted now.
+fn within_rate_limits(source: &str, client: &str) -> bool {
+    static BY_SOURCE: std::sync::OnceLock<Throttle> = std::sync::OnceLock::new();
+    static BY_CLIENT: std::sync::OnceLock<Throttle> = std::sync::OnceLock::new();
+    let now = super::db::now();
+    let source_host = parse_http_url(source)
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .unwrap_or_default();
+    BY_CLIENT
+        .get_or_init(|| Throttle::new(CLIENT_RATE_LIMIT, RATE_WINDOW_SECS))
+        .allow(client, now)
+        && BY_SOURCE
+            .get_or_init(|| Throttle::new(SOURCE_RATE_LIMIT, RATE_WINDOW_SECS))
+            .allow(&source_host, now)
+}
+
+/// `POST /webmention`.
+pub async fn endpoint(
+    headers: HeaderMap,
+    extensions: Extensions,
+    Form(form): Form<MentionForm>,
+) -> Response {
+    let Some(client) = super::session::client_ip_from(&headers, &extensions) else {
+        return (StatusCode::BAD_REQUEST, "unknown client address").into_response();
+    };
+    if !within_rate_limits(&form.source, &client.to_string()) {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many mentions, try again later",
+        )
+            .into_response();
+    }
+    let site = site_url();
+    let now = chrono::Utc::now();
+    let valid = crate::models::blog::with_posts(|_, posts| {
+        validate(&form.source, &form.target, &site, posts).and_then(|valid| {
+            posts
+                .iter()
+                .any(|post| post.meta.slug == valid.2 && post.meta.is_reachable(now, false))
+                .then_some(valid)
+                .ok_or(WebmentionError::UnknownTarget)
+        })
+    });
+    let (source, target, slug) = match valid {
+        Ok(Ok(valid)) => valid,
+        Ok(Err(e)) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    if !is_public(&source) {
+        return (StatusCode::BAD_REQUEST, "source is not a public URL").into_response();
+    }
+    let id = match super::db::connect()
+        .map_err(WebmentionError::from)
+        .and_then(|conn| {
+            receive(
+                &conn,
+                source.as_str(),
+                target.as_str(),
+                &slug,
+                super::db::now(),
+            )
+        }) {
+        Ok(id) => id,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    tokio::spawn(check(id, source, target));
+    (StatusCode::ACCEPTED, "Accepted, the source will be checked").into_response()
+}
+
+/// Sends the mentions of posts that became listed since the last run.
+pub async fn send_new() {
+    let posts = match crate::models::blog::listed_posts() {
+        Ok(posts) => posts,
+        Err(e) => {
+            dioxus::logger::tracing::warn!("Webmentions: could not load posts: {}", e);
+            return;
+        }
+    };
+    let site = site_url();
+    // Claim the posts before sending so overlapping runs do not send twice
+    let jobs: Vec<(String, Url, Vec<Url>)> = match super::db::connect()
+        .map_err(WebmentionError::from)
+        .and_then(|conn| {
+            let now = super::db::now();
+            let mut jobs = Vec::new();
+            for post in unsent(&conn, &posts, now)? {
+                let Some(source) = parse_http_url(&format!("{}/blog/{}", site, post.meta.slug))
+                else {
+                    continue;
+                };
+                if mark_handled(&conn, &post.meta.slug, now)? {
+                    let targets = outgoing_links(post, &source);
+                    jobs.push((post.meta.slug.clone(), source, targets));
+                }
+            }
+            Ok(jobs)
+        }) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            dioxus::logger::tracing::warn!("Webmentions: {}", e);
+            return;
+        }
+    };
+
+    let client = client();
+    for (slug, source, targets) in jobs {
+        for target in targets {
+            let result = if is_public(&target) {
+                send(&client, &source, &target, true).await
+            } else {
+                "target not public".to_string()
+            };
+            let logged = super::db::connect()
+                .map_err(WebmentionError::from)
+                .and_then(|conn| {
+                    log_send(&conn, &slug, target.as_str(), &result, super::db::now())
+                });
+            if let Err(e) = logged {
+                dioxus::logger::tracing::warn!("Webmentions: {}", e);
+            }
+        }
+    }
+}
+
+/// Starts sending mentions in the background: now, and then every
+/// [`SEND_INTERVAL`]. Later calls do nothing.
+pub fn start_sender() {
+    static STARTED: std::sync::Once = std::sync::Once::new();
+    STARTED.call_once(|| {
+        tokio::spawn(async {
+            let mut interval = tokio::time::interval(SEND_INTERVAL);
+            loop {
+                interval.tick().await;
+                send_new().await;
+            }
+        });
+    });
+}
@@ -1,15 +1,75 @@
+use crate::components::TagChip;
+use crate::models::reactions::PostStats;
+use crate::Route;
 use dioxus::prelude::*;
 
 #[component]
-pub fn BlogPostCard(title: String, description: String, index: i32) -> Element {
+pub fn BlogPostCard(
+    title: String,
+    description: String,
+    index: i32,
+    /// Links the card to `/blog/<slug>` when set.
+    #[props(default)]
+    slug: Option<String>,
+    #[props(default)] date: Option<String>,
+    #[props(default)] tags: Vec<String>,
+    #[props(default)] reading_minutes: Option<usize>,
+    /// Views and reactions, shown next to the date.
+    #[props(default)]
+    stats: Option<PostStats>,
+) -> Element {
+    let details: Vec<String> = date
+        .into_iter()
+        .chain(reading_minutes.map(|minutes| format!("{} min read", minutes)))
+        .chain(stats.iter().flat_map(|stats| {
+            let views = match stats.views {
+                1 => "1 view".to_string(),
+                views => format!("{} views", views),
+            };
+            std::iter::once(views)
+                .chain((!stats.reactions.is_empty()).then(|| stats.reactions_summary()))
+        }))
+        .collect();
+
     rsx! {
         div {
             class: "bg-gray-800 p-6 rounded-xl border border-gray-700 hover:border-blue-500 transition-all cursor-pointer",
-            h2 { class: "text-2xl font-semibold mb-2", "{title}" }
+            if !details.is_empty() {
+                p { class: "text-sm text-gray-500 mb-2", "{details.join(\" · \")}" }
+            }
+            match slug.clone() {
+                Some(slug) => rsx! {
+                    Link { to: Route::BlogPost { slug },
+                        h2 { class: "text-2xl font-semibold mb-2 hover:text-blue-500 transition-colors", "{title}" }
+                    }
+                },
+                None => rsx! {
+                    h2 { class: "text-2xl font-semibold mb-2", "{title}" }
+                },
+            }
             p { class: "text-gray-400 mb-4", "{description}" }
-            div { class: "flex items-center gap-2 text-blue-500 font-medium",
-                "Read more"
-                i { class: "fas fa-arrow-right text-xs" }
+            if !tags.is_empty() {
+                div { class: "flex flex-wrap gap-2 mb-4",
+                    for tag in tags {
+                        TagChip { key: "{tag}", name: tag.clone() }
+                    }
+                }
+            }
+            match slug {
+                Some(slug) => rsx! {
+                    Link {
+                        to: Route::BlogPost { slug },
+                        class: "flex items-center gap-2 text-blue-500 font-medium",
+                        "Read more"
+                        i { class: "fas fa-arrow-right text-xs" }
+                    }
+                },
+                None => rsx! {
+                    div { class: "flex items-center gap-2 text-blue-500 font-medium",
+                        "Read more"
+                        i { class: "fas fa-arrow-right text-xs" }
+                    }
+                },
             }
         }
     }
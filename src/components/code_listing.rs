@@ -0,0 +1,17 @@
+use crate::components::CopyButton;
+use crate::models::highlight::CodeBlock;
+use dioxus::prelude::*;
+
+/// A highlighted code block from a blog post, with a copy button.
+#[component]
+pub fn CodeListing(block: CodeBlock) -> Element {
+    rsx! {
+        div { class: "code-block",
+            div { class: "code-header",
+                span { "{block.language.clone().unwrap_or_default()}" }
+                CopyButton { text: block.code.clone() }
+            }
+            div { dangerous_inner_html: "{block.html}" }
+        }
+    }
+}
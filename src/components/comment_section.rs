@@ -0,0 +1,250 @@
+use crate::models::comments::{
+    delete_comment, edit_comment, get_comments, post_comment, Comment, CommentStatus,
+    MAX_COMMENT_CHARS,
+};
+use crate::models::AuthUser;
+use dioxus::prelude::*;
+
+const TEXTAREA: &str =
+    "w-full p-3 rounded-lg bg-gray-900 border border-gray-800 text-white text-sm h-28 resize-y";
+const BUTTON: &str = "bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white font-bold py-2 px-4 rounded-lg text-sm transition-all";
+const TEXT_BUTTON: &str = "text-xs text-gray-500 hover:text-white transition-colors";
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%B %-d, %Y %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Comments on a post, with forms to comment and reply for logged in
+/// readers.
+#[component]
+pub fn CommentSection(slug: String) -> Element {
+    let auth_user = use_context::<Signal<Option<AuthUser>>>();
+    let mut comments = use_resource({
+        let slug = slug.clone();
+        move || get_comments(slug.clone())
+    });
+    let mut replying = use_signal(|| None::<i64>);
+    let logged_in = auth_user().is_some();
+
+    rsx! {
+        section { id: "comments", class: "mt-12 pt-6 border-t border-gray-800",
+            h2 { class: "text-2xl font-bold mb-6", "Comments" }
+            if logged_in {
+                CommentForm { slug: slug.clone(), onposted: move |_| comments.restart() }
+            } else {
+                p { class: "text-gray-500 text-sm", "Log in to join the discussion." }
+            }
+            match comments() {
+                Some(Ok(threads)) if threads.is_empty() => rsx! {
+                    p { class: "text-gray-500 text-sm mt-6", "No comments yet." }
+                },
+                Some(Ok(threads)) => rsx! {
+                    div { class: "space-y-6 mt-8",
+                        for thread in threads {
+                            div { key: "{thread.comment.id}",
+                                CommentItem { comment: thread.comment.clone(), onchange: move |_| comments.restart() }
+                                if logged_in && !thread.comment.deleted && replying() != Some(thread.comment.id) {
+                                    button {
+                                        class: "{TEXT_BUTTON} mt-2 ml-4",
+                                        onclick: move |_| replying.set(Some(thread.comment.id)),
+                                        i { class: "fas fa-reply mr-1" }
+                                        "Reply"
+                                    }
+                                }
+                                if !thread.replies.is_empty() || replying() == Some(thread.comment.id) {
+                                    div { class: "ml-6 md:ml-10 mt-4 space-y-4 border-l border-gray-800 pl-4 md:pl-6",
+                                        for reply in thread.replies {
+                                            CommentItem { key: "{reply.id}", comment: reply, onchange: move |_| comments.restart() }
+                                        }
+                                        if replying() == Some(thread.comment.id) {
+                                            CommentForm {
+                                                slug: slug.clone(),
+                                                parent_id: thread.comment.id,
+                                                onposted: move |_| {
+                                                    replying.set(None);
+                                                    comments.restart();
+                                                },
+                                                oncancel: move |_| replying.set(None),
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-400 mt-6", "{e}" }
+                },
+                None => rsx! {
+                    p { class: "text-gray-500 text-sm mt-6", "Loading comments..." }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn CommentForm(
+    slug: String,
+    #[props(default)] parent_id: Option<i64>,
+    onposted: EventHandler<()>,
+    /// Shows a Cancel button when set.
+    #[props(default)]
+    oncancel: Option<EventHandler<()>>,
+) -> Element {
+    let mut body = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut sending = use_signal(|| false);
+
+    let submit = move |evt: FormEvent| {
+        evt.prevent_default();
+        let slug = slug.clone();
+        sending.set(true);
+        spawn(async move {
+            match post_comment(slug, parent_id, body()).await {
+                Ok(_) => {
+                    body.set(String::new());
+                    error.set(None);
+                    onposted.call(());
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            sending.set(false);
+        });
+    };
+
+    rsx! {
+        form { class: "space-y-2", onsubmit: submit,
+            textarea {
+                class: TEXTAREA,
+                placeholder: if parent_id.is_some() { "Write a reply..." } else { "Write a comment... (Markdown: *emphasis*, `code`, [links](https://...))" },
+                maxlength: "{MAX_COMMENT_CHARS}",
+                value: "{body}",
+                oninput: move |evt| body.set(evt.value()),
+            }
+            div { class: "flex items-center gap-4",
+                button {
+                    class: BUTTON,
+                    r#type: "submit",
+                    disabled: sending() || body().trim().is_empty(),
+                    if parent_id.is_some() { "Reply" } else { "Post comment" }
+                }
+                if let Some(oncancel) = oncancel {
+                    button {
+                        class: TEXT_BUTTON,
+                        r#type: "button",
+                        onclick: move |_| oncancel.call(()),
+                        "Cancel"
+                    }
+                }
+                p { class: "text-xs text-gray-500", "Comments are visible once approved." }
+            }
+            if let Some(e) = error() {
+                p { class: "text-red-400 text-sm", "{e}" }
+            }
+        }
+    }
+}
+
+#[component]
+fn CommentItem(comment: Comment, onchange: EventHandler<()>) -> Element {
+    let mut editing = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+
+    if comment.deleted {
+        return rsx! {
+            p { class: "p-4 text-sm text-gray-600 italic", "This comment was deleted." }
+        };
+    }
+
+    let id = comment.id;
+    let can_edit = comment.can_edit(chrono::Utc::now().timestamp());
+    let save = move |evt: FormEvent| {
+        evt.prevent_default();
+        let body = editing().unwrap_or_default();
+        spawn(async move {
+            match edit_comment(id, body).await {
+                Ok(_) => {
+                    editing.set(None);
+                    error.set(None);
+                    onchange.call(());
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+    let delete = move |_| {
+        spawn(async move {
+            match delete_comment(id).await {
+                Ok(_) => onchange.call(()),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        article { id: "comment-{id}", class: "p-4 rounded-xl bg-gray-900/50 border border-gray-800",
+            header { class: "flex flex-wrap items-center gap-2 text-sm mb-2",
+                span { class: "font-semibold text-white", "{comment.author}" }
+                if comment.by_owner {
+                    span { class: "px-1.5 py-0.5 rounded bg-blue-500/10 border border-blue-500/40 text-blue-400 text-xs font-bold",
+                        "Author"
+                    }
+                }
+                time { class: "text-xs text-gray-500", "{format_timestamp(comment.created_at)}" }
+                if comment.edited {
+                    span { class: "text-xs text-gray-600", "(edited)" }
+                }
+                if comment.status == CommentStatus::Pending {
+                    span { class: "px-1.5 py-0.5 rounded bg-amber-500/10 border border-amber-500/40 text-amber-400 text-xs font-bold",
+                        "Awaiting moderation"
+                    }
+                } else if comment.status == CommentStatus::Rejected {
+                    span { class: "px-1.5 py-0.5 rounded bg-red-500/10 border border-red-500/40 text-red-400 text-xs font-bold",
+                        "Not approved"
+                    }
+                }
+            }
+            if let Some(draft) = editing() {
+                form { class: "space-y-2", onsubmit: save,
+                    textarea {
+                        class: TEXTAREA,
+                        maxlength: "{MAX_COMMENT_CHARS}",
+                        value: "{draft}",
+                        oninput: move |evt| editing.set(Some(evt.value())),
+                    }
+                    div { class: "flex items-center gap-4",
+                        button { class: BUTTON, r#type: "submit", "Save" }
+                        button {
+                            class: TEXT_BUTTON,
+                            r#type: "button",
+                            onclick: move |_| editing.set(None),
+                            "Cancel"
+                        }
+                    }
+                }
+            } else {
+                div { class: "comment-body text-sm", dangerous_inner_html: "{comment.html}" }
+            }
+            if can_edit && editing().is_none() {
+                div { class: "flex gap-4 mt-3",
+                    button {
+                        class: TEXT_BUTTON,
+                        onclick: {
+                            let body = comment.body.clone();
+                            move |_| editing.set(Some(body.clone()))
+                        },
+                        "Edit"
+                    }
+                    button { class: "{TEXT_BUTTON} hover:text-red-400", onclick: delete, "Delete" }
+                }
+            }
+            if let Some(e) = error() {
+                p { class: "text-red-400 text-sm mt-2", "{e}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::components::SectionHeader;
+use dioxus::prelude::*;
+
+#[component]
+pub fn ContactCard(email: String) -> Element {
+    let mut target = use_signal(|| "site");
+
+    let tab_class = move |name: &str| {
+        if target() == name {
+            "px-4 py-2 rounded-lg bg-blue-600 text-white font-bold"
+        } else {
+            "px-4 py-2 rounded-lg text-gray-400 hover:text-white hover:bg-gray-800"
+        }
+    };
+
+    rsx! {
+        div { id: "contact", class: "px-12 py-20",
+            div { class: "max-w-4xl mx-auto",
+                SectionHeader { title: "Contact".to_string() }
+
+                div { class: "flex flex-col md:flex-row gap-12 items-center",
+                    div { class: "bg-white p-4 rounded-2xl shadow-2xl shrink-0",
+                        img {
+                            src: "/qr.svg?target={target}",
+                            alt: "QR code",
+                            class: "w-56 h-56",
+                        }
+                    }
+
+                    div { class: "flex flex-col gap-6",
+                        div { class: "flex gap-2",
+                            button { class: tab_class("site"), onclick: move |_| target.set("site"), "Website" }
+                            button { class: tab_class("vcard"), onclick: move |_| target.set("vcard"), "vCard" }
+                        }
+                        p { class: "text-gray-400 max-w-sm",
+                            if target() == "site" {
+                                "Scan to open this page on your phone."
+                            } else {
+                                "Scan to save my contact details straight to your address book."
+                            }
+                        }
+                        a {
+                            href: "mailto:{email}",
+                            class: "flex items-center gap-2 text-blue-500 font-bold hover:text-white transition-colors text-lg",
+                            i { class: "fas fa-envelope" }
+                            "{email}"
+                        }
+                        a {
+                            href: "/contact.vcf",
+                            class: "flex items-center gap-2 text-blue-500 font-bold hover:text-white transition-colors text-lg",
+                            i { class: "fas fa-address-card" }
+                            "Download vCard"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use dioxus::prelude::*;
+
+/// Copies `text` to the clipboard and briefly confirms.
+#[component]
+pub fn CopyButton(text: String) -> Element {
+    let mut copied = use_signal(|| false);
+
+    rsx! {
+        button {
+            class: "flex items-center gap-2 text-xs text-gray-400 hover:text-white transition-colors",
+            r#type: "button",
+            title: "Copy to clipboard",
+            onclick: move |_| {
+                let text = text.clone();
+                async move {
+                    let eval = document::eval(
+                        "const text = await dioxus.recv(); await navigator.clipboard.writeText(text);",
+                    );
+                    if eval.send(text).is_ok() {
+                        copied.set(true);
+                    }
+                }
+            },
+            if copied() {
+                i { class: "fas fa-check text-green-400" }
+                "Copied"
+            } else {
+                i { class: "fas fa-copy" }
+                "Copy"
+            }
+        }
+    }
+}
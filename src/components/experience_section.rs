@@ -0,0 +1,36 @@
+use crate::components::SectionHeader;
+use crate::models::cv::Experience;
+use dioxus::prelude::*;
+
+#[component]
+pub fn ExperienceSection(experience: Vec<Experience>) -> Element {
+    rsx! {
+        div { id: "experience", class: "px-12 py-20",
+            div { class: "max-w-4xl mx-auto",
+                SectionHeader { title: "Experience".to_string() }
+
+                div { class: "space-y-10",
+                    for entry in experience {
+                        div { key: "{entry.id}", class: "border-l-2 border-blue-500/30 pl-6",
+                            p { class: "text-sm text-gray-500 mb-1",
+                                "{entry.start} – {entry.end.clone().unwrap_or_else(|| \"Present\".to_string())}"
+                            }
+                            h3 { class: "text-2xl font-semibold text-white",
+                                "{entry.position}"
+                                span { class: "text-blue-500", " · {entry.organization}" }
+                            }
+                            p { class: "text-gray-400 mt-2", "{entry.summary}" }
+                            if !entry.highlights.is_empty() {
+                                ul { class: "list-disc list-inside text-gray-400 mt-3 space-y-1",
+                                    for highlight in entry.highlights.iter() {
+                                        li { "{highlight}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
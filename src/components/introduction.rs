@@ -1,41 +1,51 @@
 use crate::components::{ProfileImage, SocialLink};
+use crate::models::cv::Basics;
 use dioxus::prelude::*;
 
 #[component]
-pub fn IntroductionSection() -> Element {
+pub fn IntroductionSection(basics: Basics) -> Element {
     rsx! {
         div { class: "flex items-center min-h-[90vh] px-12 py-20",
             div { class: "grid grid-cols-1 lg:grid-cols-2 gap-16 items-center max-w-6xl mx-auto",
                 div {
                     p { class: "text-blue-500 font-bold tracking-widest uppercase mb-4 text-sm",
-                        "Fullstack Developer & AI Engineer"
+                        "{basics.label}"
                     }
                     h1 { class: "text-7xl font-black mb-8 leading-tight",
-                        span { class: "block", "Taian" }
-                        span { class: "block text-white/90", "Feitosa" }
+                        span { class: "block", "{basics.first_name}" }
+                        span { class: "block text-white/90", "{basics.last_name}" }
                     }
                     p { class: "text-xl text-gray-400 mb-12 max-w-lg leading-relaxed",
-                        "Building bridges between logic and creativity through code, "
-                        "community, and innovative AI solutions."
+                        "{basics.summary}"
                     }
 
-                    div { class: "flex gap-8 items-center",
-                        SocialLink {
-                            href: "https://github.com/taianf".to_string(),
-                            icon: "fa-github".to_string(),
-                            label: "Github".to_string()
+                    if let Some(phone) = basics.phone.clone() {
+                        div { class: "flex flex-wrap gap-6 text-gray-300 mb-8",
+                            a { href: "mailto:{basics.email}", class: "flex items-center gap-2 hover:text-white",
+                                i { class: "fas fa-envelope text-blue-500" }
+                                "{basics.email}"
+                            }
+                            a { href: "tel:{phone}", class: "flex items-center gap-2 hover:text-white",
+                                i { class: "fas fa-phone text-blue-500" }
+                                "{phone}"
+                            }
                         }
-                        SocialLink {
-                            href: "https://www.linkedin.com/in/taian-feitosa/".to_string(),
-                            icon: "fa-linkedin".to_string(),
-                            label: "LinkedIn".to_string()
+                    }
+
+                    div { class: "flex gap-8 items-center",
+                        for profile in basics.profiles.iter() {
+                            SocialLink {
+                                href: profile.url.clone(),
+                                icon: profile.icon.clone(),
+                                label: profile.network.clone()
+                            }
                         }
                     }
                 }
 
                 ProfileImage {
-                    src: "https://media.licdn.com/dms/image/v2/D4D03AQFvFMptVJnakQ/profile-displayphoto-shrink_800_800/profile-displayphoto-shrink_800_800/0/1673901553883?e=1770249600&v=beta&t=KYANNIIT41GRnj54wCutvAhjrRTF3JXdKGNU1OpymSc".to_string(),
-                    alt: "Taian Feitosa".to_string()
+                    src: basics.image.clone(),
+                    alt: basics.full_name()
                 }
             }
         }
@@ -0,0 +1,12 @@
+use crate::models::structured_data::to_script;
+use dioxus::prelude::*;
+
+/// Adds a schema.org JSON-LD document to the page head.
+#[component]
+pub fn JsonLd(data: serde_json::Value) -> Element {
+    let script = to_script(&data);
+
+    rsx! {
+        document::Script { r#type: "application/ld+json", "{script}" }
+    }
+}
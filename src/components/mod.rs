@@ -2,30 +2,74 @@
 
 mod about_section;
 mod blog_post_card;
+mod code_listing;
+mod comment_section;
+mod contact_card;
+mod copy_button;
+mod experience_section;
 mod info_field;
 mod introduction;
+mod json_ld;
 mod loading_spinner;
 mod login_button;
 mod login_card;
 mod logo;
 mod nav_item;
+mod pager;
+mod post_body;
+mod post_list;
 mod profile_image;
+mod projects_section;
+mod reaction_bar;
+mod related_posts;
+mod search_box;
 mod section_card;
 mod section_header;
+mod seo;
+mod series_nav;
+mod skill_match_list;
+mod skills_section;
 mod social_link;
+mod syndication_links;
+mod table_of_contents;
+mod tag_chip;
+mod tag_cloud;
 mod user_profile_card;
+mod webmention_list;
 
 pub use about_section::AboutSection;
 pub use blog_post_card::BlogPostCard;
+pub use code_listing::CodeListing;
+pub use comment_section::CommentSection;
+pub use contact_card::ContactCard;
+pub use copy_button::CopyButton;
+pub use experience_section::ExperienceSection;
 pub use info_field::InfoField;
 pub use introduction::IntroductionSection;
+pub use json_ld::JsonLd;
 pub use loading_spinner::LoadingSpinner;
 pub use login_button::LoginButton;
 pub use login_card::LoginCard;
 pub use logo::Logo;
 pub use nav_item::NavItem;
+pub use pager::Pager;
+pub use post_body::PostBody;
+pub use post_list::PostList;
 pub use profile_image::ProfileImage;
+pub use projects_section::ProjectsSection;
+pub use reaction_bar::ReactionBar;
+pub use related_posts::RelatedPosts;
+pub use search_box::SearchBox;
 pub use section_card::SectionCard;
 pub use section_header::SectionHeader;
+pub use seo::Seo;
+pub use series_nav::SeriesNav;
+pub use skill_match_list::SkillMatchList;
+pub use skills_section::SkillsSection;
 pub use social_link::SocialLink;
+pub use syndication_links::SyndicationLinks;
+pub use table_of_contents::TableOfContents;
+pub use tag_chip::TagChip;
+pub use tag_cloud::TagCloud;
 pub use user_profile_card::UserProfileCard;
+pub use webmention_list::WebmentionList;
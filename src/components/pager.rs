@@ -0,0 +1,40 @@
+use crate::Route;
+use dioxus::prelude::*;
+
+/// Previous/next links: newer content on the left, older on the right.
+#[component]
+pub fn Pager(
+    newer: Option<Route>,
+    newer_label: String,
+    older: Option<Route>,
+    older_label: String,
+) -> Element {
+    if newer.is_none() && older.is_none() {
+        return rsx! {};
+    }
+
+    rsx! {
+        nav { class: "flex justify-between gap-6 mt-12 pt-6 border-t border-gray-800",
+            div { class: "flex-1",
+                if let Some(route) = newer {
+                    Link {
+                        to: route,
+                        class: "flex items-center gap-2 text-blue-500 font-medium hover:text-white transition-colors",
+                        i { class: "fas fa-arrow-left text-xs" }
+                        "{newer_label}"
+                    }
+                }
+            }
+            div { class: "flex-1 flex justify-end text-right",
+                if let Some(route) = older {
+                    Link {
+                        to: route,
+                        class: "flex items-center gap-2 text-blue-500 font-medium hover:text-white transition-colors",
+                        "{older_label}"
+                        i { class: "fas fa-arrow-right text-xs" }
+                    }
+                }
+            }
+        }
+    }
+}
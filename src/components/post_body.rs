@@ -0,0 +1,23 @@
+use crate::components::CodeListing;
+use crate::models::blog::PostBlock;
+use dioxus::prelude::*;
+
+/// A rendered post body. Shared by the post page and the editor preview so
+/// both look the same.
+#[component]
+pub fn PostBody(blocks: Vec<PostBlock>) -> Element {
+    rsx! {
+        div { class: "post-body",
+            for block in blocks {
+                match block {
+                    PostBlock::Html(html) => rsx! {
+                        div { dangerous_inner_html: "{html}" }
+                    },
+                    PostBlock::Code(code) => rsx! {
+                        CodeListing { block: code }
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::components::BlogPostCard;
+use crate::models::blog::PostMeta;
+use crate::models::reactions::get_post_stats;
+use dioxus::prelude::*;
+
+/// Cards for a list of posts, in the given order. View and reaction counts
+/// are filled in once they have loaded.
+#[component]
+pub fn PostList(posts: Vec<PostMeta>) -> Element {
+    let slugs: Vec<String> = posts.iter().map(|post| post.slug.clone()).collect();
+    let stats = use_resource(use_reactive!(|slugs| get_post_stats(slugs)));
+    let stats = stats().and_then(Result::ok).unwrap_or_default();
+
+    rsx! {
+        div { class: "grid gap-6",
+            for (i, post) in posts.into_iter().enumerate() {
+                BlogPostCard {
+                    key: "{post.slug}",
+                    reading_minutes: post.reading_minutes(),
+                    stats: stats.iter().find(|stats| stats.slug == post.slug).cloned(),
+                    title: post.title,
+                    description: post.description,
+                    index: i as i32 + 1,
+                    slug: post.slug,
+                    date: post.date.format("%B %-d, %Y").to_string(),
+                    tags: post.tags,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::components::SectionHeader;
+use crate::models::cv::Project;
+use dioxus::prelude::*;
+
+#[component]
+pub fn ProjectsSection(projects: Vec<Project>) -> Element {
+    rsx! {
+        div { id: "projects", class: "px-12 py-20",
+            div { class: "max-w-4xl mx-auto",
+                SectionHeader { title: "Projects".to_string() }
+
+                div { class: "grid gap-6",
+                    for project in projects {
+                        a {
+                            key: "{project.id}",
+                            href: "{project.url}",
+                            target: "_blank",
+                            class: "block bg-gray-800 p-6 rounded-xl border border-gray-700 hover:border-blue-500 transition-all",
+                            h3 { class: "text-2xl font-semibold mb-2", "{project.name}" }
+                            p { class: "text-gray-400", "{project.description}" }
+                            if !project.highlights.is_empty() {
+                                ul { class: "list-disc list-inside text-gray-400 mt-3 space-y-1",
+                                    for highlight in project.highlights.iter() {
+                                        li { "{highlight}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::models::reactions::{record_view, set_reaction, PostStats, Reaction};
+use dioxus::prelude::*;
+
+/// View count and reaction buttons under a post. The view is counted from
+/// the browser once the page is open, so server rendering and crawlers do
+/// not add to it.
+#[component]
+pub fn ReactionBar(slug: String) -> Element {
+    let mut stats = use_signal(|| None::<PostStats>);
+    let mut error = use_signal(|| None::<String>);
+
+    use_effect({
+        let slug = slug.clone();
+        move || {
+            let slug = slug.clone();
+            spawn(async move {
+                match record_view(slug).await {
+                    Ok(counted) => stats.set(Some(counted)),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            });
+        }
+    });
+
+    let Some(current) = stats() else {
+        return rsx! {
+            div { class: "mt-10 min-h-10",
+                if let Some(e) = error() {
+                    p { class: "text-red-400 text-sm", "{e}" }
+                }
+            }
+        };
+    };
+
+    rsx! {
+        div { class: "mt-10 flex flex-wrap items-center gap-2",
+            for reaction in Reaction::ALL {
+                button {
+                    key: "{reaction.as_str()}",
+                    class: if current.mine == Some(reaction) { "px-3 py-1.5 rounded-full border border-blue-500 bg-blue-500/10 text-sm transition-colors" } else { "px-3 py-1.5 rounded-full border border-gray-800 hover:border-gray-600 text-sm transition-colors" },
+                    title: "{reaction.label()}",
+                    aria_pressed: "{current.mine == Some(reaction)}",
+                    onclick: {
+                        let slug = slug.clone();
+                        let toggled = (current.mine != Some(reaction)).then_some(reaction);
+                        move |_| {
+                            let slug = slug.clone();
+                            spawn(async move {
+                                match set_reaction(slug, toggled).await {
+                                    Ok(updated) => {
+                                        stats.set(Some(updated));
+                                        error.set(None);
+                                    }
+                                    Err(e) => error.set(Some(e.to_string())),
+                                }
+                            });
+                        }
+                    },
+                    "{reaction.emoji()}"
+                    if current.count(reaction) > 0 {
+                        span { class: "ml-1.5 text-gray-400", "{current.count(reaction)}" }
+                    }
+                }
+            }
+            span { class: "ml-auto text-sm text-gray-500",
+                i { class: "fas fa-eye mr-2" }
+                if current.views == 1 { "1 view" } else { "{current.views} views" }
+            }
+        }
+        if let Some(e) = error() {
+            p { class: "text-red-400 text-sm mt-2", "{e}" }
+        }
+    }
+}
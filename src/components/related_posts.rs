@@ -0,0 +1,32 @@
+use crate::models::blog::PostMeta;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// A short list of posts to read next.
+#[component]
+pub fn RelatedPosts(posts: Vec<PostMeta>) -> Element {
+    if posts.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        section { class: "mt-12 pt-6 border-t border-gray-800",
+            h2 { class: "text-xs uppercase tracking-widest text-gray-500 font-bold mb-4",
+                "Related posts"
+            }
+            div { class: "grid gap-4 md:grid-cols-3",
+                for post in posts {
+                    Link {
+                        key: "{post.slug}",
+                        to: Route::BlogPost { slug: post.slug.clone() },
+                        class: "block p-4 rounded-xl border border-gray-800 hover:border-blue-500 transition-colors",
+                        h3 { class: "font-semibold mb-2 leading-snug", "{post.title}" }
+                        p { class: "text-xs text-gray-500",
+                            "{post.date.format(\"%B %-d, %Y\")} · {post.reading_minutes()} min read"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
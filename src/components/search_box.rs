@@ -0,0 +1,40 @@
+use crate::Route;
+use dioxus::prelude::*;
+
+/// A search field that opens the results page. It is a plain GET form, so
+/// it also works before the app has hydrated.
+#[component]
+pub fn SearchBox(
+    #[props(default)] initial: String,
+    #[props(default)] onsubmit: EventHandler<()>,
+) -> Element {
+    let mut query = use_signal(|| initial.clone());
+    let navigator = use_navigator();
+
+    // Follow the route when the results page is reached another way
+    use_effect(use_reactive!(|initial| query.set(initial)));
+
+    rsx! {
+        form {
+            role: "search",
+            action: "/search",
+            method: "get",
+            class: "relative",
+            onsubmit: move |evt| {
+                evt.prevent_default();
+                navigator.push(Route::Search { q: query().trim().to_string() });
+                onsubmit.call(());
+            },
+            i { class: "fas fa-search absolute left-3 top-1/2 -translate-y-1/2 text-gray-500" }
+            input {
+                r#type: "search",
+                name: "q",
+                placeholder: "Search posts and projects",
+                "aria-label": "Search",
+                value: "{query}",
+                oninput: move |evt| query.set(evt.value()),
+                class: "w-full pl-10 pr-3 py-2 bg-gray-900 border border-gray-800 rounded-xl text-gray-200 placeholder-gray-500 focus:outline-none focus:border-blue-500",
+            }
+        }
+    }
+}
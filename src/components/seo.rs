@@ -0,0 +1,67 @@
+use crate::models::seo::{full_title, meta_tags, MetaTag, PageKind, PageMeta, SeoDefaults};
+use crate::models::site::SiteUrl;
+use crate::Route;
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+
+/// The head of a page: title, canonical link, description, Open Graph and
+/// Twitter card tags. Anything left out falls back to the CV (see
+/// [`SeoDefaults`]). The canonical URL defaults to the current route, and
+/// routes that are not indexable (see `Route::is_indexable`) are kept out of
+/// search.
+#[component]
+pub fn Seo(
+    #[props(default)] title: String,
+    #[props(default)] description: String,
+    /// Absolute canonical URL, for pages that live somewhere else first.
+    canonical: Option<String>,
+    /// Absolute or site-relative URL of the preview image.
+    image: Option<String>,
+    #[props(default)] kind: PageKind,
+    published: Option<NaiveDate>,
+    #[props(default)] tags: Vec<String>,
+    /// Keeps an otherwise indexable page (such as a draft) out of search.
+    #[props(default)]
+    noindex: bool,
+) -> Element {
+    let site_url = use_context::<SiteUrl>();
+    let defaults = use_context::<SeoDefaults>();
+    let route = use_route::<Route>();
+
+    let path = route.to_string();
+    let path = path.split('?').next().unwrap_or_default();
+    let page = PageMeta {
+        url: canonical.unwrap_or_else(|| site_url.absolute(path)),
+        title: title.clone(),
+        description,
+        image,
+        kind,
+        published,
+        tags,
+    };
+    let robots = if !route.is_indexable() {
+        Some("noindex, nofollow")
+    } else if noindex {
+        Some("noindex")
+    } else {
+        None
+    };
+
+    rsx! {
+        document::Title { "{full_title(&title, &defaults.site_name)}" }
+        document::Link { rel: "canonical", href: "{page.url}" }
+        if let Some(robots) = robots {
+            document::Meta { name: "robots", content: robots }
+        }
+        for tag in meta_tags(&page, &defaults, &site_url.0) {
+            match tag {
+                MetaTag::Name(name, content) => rsx! {
+                    document::Meta { name, content }
+                },
+                MetaTag::Property(property, content) => rsx! {
+                    document::Meta { property, content }
+                },
+            }
+        }
+    }
+}
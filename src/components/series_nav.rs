@@ -0,0 +1,61 @@
+use crate::models::series::Series;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// The parts of the series a post belongs to, with the current one marked
+/// and links to the parts either side.
+#[component]
+pub fn SeriesNav(series: Series) -> Element {
+    let prev = series.prev().cloned();
+    let next = series.next().cloned();
+
+    rsx! {
+        nav { class: "mb-10 p-6 rounded-xl border border-gray-800 bg-gray-900/50",
+            aria_label: "Series",
+            p { class: "text-xs uppercase tracking-widest text-gray-500 font-bold mb-1",
+                "Part {series.current + 1} of {series.parts.len()}"
+            }
+            h2 { class: "text-lg font-bold mb-4", "{series.name}" }
+            ol { class: "space-y-2 list-decimal list-inside text-sm",
+                for (i, part) in series.parts.iter().enumerate() {
+                    li { key: "{part.slug}",
+                        class: if i == series.current { "text-white font-semibold" } else { "text-gray-500" },
+                        if i == series.current {
+                            span { aria_current: "page", "{part.title}" }
+                        } else {
+                            Link {
+                                to: Route::BlogPost { slug: part.slug.clone() },
+                                class: "text-blue-500 hover:text-white transition-colors",
+                                "{part.title}"
+                            }
+                        }
+                    }
+                }
+            }
+            if prev.is_some() || next.is_some() {
+                div { class: "flex justify-between gap-6 mt-4 pt-4 border-t border-gray-800 text-sm",
+                    div {
+                        if let Some(part) = prev {
+                            Link {
+                                to: Route::BlogPost { slug: part.slug },
+                                class: "flex items-center gap-2 text-blue-500 hover:text-white transition-colors",
+                                i { class: "fas fa-arrow-left text-xs" }
+                                "Previous part"
+                            }
+                        }
+                    }
+                    div {
+                        if let Some(part) = next {
+                            Link {
+                                to: Route::BlogPost { slug: part.slug },
+                                class: "flex items-center gap-2 text-blue-500 hover:text-white transition-colors",
+                                "Next part"
+                                i { class: "fas fa-arrow-right text-xs" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::models::job_match::SkillMatch;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SkillMatchList(
+    title: String,
+    icon: String,
+    color: String,
+    skills: Vec<SkillMatch>,
+) -> Element {
+    rsx! {
+        div {
+            h3 { class: "text-sm uppercase tracking-widest text-gray-500 mb-3 flex items-center gap-2",
+                i { class: "fas {icon} {color}" }
+                "{title} ({skills.len()})"
+            }
+            if skills.is_empty() {
+                p { class: "text-gray-600 text-sm", "None" }
+            }
+            ul { class: "space-y-2",
+                for skill in skills {
+                    li { key: "{skill.id}", class: "text-gray-300",
+                        span { class: "font-medium", "{skill.name}" }
+                        if skill.mentions > 1 {
+                            span { class: "text-gray-500 text-sm", " ×{skill.mentions}" }
+                        }
+                        if !skill.via.is_empty() {
+                            span { class: "text-gray-500 text-sm", " via {skill.via.join(\", \")}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
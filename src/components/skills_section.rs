@@ -0,0 +1,31 @@
+use crate::components::SectionHeader;
+use crate::models::cv::Skill;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SkillsSection(groups: Vec<(String, Vec<Skill>)>) -> Element {
+    rsx! {
+        div { id: "skills", class: "px-12 py-20",
+            div { class: "max-w-4xl mx-auto",
+                SectionHeader { title: "Skills".to_string() }
+
+                div { class: "grid grid-cols-1 md:grid-cols-2 gap-8",
+                    for (category, skills) in groups {
+                        div { key: "{category}",
+                            h3 { class: "text-xs uppercase tracking-widest text-gray-500 mb-3", "{category}" }
+                            div { class: "flex flex-wrap gap-2",
+                                for skill in skills {
+                                    span {
+                                        key: "{skill.id}",
+                                        class: "px-3 py-1 rounded-full bg-blue-500/10 border border-blue-500/20 text-blue-400 text-sm font-medium",
+                                        "{skill.name}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
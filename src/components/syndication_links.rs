@@ -0,0 +1,55 @@
+use crate::models::webmentions::parse_http_url;
+use dioxus::prelude::*;
+
+/// The site name to show for a link: its host without `www.`.
+fn site_name(url: &str) -> String {
+    parse_http_url(url)
+        .and_then(|url| url.host_str().map(str::to_string))
+        .map(|host| host.trim_start_matches("www.").to_string())
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// Where else a post lives: the original for posts first published
+/// elsewhere, and copies syndicated to other sites.
+#[component]
+pub fn SyndicationLinks(canonical_url: Option<String>, links: Vec<String>) -> Element {
+    if canonical_url.is_none() && links.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        section { class: "mt-12 pt-6 border-t border-gray-800 text-sm text-gray-400 space-y-2",
+            if let Some(url) = canonical_url {
+                p {
+                    "Originally published on "
+                    a {
+                        href: "{url}",
+                        class: "text-blue-500 hover:text-white transition-colors",
+                        "{site_name(&url)}"
+                    }
+                    "."
+                }
+            }
+            if !links.is_empty() {
+                p {
+                    span { class: "text-xs uppercase tracking-widest text-gray-500 font-bold mr-3",
+                        "Syndicated to"
+                    }
+                    for (i , url) in links.iter().enumerate() {
+                        span { key: "{url}",
+                            if i > 0 {
+                                span { class: "text-gray-600", " · " }
+                            }
+                            a {
+                                href: "{url}",
+                                rel: "syndication",
+                                class: "u-syndication text-blue-500 hover:text-white transition-colors",
+                                "{site_name(url)}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::models::toc::TocEntry;
+use dioxus::prelude::*;
+
+/// Reports the id of the last heading scrolled past (with a little slack for
+/// the page top) whenever it changes. The listener is kept on `window` so a
+/// later table of contents, or [`STOP_SCROLL_SPY`], can remove it.
+const SCROLL_SPY: &str = r#"
+const ids = await dioxus.recv();
+let current = null;
+const update = () => {
+    let active = "";
+    for (const id of ids) {
+        const heading = document.getElementById(id);
+        if (heading && heading.getBoundingClientRect().top <= 96) {
+            active = id;
+        }
+    }
+    if (active !== current) {
+        current = active;
+        dioxus.send(active);
+    }
+};
+if (window.__tocScrollSpy) {
+    window.removeEventListener("scroll", window.__tocScrollSpy);
+}
+window.__tocScrollSpy = update;
+window.addEventListener("scroll", update, { passive: true });
+update();
+"#;
+
+/// Removes the listener added by [`SCROLL_SPY`].
+const STOP_SCROLL_SPY: &str = r#"
+if (window.__tocScrollSpy) {
+    window.removeEventListener("scroll", window.__tocScrollSpy);
+    window.__tocScrollSpy = null;
+}
+"#;
+
+/// The post's headings as a nested list of links, highlighting the section
+/// being read.
+#[component]
+pub fn TableOfContents(entries: Vec<TocEntry>) -> Element {
+    let mut active = use_signal(String::new);
+    let ids: Vec<String> = entries.iter().flat_map(TocEntry::ids).collect();
+
+    use_future(move || {
+        let ids = ids.clone();
+        async move {
+            let mut spy = document::eval(SCROLL_SPY);
+            if spy.send(ids).is_err() {
+                return;
+            }
+            while let Ok(id) = spy.recv::<String>().await {
+                active.set(id);
+            }
+        }
+    });
+    use_drop(|| {
+        document::eval(STOP_SCROLL_SPY);
+    });
+
+    rsx! {
+        nav { class: "text-sm", aria_label: "Table of contents",
+            p { class: "text-xs uppercase tracking-widest text-gray-500 font-bold mb-3",
+                "On this page"
+            }
+            TocList { entries, active: active() }
+        }
+    }
+}
+
+#[component]
+fn TocList(entries: Vec<TocEntry>, active: String) -> Element {
+    rsx! {
+        ul { class: "space-y-2",
+            for entry in entries {
+                li { key: "{entry.id}",
+                    a {
+                        href: "#{entry.id}",
+                        class: if entry.id == active { "block text-blue-400 font-semibold border-l-2 border-blue-500 pl-3" } else { "block text-gray-500 hover:text-white border-l-2 border-transparent pl-3 transition-colors" },
+                        "{entry.title}"
+                    }
+                    if !entry.children.is_empty() {
+                        div { class: "ml-3 mt-2",
+                            TocList { entries: entry.children.clone(), active: active.clone() }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
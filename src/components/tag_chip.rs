@@ -0,0 +1,15 @@
+use crate::models::tags::tag_slug;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// A tag linking to its archive page.
+#[component]
+pub fn TagChip(name: String) -> Element {
+    rsx! {
+        Link {
+            to: Route::BlogTag { tag: tag_slug(&name) },
+            class: "px-2 py-0.5 bg-gray-800 border border-gray-700 rounded text-sm text-gray-400 hover:text-white hover:border-blue-500 transition-colors",
+            "#{name}"
+        }
+    }
+}
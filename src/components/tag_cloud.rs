@@ -0,0 +1,31 @@
+use crate::models::tags::TagCount;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// All tags with their post counts; more used tags are drawn larger.
+#[component]
+pub fn TagCloud(tags: Vec<TagCount>) -> Element {
+    let max = tags.iter().map(|t| t.count).max().unwrap_or(1);
+
+    rsx! {
+        div { class: "flex flex-wrap items-baseline gap-x-4 gap-y-2",
+            for tag in tags.into_iter() {
+                Link {
+                    key: "{tag.slug}",
+                    to: Route::BlogTag { tag: tag.slug.clone() },
+                    class: format!(
+                        "{} text-gray-400 hover:text-blue-500 transition-colors",
+                        match tag.count * 3 / max {
+                            3 => "text-2xl font-bold",
+                            2 => "text-xl font-semibold",
+                            1 => "text-lg",
+                            _ => "text-base",
+                        },
+                    ),
+                    "#{tag.name}"
+                    span { class: "ml-1 text-xs text-gray-600", "{tag.count}" }
+                }
+            }
+        }
+    }
+}
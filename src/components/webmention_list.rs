@@ -0,0 +1,42 @@
+use crate::models::webmentions::Webmention;
+use dioxus::prelude::*;
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%B %-d, %Y").to_string())
+        .unwrap_or_default()
+}
+
+/// Pages elsewhere that link to a post, as verified webmentions.
+#[component]
+pub fn WebmentionList(mentions: Vec<Webmention>) -> Element {
+    if mentions.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        section { id: "mentions", class: "mt-12 pt-6 border-t border-gray-800",
+            h2 { class: "text-xs uppercase tracking-widest text-gray-500 font-bold mb-4",
+                if mentions.len() == 1 { "1 mention" } else { "{mentions.len()} mentions" }
+            }
+            ul { class: "space-y-3",
+                for mention in mentions {
+                    li { key: "{mention.source}", class: "text-sm",
+                        a {
+                            href: "{mention.source}",
+                            rel: "nofollow ugc noopener",
+                            class: "text-blue-500 hover:text-white transition-colors",
+                            if mention.title.is_empty() { "{mention.host()}" } else { "{mention.title}" }
+                        }
+                        span { class: "text-xs text-gray-500 ml-2",
+                            if !mention.title.is_empty() {
+                                "{mention.host()} · "
+                            }
+                            "{format_timestamp(mention.verified_at)}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,15 @@
+#[cfg(feature = "server")]
+pub mod backend;
 pub mod components;
 pub mod models;
 pub mod views;
 
 use dioxus::prelude::*;
-use views::{AuthCallback, Blog, Home, Navbar, Profile};
+use views::{
+    AdminComments, AdminPosts, AdminShareLinks, AuthCallback, Blog, BlogArchive, BlogPage,
+    BlogPost, BlogTag, CvVariant, EditPost, Home, JobMatch, Navbar, PostHistory, Profile, Search,
+    SharedCv,
+};
 
 #[derive(Debug, Clone, Routable, PartialEq)]
 pub enum Route {
@@ -12,8 +18,61 @@ pub enum Route {
     Home {},
     #[route("/blog")]
     Blog {},
+    #[route("/blog/page/:page")]
+    BlogPage { page: usize },
+    #[route("/blog/:year/:month")]
+    BlogArchive { year: i32, month: u32 },
+    #[route("/blog/:slug")]
+    BlogPost { slug: String },
+    #[route("/blog/tag/:tag")]
+    BlogTag { tag: String },
+    #[route("/search?:q")]
+    Search { q: String },
     #[route("/profile")]
     Profile {},
     #[route("/auth/callback")]
     AuthCallback {},
+    #[route("/cv/:variant")]
+    CvVariant { variant: String },
+    #[route("/s/:token")]
+    SharedCv { token: String },
+    #[route("/admin/share-links")]
+    AdminShareLinks {},
+    #[route("/admin/job-match")]
+    JobMatch {},
+    #[route("/admin/posts")]
+    AdminPosts {},
+    #[route("/admin/posts/:slug")]
+    EditPost { slug: String },
+    #[route("/admin/posts/:slug/history")]
+    PostHistory { slug: String },
+    #[route("/admin/comments")]
+    AdminComments {},
+}
+
+impl Route {
+    /// Whether search engines may index the page. Drives `sitemap.xml` and
+    /// `robots.txt`; the match is exhaustive so every new route has to
+    /// choose.
+    pub fn is_indexable(&self) -> bool {
+        match self {
+            Route::Home {}
+            | Route::Blog {}
+            | Route::BlogPage { .. }
+            | Route::BlogArchive { .. }
+            | Route::BlogPost { .. }
+            | Route::BlogTag { .. }
+            | Route::CvVariant { .. } => true,
+            Route::Search { .. }
+            | Route::Profile {}
+            | Route::AuthCallback {}
+            | Route::SharedCv { .. }
+            | Route::AdminShareLinks {}
+            | Route::JobMatch {}
+            | Route::AdminPosts {}
+            | Route::EditPost { .. }
+            | Route::PostHistory { .. }
+            | Route::AdminComments {} => false,
+        }
+    }
 }
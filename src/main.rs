@@ -7,19 +7,59 @@ const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 
 fn main() {
     #[cfg(feature = "server")]
-    dotenvy::dotenv().ok();
+    {
+        dotenvy::dotenv().ok();
+        dioxus::logger::initialize_default();
+        // Served by hand rather than with `dioxus::serve` so requests carry
+        // the peer address that rate limits key clients by.
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to start the runtime")
+            .block_on(async {
+                cv::backend::webmentions::start_sender();
+                let router = dioxus::server::router(App).merge(cv::backend::router());
+                let address = dioxus::cli_config::fullstack_address_or_localhost();
+                let listener = tokio::net::TcpListener::bind(address)
+                    .await
+                    .expect("Failed to bind the server address");
+                dioxus::server::axum::serve(
+                    listener,
+                    router.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+                )
+                .await
+                .expect("Server error");
+            });
+    }
 
+    #[cfg(not(feature = "server"))]
     dioxus::launch(App);
 }
 
 #[component]
 fn App() -> Element {
     let _auth_user = use_context_provider(|| Signal::new(None::<cv::models::AuthUser>));
+    let site_url = use_server_cached(cv::models::site::site_url);
+    use_context_provider(|| cv::models::site::SiteUrl(site_url));
+    let seo_defaults = use_server_cached(cv::models::seo::site_defaults);
+    use_context_provider(|| seo_defaults);
 
     rsx! {
         document::Link { rel: "icon", href: FAVICON }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
         document::Link { rel: "stylesheet", href: TAILWIND_CSS }
+        document::Link {
+            rel: "alternate",
+            r#type: "application/rss+xml",
+            title: "Blog (RSS)",
+            href: "/feed.xml",
+        }
+        document::Link {
+            rel: "alternate",
+            r#type: "application/atom+xml",
+            title: "Blog (Atom)",
+            href: "/atom.xml",
+        }
         document::Link {
             rel: "stylesheet",
             href: "https://cdnjs.cloudflare.com/ajax/libs/font-awesome/6.0.0/css/all.min.css",
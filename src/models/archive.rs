@@ -0,0 +1,192 @@
+//! Blog navigation computed from the post index: pages, month archives and
+//! the posts either side of a post. Posts are expected newest first, as
+//! returned by `blog::load_posts`.
+
+#[cfg(feature = "server")]
+use crate::models::blog::public_posts;
+use crate::models::blog::PostMeta;
+use crate::models::tags::TagCount;
+use chrono::Datelike;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Page {
+    /// 1-based.
+    pub number: usize,
+    pub total_pages: usize,
+    pub posts: Vec<PostMeta>,
+}
+
+impl Page {
+    /// The page with newer posts.
+    pub fn prev(&self) -> Option<usize> {
+        (self.number > 1).then(|| self.number - 1)
+    }
+
+    /// The page with older posts.
+    pub fn next(&self) -> Option<usize> {
+        (self.number < self.total_pages).then(|| self.number + 1)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MonthArchive {
+    pub year: i32,
+    pub month: u32,
+    pub count: usize,
+}
+
+impl MonthArchive {
+    /// "October 2026".
+    pub fn label(&self) -> String {
+        month_label(self.year, self.month)
+            .unwrap_or_else(|| format!("{}-{:02}", self.year, self.month))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PostLink {
+    pub slug: String,
+    pub title: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Neighbours {
+    pub newer: Option<PostLink>,
+    pub older: Option<PostLink>,
+}
+
+/// Everything the blog index shows.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BlogIndex {
+    pub page: Page,
+    pub tags: Vec<TagCount>,
+    pub months: Vec<MonthArchive>,
+}
+
+/// "October 2026", or `None` when there is no such month.
+pub fn month_label(year: i32, month: u32) -> Option<String> {
+    chrono::NaiveDate::from_ymd_opt(year, month, 1).map(|date| date.format("%B %Y").to_string())
+}
+
+/// Page `number` of `posts`. Page 1 always exists, even with no posts.
+pub fn paginate(posts: &[PostMeta], number: usize, per_page: usize) -> Option<Page> {
+    let per_page = per_page.max(1);
+    let total_pages = posts.len().div_ceil(per_page).max(1);
+    if number == 0 || number > total_pages {
+        return None;
+    }
+
+    Some(Page {
+        number,
+        total_pages,
+        posts: posts
+            .iter()
+            .skip((number - 1) * per_page)
+            .take(per_page)
+            .cloned()
+            .collect(),
+    })
+}
+
+/// Months that have posts, newest first.
+pub fn months(posts: &[PostMeta]) -> Vec<MonthArchive> {
+    let mut months: Vec<MonthArchive> = Vec::new();
+    for post in posts {
+        let (year, month) = (post.date.year(), post.date.month());
+        match months
+            .iter_mut()
+            .find(|m| m.year == year && m.month == month)
+        {
+            Some(archive) => archive.count += 1,
+            None => months.push(MonthArchive {
+                year,
+                month,
+                count: 1,
+            }),
+        }
+    }
+    months.sort_by_key(|m| std::cmp::Reverse((m.year, m.month)));
+    months
+}
+
+pub fn in_month(posts: &[PostMeta], year: i32, month: u32) -> Vec<PostMeta> {
+    posts
+        .iter()
+        .filter(|post| post.date.year() == year && post.date.month() == month)
+        .cloned()
+        .collect()
+}
+
+pub fn neighbours(posts: &[PostMeta], slug: &str) -> Neighbours {
+    let link = |post: &PostMeta| PostLink {
+        slug: post.slug.clone(),
+        title: post.title.clone(),
+    };
+    match posts.iter().position(|post| post.slug == slug) {
+        Some(i) => Neighbours {
+            newer: i.checked_sub(1).map(|j| link(&posts[j])),
+            older: posts.get(i + 1).map(link),
+        },
+        None => Neighbours::default(),
+    }
+}
+
+/// Posts per listing page (`BLOG_PAGE_SIZE`, default 10).
+#[cfg(feature = "server")]
+fn page_size() -> usize {
+    std::env::var("BLOG_PAGE_SIZE")
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .filter(|&size| size > 0)
+        .unwrap_or(10)
+}
+
+#[server]
+pub async fn get_blog_index(page: usize) -> Result<BlogIndex, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let posts = public_posts()?;
+        Ok(BlogIndex {
+            page: paginate(&posts, page, page_size())
+                .ok_or_else(|| ServerFnError::new(format!("No page {}", page)))?,
+            tags: crate::models::tags::tag_counts(&posts),
+            months: months(&posts),
+        })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+#[server]
+pub async fn get_month_archive(year: i32, month: u32) -> Result<Vec<PostMeta>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        if month_label(year, month).is_none() {
+            return Err(ServerFnError::new(format!(
+                "No archive for {}-{:02}",
+                year, month
+            )));
+        }
+        Ok(in_month(&public_posts()?, year, month))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+#[server]
+pub async fn get_neighbours(slug: String) -> Result<Neighbours, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        Ok(neighbours(&public_posts()?, &slug))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
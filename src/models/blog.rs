@@ -0,0 +1,819 @@
+//! Blog posts: Markdown files with YAML (`---`) or TOML (`+++`) front matter,
+//! loaded from `content/posts` and rendered to HTML.
+
+use crate::models::highlight::CodeBlock;
+use crate::models::media::MediaImage;
+use crate::models::tags::{normalize_tags, tag_slug};
+use crate::models::toc::{build_toc, HeadingIds, TocEntry};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use dioxus::prelude::*;
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Where a post is in its lifecycle.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    /// Only editors can see it.
+    Draft,
+    /// Becomes published at `publish_at`.
+    Scheduled,
+    /// Served at its URL but left out of listings, feeds, the sitemap and
+    /// search.
+    Unlisted,
+    #[default]
+    Published,
+}
+
+impl PostStatus {
+    pub const ALL: [PostStatus; 4] = [
+        PostStatus::Draft,
+        PostStatus::Scheduled,
+        PostStatus::Unlisted,
+        PostStatus::Published,
+    ];
+
+    /// The front matter spelling.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Scheduled => "scheduled",
+            PostStatus::Unlisted => "unlisted",
+            PostStatus::Published => "published",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_str() == status)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "Draft",
+            PostStatus::Scheduled => "Scheduled",
+            PostStatus::Unlisted => "Unlisted",
+            PostStatus::Published => "Published",
+        }
+    }
+}
+
+/// Reading speed used for [`PostMeta::reading_minutes`].
+pub const WORDS_PER_MINUTE: usize = 220;
+
+/// Everything about a post except its body.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PostMeta {
+    /// File name without the `.md` extension, used in `/blog/<slug>`.
+    pub slug: String,
+    pub title: String,
+    pub date: NaiveDate,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub status: PostStatus,
+    /// When a scheduled post goes live.
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Words of prose in the body (see [`RenderedPost::word_count`]).
+    pub word_count: usize,
+    /// The multi-part series the post belongs to, by name.
+    pub series: Option<String>,
+    /// Position in the series; parts without one follow in date order.
+    pub series_part: Option<u32>,
+    /// Where the post first appeared, for posts brought over from
+    /// elsewhere.
+    pub canonical_url: Option<String>,
+    /// Copies of the post on other sites (dev.to, Hashnode, ...).
+    pub syndication: Vec<String>,
+    /// Image for link previews, absolute or site-relative.
+    pub image: Option<String>,
+}
+
+impl PostMeta {
+    /// The status in effect at `now`: scheduled posts count as published
+    /// once `publish_at` has passed. Every visibility check goes through
+    /// here.
+    pub fn status_at(&self, now: DateTime<Utc>) -> PostStatus {
+        match (self.status, self.publish_at) {
+            (PostStatus::Scheduled, Some(at)) if at <= now => PostStatus::Published,
+            (status, _) => status,
+        }
+    }
+
+    /// Whether the post appears in listings, archives, feeds, the sitemap
+    /// and search.
+    pub fn is_listed(&self, now: DateTime<Utc>) -> bool {
+        self.status_at(now) == PostStatus::Published
+    }
+
+    /// Whether `/blog/<slug>` serves the post. Editors see every post.
+    pub fn is_reachable(&self, now: DateTime<Utc>, editor: bool) -> bool {
+        editor
+            || matches!(
+                self.status_at(now),
+                PostStatus::Published | PostStatus::Unlisted
+            )
+    }
+
+    /// Minutes to read the post at [`WORDS_PER_MINUTE`], at least one.
+    pub fn reading_minutes(&self) -> usize {
+        self.word_count.div_ceil(WORDS_PER_MINUTE).max(1)
+    }
+
+    /// The URL search engines should treat as the post: where it first
+    /// appeared, or its page here.
+    pub fn canonical(&self, site_url: &str) -> String {
+        self.canonical_url
+            .clone()
+            .unwrap_or_else(|| crate::models::feed::post_url(site_url, &self.slug))
+    }
+
+    /// Whether the post is tagged with `slug` (see [`tag_slug`]).
+    pub fn has_tag(&self, slug: &str) -> bool {
+        self.tags.iter().any(|tag| tag_slug(tag) == slug)
+    }
+}
+
+/// A piece of a rendered post. Top-level code blocks are kept apart so the
+/// page can render them with interactive components; code nested in lists
+/// or quotes stays inline in the surrounding HTML.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum PostBlock {
+    Html(String),
+    Code(CodeBlock),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Post {
+    pub meta: PostMeta,
+    pub markdown: String,
+    pub blocks: Vec<PostBlock>,
+    pub toc: Vec<TocEntry>,
+}
+
+impl Post {
+    /// The whole body as one HTML string.
+    pub fn html(&self) -> String {
+        blocks_to_html(&self.blocks)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FrontMatter {
+    title: String,
+    /// Defaults to the day of `publish_at`.
+    date: Option<NaiveDate>,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Shorthand for `status: draft`.
+    #[serde(default)]
+    draft: bool,
+    status: Option<PostStatus>,
+    publish_at: Option<String>,
+    series: Option<String>,
+    series_part: Option<u32>,
+    canonical_url: Option<String>,
+    #[serde(default)]
+    syndication: Vec<String>,
+    image: Option<String>,
+}
+
+/// Accepts RFC 3339 (`2026-11-02T09:00:00+01:00`), a UTC date and time
+/// (`2026-11-02 09:00`) or a bare date (midnight UTC).
+pub fn parse_publish_at(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(at) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(at.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        .map_err(|_| format!("Invalid publish_at '{}'", value))
+}
+
+impl FrontMatter {
+    /// The status and publish time, checked for contradictions. Setting
+    /// `publish_at` alone schedules the post.
+    fn lifecycle(&self) -> Result<(PostStatus, Option<DateTime<Utc>>), String> {
+        let publish_at = self
+            .publish_at
+            .as_deref()
+            .map(parse_publish_at)
+            .transpose()?;
+        let status = match (self.draft, self.status) {
+            (true, None | Some(PostStatus::Draft)) => PostStatus::Draft,
+            (true, Some(status)) => {
+                return Err(format!(
+                    "draft = true conflicts with status = {}",
+                    status.as_str()
+                ))
+            }
+            (false, Some(status)) => status,
+            (false, None) if publish_at.is_some() => PostStatus::Scheduled,
+            (false, None) => PostStatus::Published,
+        };
+        match (status, publish_at) {
+            (PostStatus::Scheduled, None) => {
+                Err("Scheduled posts need a publish_at time".to_string())
+            }
+            (PostStatus::Scheduled, at) | (_, at @ None) => Ok((status, at)),
+            (_, Some(_)) => Err("publish_at only applies to scheduled posts".to_string()),
+        }
+    }
+
+    /// The series name and part, checked for contradictions.
+    fn series(&self) -> Result<(Option<String>, Option<u32>), String> {
+        let series = self
+            .series
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+        match (&series, self.series_part) {
+            (None, Some(_)) => Err("series_part needs a series".to_string()),
+            (_, Some(0)) => Err("series_part starts at 1".to_string()),
+            _ => Ok((series, self.series_part)),
+        }
+    }
+
+    /// The canonical URL, which has to be absolute.
+    fn canonical_url(&self) -> Result<Option<String>, String> {
+        match self.canonical_url.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(url) => absolute_url("canonical_url", url).map(Some),
+        }
+    }
+
+    /// The syndication links, which have to be absolute. Blank entries
+    /// and repeats are dropped.
+    fn syndication(&self) -> Result<Vec<String>, String> {
+        let mut links: Vec<String> = Vec::new();
+        for url in self.syndication.iter().map(|url| url.trim()) {
+            if !url.is_empty() && !links.iter().any(|link| link == url) {
+                links.push(absolute_url("syndication", url)?);
+            }
+        }
+        Ok(links)
+    }
+
+    /// The preview image: an absolute URL or a path on this site.
+    fn image(&self) -> Result<Option<String>, String> {
+        match self.image.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(path) if path.starts_with('/') && !path.starts_with("//") => {
+                Ok(Some(path.to_string()))
+            }
+            Some(url) => absolute_url("image", url).map(Some),
+        }
+    }
+}
+
+/// Checks that the `field` value `url` is an absolute http(s) URL.
+fn absolute_url(field: &str, url: &str) -> Result<String, String> {
+    if (url.starts_with("https://") || url.starts_with("http://"))
+        && !url.contains(char::is_whitespace)
+    {
+        Ok(url.to_string())
+    } else {
+        Err(format!(
+            "{} must be an absolute http(s) URL, not '{}'",
+            field, url
+        ))
+    }
+}
+
+/// Slugs double as file names and URL segments: lowercase ASCII letters,
+/// digits and single dashes.
+pub fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && !slug.contains("--")
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+fn parse_front_matter(source: &str) -> Result<(FrontMatter, &str), String> {
+    let (delimiter, is_toml) = if source.starts_with("---") {
+        ("---", false)
+    } else if source.starts_with("+++") {
+        ("+++", true)
+    } else {
+        return Err("Missing front matter (expected --- or +++ on the first line)".to_string());
+    };
+
+    let rest = source[3..]
+        .strip_prefix("\r\n")
+        .or_else(|| source[3..].strip_prefix('\n'))
+        .ok_or("Front matter delimiter must be on its own line")?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == delimiter {
+            let front = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            let front_matter = if is_toml {
+                let mut table: toml::Table = toml::from_str(front).map_err(|e| e.to_string())?;
+                // Bare TOML dates deserialize as a datetime, not a string
+                for key in ["date", "publish_at"] {
+                    if let Some(toml::Value::Datetime(date)) = table.get(key) {
+                        table.insert(key.to_string(), toml::Value::String(date.to_string()));
+                    }
+                }
+                table
+                    .try_into()
+                    .map_err(|e: toml::de::Error| e.to_string())?
+            } else {
+                serde_yaml::from_str(front).map_err(|e| e.to_string())?
+            };
+            return Ok((front_matter, body));
+        }
+        offset += line.len();
+    }
+    Err(format!(
+        "Unterminated front matter (missing closing {})",
+        delimiter
+    ))
+}
+
+/// Parses a post file. `slug` comes from the file name.
+pub fn parse_post(slug: &str, source: &str) -> Result<Post, String> {
+    if !is_valid_slug(slug) {
+        return Err(format!("Invalid slug '{}'", slug));
+    }
+    let (front, body) = parse_front_matter(source)?;
+    if front.title.trim().is_empty() {
+        return Err("Title must not be empty".to_string());
+    }
+    let (status, publish_at) = front.lifecycle()?;
+    let (series, series_part) = front.series()?;
+    let canonical_url = front.canonical_url()?;
+    let syndication = front.syndication()?;
+    let image = front.image()?;
+    let date = front
+        .date
+        .or(publish_at.map(|at| at.date_naive()))
+        .ok_or("Missing date")?;
+
+    let rendered = render_post(body);
+    Ok(Post {
+        meta: PostMeta {
+            slug: slug.to_string(),
+            title: front.title,
+            date,
+            description: front.description,
+            tags: normalize_tags(front.tags),
+            status,
+            publish_at,
+            word_count: rendered.word_count,
+            series,
+            series_part,
+            canonical_url,
+            syndication,
+            image,
+        },
+        markdown: body.trim_start().to_string(),
+        blocks: rendered.blocks,
+        toc: rendered.toc,
+    })
+}
+
+/// Only web and mail links are allowed; relative URLs and fragments always are.
+pub fn is_safe_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside the scheme
+    let cleaned: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+    match cleaned.find([':', '/', '?', '#']) {
+        Some(i) if cleaned[i..].starts_with(':') => {
+            matches!(&cleaned[..i], "http" | "https" | "mailto")
+        }
+        _ => true,
+    }
+}
+
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    if is_safe_url(&url) {
+        url
+    } else {
+        CowStr::Borrowed("#")
+    }
+}
+
+/// Renders Markdown to HTML. Raw HTML in the source is shown as text and
+/// links or images with scripting schemes are neutralised, so the result can
+/// be inserted into the page as is.
+pub fn render_markdown(markdown: &str) -> String {
+    blocks_to_html(&render_blocks(markdown))
+}
+
+pub fn blocks_to_html(blocks: &[PostBlock]) -> String {
+    blocks
+        .iter()
+        .map(|block| match block {
+            PostBlock::Html(html) => html.clone(),
+            PostBlock::Code(code) => code.to_html(),
+        })
+        .collect()
+}
+
+/// Like [`render_markdown`], with code blocks highlighted and split out.
+pub fn render_blocks(markdown: &str) -> Vec<PostBlock> {
+    render_post(markdown).blocks
+}
+
+/// A post body rendered by [`render_post`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RenderedPost {
+    pub blocks: Vec<PostBlock>,
+    pub toc: Vec<TocEntry>,
+    /// Words of prose, not counting code blocks.
+    pub word_count: usize,
+}
+
+/// Renders a post body. Headings get ids from their text (see
+/// [`heading_slug`](crate::models::toc::heading_slug)) and a `#` link, and
+/// are collected into a table of contents.
+pub fn render_post(markdown: &str) -> RenderedPost {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+    let mut code_blocks = Vec::new();
+    // The code block being read: its fence info, source and whether it is
+    // at the top level of the post
+    let mut current: Option<(String, String, bool)> = None;
+    // How many elements are open; only top-level code blocks are split out,
+    // as splitting inside a list or quote would cut its markup in two
+    let mut depth = 0usize;
+    let mut events = Vec::new();
+    // An uploaded image being read: the image, its title and its alt text
+    let mut media: Option<(MediaImage, CowStr, String)> = None;
+    // The heading being read: where it starts in `events` and its text
+    let mut heading: Option<(usize, String)> = None;
+    let mut heading_ids = HeadingIds::default();
+    let mut headings = Vec::new();
+    let mut word_count = 0;
+    for event in Parser::new_ext(markdown, options) {
+        let top_level = depth == 0;
+        match &event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if let Some((_, _, alt)) = media.as_mut() {
+            match event {
+                Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                Event::End(TagEnd::Image) => {
+                    if let Some((image, title, alt)) = media.take() {
+                        events.push(Event::Html(image.to_html(&alt, &title).into()));
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if let Event::Text(text) | Event::Code(text) = &event {
+            if current.is_none() {
+                word_count += text.split_whitespace().count();
+            }
+            if let Some((_, title)) = heading.as_mut() {
+                title.push_str(text);
+            }
+        }
+        match event {
+            Event::Start(Tag::Heading {
+                level,
+                id: _,
+                classes,
+                attrs,
+            }) => {
+                heading = Some((events.len(), String::new()));
+                events.push(Event::Start(Tag::Heading {
+                    level,
+                    id: None,
+                    classes,
+                    attrs,
+                }));
+            }
+            Event::End(TagEnd::Heading(level)) => {
+                if let Some((start, title)) = heading.take() {
+                    let id = heading_ids.id_for(&title);
+                    if let Event::Start(Tag::Heading { id: slot, .. }) = &mut events[start] {
+                        *slot = Some(id.clone().into());
+                    }
+                    events.push(Event::Html(
+                        format!(
+                            "<a class=\"heading-anchor\" href=\"#{}\" aria-label=\"Link to this section\">#</a>",
+                            id
+                        )
+                        .into(),
+                    ));
+                    headings.push((level as u8, id, title.trim().to_string()));
+                }
+                events.push(Event::End(TagEnd::Heading(level)));
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let info = match kind {
+                    CodeBlockKind::Fenced(info) => info.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                current = Some((info, String::new(), top_level));
+            }
+            Event::Text(text) if current.is_some() => {
+                if let Some((_, code, _)) = current.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => match current.take() {
+                Some((info, code, true)) => {
+                    // Raw HTML from the source is escaped below, so this
+                    // marker cannot be forged by post content
+                    events.push(Event::Html(
+                        format!("<!--code:{}-->", code_blocks.len()).into(),
+                    ));
+                    code_blocks.push(CodeBlock::new(&info, code));
+                }
+                Some((info, code, false)) => {
+                    events.push(Event::Html(CodeBlock::new(&info, code).to_html().into()));
+                }
+                None => {}
+            },
+            Event::Html(raw) | Event::InlineHtml(raw) => events.push(Event::Text(raw)),
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => events.push(Event::Start(Tag::Link {
+                link_type,
+                dest_url: safe_url(dest_url),
+                title,
+                id,
+            })),
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => match MediaImage::from_url(&dest_url) {
+                Some(image) => media = Some((image, title, String::new())),
+                None => events.push(Event::Start(Tag::Image {
+                    link_type,
+                    dest_url: safe_url(dest_url),
+                    title,
+                    id,
+                })),
+            },
+            other => events.push(other),
+        }
+    }
+
+    // Rendered in one pass so footnote numbering spans the whole post
+    let mut output = String::new();
+    html::push_html(&mut output, events.into_iter());
+
+    let mut blocks = Vec::new();
+    let mut rest = output.as_str();
+    for (i, code) in code_blocks.into_iter().enumerate() {
+        let marker = format!("<!--code:{}-->", i);
+        let (before, after) = rest.split_once(&marker).unwrap_or((rest, ""));
+        if !before.is_empty() {
+            blocks.push(PostBlock::Html(before.to_string()));
+        }
+        blocks.push(PostBlock::Code(code));
+        rest = after.strip_prefix('\n').unwrap_or(after);
+    }
+    if !rest.is_empty() {
+        blocks.push(PostBlock::Html(rest.to_string()));
+    }
+    RenderedPost {
+        blocks,
+        toc: build_toc(&headings),
+        word_count,
+    }
+}
+
+/// The text of a post without Markdown syntax, for search and summaries.
+/// Code is kept; block boundaries become line breaks.
+pub fn plain_text(markdown: &str) -> String {
+    let mut text = String::new();
+    for event in Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_FOOTNOTES) {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::SoftBreak => text.push(' '),
+            Event::HardBreak
+            | Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::CodeBlock
+                | TagEnd::TableCell,
+            ) if !text.ends_with('\n') => text.push('\n'),
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+/// Loads every `*.md` file in `dir`, newest first. Files that cannot be
+/// read or parsed are logged and skipped, so one broken post does not take
+/// the whole blog down.
+pub fn load_posts(dir: &Path) -> Result<Vec<Post>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Cannot read {}: {}", dir.display(), e))?;
+    let mut posts = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        let slug = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        let post = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+            .and_then(|source| {
+                parse_post(slug, &source).map_err(|e| format!("{}: {}", path.display(), e))
+            });
+        match post {
+            Ok(post) => posts.push(post),
+            Err(e) => dioxus::logger::tracing::warn!("Skipping post: {}", e),
+        }
+    }
+    sort_posts(&mut posts);
+    Ok(posts)
+}
+
+/// Newest first; posts from the same day by slug.
+pub fn sort_posts(posts: &mut [Post]) {
+    posts.sort_by(|a, b| {
+        b.meta
+            .date
+            .cmp(&a.meta.date)
+            .then_with(|| a.meta.slug.cmp(&b.meta.slug))
+    });
+}
+
+/// Posts written in the editor replace files with the same slug.
+pub fn merge_posts(files: Vec<Post>, saved: Vec<Post>) -> Vec<Post> {
+    let mut posts: Vec<Post> = files
+        .into_iter()
+        .filter(|file| !saved.iter().any(|post| post.meta.slug == file.meta.slug))
+        .collect();
+    posts.extend(saved);
+    sort_posts(&mut posts);
+    posts
+}
+
+/// `$CONTENT_DIR/posts` (default `content/posts`).
+#[cfg(feature = "server")]
+pub fn posts_dir() -> std::path::PathBuf {
+    let dir = std::env::var("CONTENT_DIR").unwrap_or_else(|_| "content".to_string());
+    Path::new(&dir).join("posts")
+}
+
+/// Names, sizes and modification times of the `*.md` files in `dir`. Cheap
+/// to read, and changes whenever a post file is added, removed or edited.
+pub fn dir_fingerprint(dir: &Path) -> Vec<(String, u64, Option<std::time::SystemTime>)> {
+    let mut files: Vec<_> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.ends_with(".md") {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            Some((name, metadata.len(), metadata.modified().ok()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(feature = "server")]
+type PostsKey = (Vec<(String, u64, Option<std::time::SystemTime>)>, u64);
+
+/// The parsed post set, the key it was loaded under and its version.
+#[cfg(feature = "server")]
+static POSTS: std::sync::Mutex<Option<(PostsKey, u64, Vec<Post>)>> = std::sync::Mutex::new(None);
+
+/// Runs `f` on the posts from [`posts_dir`] merged with those saved from
+/// the editor. Parsing and highlighting every post is expensive, so the set
+/// is cached until a post file changes or a post is saved from the editor,
+/// and `f` borrows it instead of copying it; clone only what is returned.
+/// The version changes whenever the set is reloaded, for caches built on
+/// top of it. The cache stays locked while `f` runs.
+#[cfg(feature = "server")]
+pub fn with_posts<R>(f: impl FnOnce(u64, &[Post]) -> R) -> Result<R, ServerFnError> {
+    // Read before loading, so a save during the load triggers a reload
+    let key = (
+        dir_fingerprint(&posts_dir()),
+        crate::backend::posts::generation(),
+    );
+    let mut cache = POSTS.lock().unwrap_or_else(|e| e.into_inner());
+    let version = match cache.as_ref() {
+        Some((cached, version, _)) if *cached == key => *version,
+        cached => {
+            let version = cached.map_or(0, |(_, version, _)| version + 1);
+            *cache = Some((key, version, load_all_posts()?));
+            version
+        }
+    };
+    let posts = cache.as_ref().map_or(&[][..], |(_, _, posts)| posts);
+    Ok(f(version, posts))
+}
+
+#[cfg(feature = "server")]
+fn load_all_posts() -> Result<Vec<Post>, ServerFnError> {
+    let files = load_posts(&posts_dir()).map_err(ServerFnError::new)?;
+    let conn = crate::backend::db::connect().map_err(ServerFnError::new)?;
+    // Like broken files, saved posts that no longer parse are skipped
+    let saved = crate::backend::posts::saved(&conn)
+        .map_err(ServerFnError::new)?
+        .iter()
+        .filter_map(|saved| match parse_post(&saved.slug, &saved.source) {
+            Ok(post) => Some(post),
+            Err(e) => {
+                dioxus::logger::tracing::warn!("Skipping post: {} (saved): {}", saved.slug, e);
+                None
+            }
+        })
+        .collect();
+    Ok(merge_posts(files, saved))
+}
+
+/// Posts listed on the public site right now, newest first.
+#[cfg(feature = "server")]
+pub fn listed_posts() -> Result<Vec<Post>, ServerFnError> {
+    let now = Utc::now();
+    with_posts(|_, posts| {
+        posts
+            .iter()
+            .filter(|post| post.meta.is_listed(now))
+            .cloned()
+            .collect()
+    })
+}
+
+/// Metadata of [`listed_posts`], without copying the posts.
+#[cfg(feature = "server")]
+pub fn public_posts() -> Result<Vec<PostMeta>, ServerFnError> {
+    let now = Utc::now();
+    with_posts(|_, posts| {
+        posts
+            .iter()
+            .filter(|post| post.meta.is_listed(now))
+            .map(|post| post.meta.clone())
+            .collect()
+    })
+}
+
+#[server]
+pub async fn list_posts() -> Result<Vec<PostMeta>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        public_posts()
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+#[server]
+pub async fn get_post(slug: String) -> Result<Post, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let editor = crate::backend::session::current_user()
+            .is_some_and(|user| crate::backend::session::is_editor(&user.email));
+        let now = Utc::now();
+        with_posts(|_, posts| {
+            posts
+                .iter()
+                .find(|p| p.meta.slug == slug && p.meta.is_reachable(now, editor))
+                .cloned()
+        })?
+        .ok_or_else(|| ServerFnError::new(format!("Post not found: {}", slug)))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
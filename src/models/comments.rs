@@ -0,0 +1,358 @@
+//! Comments on blog posts, stored in SQLite (see `backend::comments`).
+//!
+//! Logged-in readers comment and reply one level deep. Comments from anyone
+//! but the owner wait in a moderation queue until approved; their authors
+//! see them in the meantime.
+
+use crate::models::blog::is_safe_url;
+use crate::models::highlight::escape_html;
+use dioxus::prelude::*;
+use pulldown_cmark::{html, CodeBlockKind, Event, LinkType, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+
+/// How long after posting a comment its author may edit or delete it.
+pub const EDIT_WINDOW_SECS: i64 = 15 * 60;
+pub const MAX_COMMENT_CHARS: usize = 5000;
+/// Comments one reader may post per [`RATE_WINDOW_SECS`].
+pub const RATE_LIMIT: usize = 5;
+pub const RATE_WINDOW_SECS: i64 = 10 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    /// Waiting for moderation; only its author sees it.
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+    /// Hidden like rejected comments, but its author still sees it as
+    /// pending.
+    Spam,
+}
+
+impl CommentStatus {
+    pub const ALL: [CommentStatus; 4] = [
+        CommentStatus::Pending,
+        CommentStatus::Approved,
+        CommentStatus::Rejected,
+        CommentStatus::Spam,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentStatus::Pending => "pending",
+            CommentStatus::Approved => "approved",
+            CommentStatus::Rejected => "rejected",
+            CommentStatus::Spam => "spam",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_str() == status)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CommentStatus::Pending => "Pending",
+            CommentStatus::Approved => "Approved",
+            CommentStatus::Rejected => "Rejected",
+            CommentStatus::Spam => "Spam",
+        }
+    }
+}
+
+/// A comment as one reader sees it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Comment {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    /// Shown name (see [`display_name`]); empty once deleted.
+    pub author: String,
+    pub by_owner: bool,
+    /// The body rendered with [`render_comment`]; empty once deleted.
+    pub html: String,
+    /// The Markdown source, only sent to the author and the owner.
+    pub body: String,
+    pub status: CommentStatus,
+    pub created_at: i64,
+    pub edited: bool,
+    /// Deleted comments stay as placeholders while they have replies.
+    pub deleted: bool,
+    /// Written by the reader looking at it.
+    pub mine: bool,
+}
+
+impl Comment {
+    /// Whether the reader may still edit or delete it at `now`.
+    pub fn can_edit(&self, now: i64) -> bool {
+        self.mine && !self.deleted && now < self.created_at + EDIT_WINDOW_SECS
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CommentThread {
+    pub comment: Comment,
+    pub replies: Vec<Comment>,
+}
+
+/// A comment in the moderation queue, with what the public page leaves out.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ModerationItem {
+    pub comment: Comment,
+    pub email: String,
+    pub post_slug: String,
+    pub post_title: String,
+}
+
+/// The name shown for a commenter: their email address up to the `@`, so
+/// addresses are never published.
+pub fn display_name(email: &str) -> String {
+    email.split('@').next().unwrap_or_default().to_string()
+}
+
+/// Groups comments in posting order into threads. Replies whose parent is
+/// not shown are dropped, as are deleted comments nobody replied to.
+pub fn threads(comments: Vec<Comment>) -> Vec<CommentThread> {
+    let (top, replies): (Vec<Comment>, Vec<Comment>) =
+        comments.into_iter().partition(|c| c.parent_id.is_none());
+    top.into_iter()
+        .map(|comment| CommentThread {
+            replies: replies
+                .iter()
+                .filter(|reply| reply.parent_id == Some(comment.id) && !reply.deleted)
+                .cloned()
+                .collect(),
+            comment,
+        })
+        .filter(|thread| !thread.comment.deleted || !thread.replies.is_empty())
+        .collect()
+}
+
+/// Renders a comment. Comments get a small subset of Markdown: emphasis,
+/// code, quotes, lists and links. Headings become paragraphs, images are
+/// reduced to their alt text and raw HTML is shown as text. Links are marked
+/// `nofollow ugc` so spam earns nothing.
+pub fn render_comment(text: &str) -> String {
+    let mut links = Vec::new();
+    let events = Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH).filter_map(|event| {
+        Some(match event {
+            Event::Start(Tag::Heading { .. } | Tag::HtmlBlock) => Event::Start(Tag::Paragraph),
+            Event::End(TagEnd::Heading(_) | TagEnd::HtmlBlock) => Event::End(TagEnd::Paragraph),
+            Event::Start(Tag::CodeBlock(_)) => {
+                Event::Start(Tag::CodeBlock(CodeBlockKind::Indented))
+            }
+            Event::Start(Tag::Image { .. }) | Event::End(TagEnd::Image) => return None,
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }) => {
+                let href = if link_type == LinkType::Email {
+                    format!("mailto:{}", dest_url)
+                } else {
+                    dest_url.to_string()
+                };
+                let safe = is_safe_url(&href);
+                links.push(safe);
+                if !safe {
+                    return None;
+                }
+                Event::Html(
+                    format!(
+                        "<a href=\"{}\" rel=\"nofollow ugc noopener\">",
+                        escape_html(&href)
+                    )
+                    .into(),
+                )
+            }
+            Event::End(TagEnd::Link) => {
+                if links.pop() != Some(true) {
+                    return None;
+                }
+                Event::Html("</a>".into())
+            }
+            Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+            event => event,
+        })
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    out
+}
+
+/// Fails unless `/blog/<slug>` is open to the reader, so comments of drafts
+/// and scheduled posts stay hidden.
+#[cfg(feature = "server")]
+fn require_reachable(slug: &str, editor: bool) -> Result<(), ServerFnError> {
+    let now = chrono::Utc::now();
+    if crate::models::blog::with_posts(|_, posts| {
+        posts
+            .iter()
+            .any(|post| post.meta.slug == slug && post.meta.is_reachable(now, editor))
+    })? {
+        Ok(())
+    } else {
+        Err(ServerFnError::new(format!("Post not found: {}", slug)))
+    }
+}
+
+/// The comments on a post the current reader may see.
+#[server]
+pub async fn get_comments(slug: String) -> Result<Vec<CommentThread>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{comments, db, session};
+
+        let viewer = session::current_user().map(|user| user.email);
+        require_reachable(&slug, viewer.as_deref().is_some_and(session::is_editor))?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        let stored =
+            comments::for_post(&conn, &slug).map_err(|e| ServerFnError::new(e.to_string()))?;
+        Ok(threads(comments::visible(stored, viewer.as_deref())))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Adds a comment, or a reply when `parent_id` is set. The owner's comments
+/// are approved straight away; everyone else's wait for moderation.
+#[server]
+pub async fn post_comment(
+    slug: String,
+    parent_id: Option<i64>,
+    body: String,
+) -> Result<Comment, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{comments, db, session};
+
+        let user = session::require_user()?;
+        let owner = session::is_owner(&user.email);
+        require_reachable(&slug, session::is_editor(&user.email))?;
+
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        let now = db::now();
+        if !owner {
+            comments::check_rate(&conn, &user.email, now)
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+        }
+        let status = if owner {
+            CommentStatus::Approved
+        } else {
+            CommentStatus::Pending
+        };
+        let id = comments::add(&conn, &slug, parent_id, &user.email, &body, status, now)
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        comments::get(&conn, id)
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .map(|stored| stored.to_comment(Some(&user.email)))
+            .ok_or_else(|| ServerFnError::new("Comment not found"))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Changes the text of one of the reader's comments inside the edit
+/// window. Edited comments go back through moderation.
+#[server]
+pub async fn edit_comment(id: i64, body: String) -> Result<Comment, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{comments, db, session};
+
+        let user = session::require_user()?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        comments::edit(
+            &conn,
+            id,
+            &user.email,
+            &body,
+            session::is_owner(&user.email),
+            db::now(),
+        )
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        comments::get(&conn, id)
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .map(|stored| stored.to_comment(Some(&user.email)))
+            .ok_or_else(|| ServerFnError::new("Comment not found"))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Deletes one of the reader's comments inside the edit window.
+#[server]
+pub async fn delete_comment(id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{comments, db, session};
+
+        let user = session::require_user()?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        comments::delete(&conn, id, &user.email, db::now())
+            .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Comments with `status`, newest first, for the owner's moderation queue.
+#[server]
+pub async fn list_comments(status: CommentStatus) -> Result<Vec<ModerationItem>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{comments, db, session};
+
+        session::require_owner()?;
+        let titles: std::collections::HashMap<String, String> =
+            crate::models::blog::with_posts(|_, posts| {
+                posts
+                    .iter()
+                    .map(|post| (post.meta.slug.clone(), post.meta.title.clone()))
+                    .collect()
+            })?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        let stored =
+            comments::with_status(&conn, status).map_err(|e| ServerFnError::new(e.to_string()))?;
+        Ok(stored
+            .into_iter()
+            .map(|stored| ModerationItem {
+                post_title: titles
+                    .get(&stored.post_slug)
+                    .cloned()
+                    .unwrap_or_else(|| stored.post_slug.clone()),
+                comment: stored.to_moderated_comment(),
+                email: stored.author.clone(),
+                post_slug: stored.post_slug,
+            })
+            .collect())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+#[server]
+pub async fn moderate_comment(id: i64, status: CommentStatus) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{comments, db, session};
+
+        session::require_owner()?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        comments::set_status(&conn, id, status).map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
@@ -0,0 +1,80 @@
+//! Contact exports: a vCard built from the CV basics and QR codes that point
+//! at the site or the vCard.
+
+use crate::models::cv::Basics;
+use qrcode::render::svg;
+use qrcode::QrCode;
+
+/// Escapes a vCard property value (RFC 6350, section 3.4).
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line to at most 75 octets, continuing with CRLF and a space.
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+    out
+}
+
+/// Builds a vCard 4.0 for the CV owner. `site_url` is included as the main URL.
+pub fn vcard(basics: &Basics, site_url: &str) -> String {
+    let mut lines = vec![
+        "BEGIN:VCARD".to_string(),
+        "VERSION:4.0".to_string(),
+        format!("FN:{}", escape(&basics.full_name())),
+        format!(
+            "N:{};{};;;",
+            escape(&basics.last_name),
+            escape(&basics.first_name)
+        ),
+        format!("TITLE:{}", escape(&basics.label)),
+        format!("EMAIL;TYPE=work:{}", escape(&basics.email)),
+    ];
+    if let Some(phone) = &basics.phone {
+        lines.push(format!(
+            "TEL;VALUE=uri;TYPE=cell:tel:{}",
+            phone.replace(' ', "")
+        ));
+    }
+    lines.push(format!("URL:{}", site_url));
+    for profile in &basics.profiles {
+        lines.push(format!(
+            "URL;TYPE={}:{}",
+            profile.network.to_lowercase(),
+            profile.url
+        ));
+    }
+    if !basics.image.is_empty() {
+        lines.push(format!("PHOTO:{}", basics.image));
+    }
+    lines.push("END:VCARD".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+/// Renders `data` as a standalone SVG QR code.
+pub fn qr_svg(data: &str) -> Result<String, String> {
+    let code = QrCode::new(data.as_bytes()).map_err(|e| format!("Cannot encode QR code: {}", e))?;
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(256, 256)
+        .dark_color(svg::Color("#0f1116"))
+        .light_color(svg::Color("#ffffff"))
+        .build())
+}
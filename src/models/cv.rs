@@ -0,0 +1,462 @@
+//! CV data: the master record loaded from `content/cv.toml` and the tailored
+//! variants derived from it.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Basics {
+    pub first_name: String,
+    pub last_name: String,
+    pub label: String,
+    pub summary: String,
+    pub email: String,
+    /// Only shown through share links, never on public pages.
+    #[serde(default)]
+    pub phone: Option<String>,
+    pub image: String,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+}
+
+impl Basics {
+    pub fn full_name(&self) -> String {
+        format!("{} {}", self.first_name, self.last_name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub network: String,
+    pub url: String,
+    pub icon: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Experience {
+    pub id: String,
+    pub position: String,
+    pub organization: String,
+    pub start: String,
+    #[serde(default)]
+    pub end: Option<String>,
+    pub summary: String,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+    #[serde(default)]
+    pub skills: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Skill {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub description: String,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+    #[serde(default)]
+    pub skills: Vec<String>,
+}
+
+/// A named CV profile. Entries are listed by id in the order they should
+/// appear, optionally re-wording the master text.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Variant {
+    pub slug: String,
+    pub name: String,
+    /// Private variants are only reachable through share links.
+    #[serde(default = "default_public")]
+    pub public: bool,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub experience: Vec<ExperienceSelection>,
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub projects: Vec<ProjectSelection>,
+}
+
+fn default_public() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VariantSummary {
+    pub slug: String,
+    pub name: String,
+    pub public: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExperienceSelection {
+    pub id: String,
+    #[serde(default)]
+    pub position: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub highlights: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProjectSelection {
+    pub id: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub highlights: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Cv {
+    pub basics: Basics,
+    #[serde(default)]
+    pub experience: Vec<Experience>,
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    #[serde(default)]
+    pub projects: Vec<Project>,
+    #[serde(default)]
+    pub variants: Vec<Variant>,
+}
+
+/// A CV ready to render: either the full master record or one variant of it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TailoredCv {
+    pub variant: Option<String>,
+    pub basics: Basics,
+    pub experience: Vec<Experience>,
+    pub skills: Vec<Skill>,
+    pub projects: Vec<Project>,
+}
+
+impl Cv {
+    pub fn from_toml(source: &str) -> Result<Self, String> {
+        let cv: Cv = toml::from_str(source).map_err(|e| format!("Invalid CV data: {}", e))?;
+        cv.validate()?;
+        Ok(cv)
+    }
+
+    /// Checks that every id referenced by a variant, experience or project exists.
+    pub fn validate(&self) -> Result<(), String> {
+        let skill_exists = |id: &String| self.skills.iter().any(|s| &s.id == id);
+
+        for experience in &self.experience {
+            if let Some(id) = experience.skills.iter().find(|id| !skill_exists(id)) {
+                return Err(format!(
+                    "Experience '{}' references unknown skill '{}'",
+                    experience.id, id
+                ));
+            }
+        }
+        for project in &self.projects {
+            if let Some(id) = project.skills.iter().find(|id| !skill_exists(id)) {
+                return Err(format!(
+                    "Project '{}' references unknown skill '{}'",
+                    project.id, id
+                ));
+            }
+        }
+
+        for (i, variant) in self.variants.iter().enumerate() {
+            if self.variants[..i].iter().any(|v| v.slug == variant.slug) {
+                return Err(format!("Duplicate variant '{}'", variant.slug));
+            }
+            for selection in &variant.experience {
+                if !self.experience.iter().any(|e| e.id == selection.id) {
+                    return Err(format!(
+                        "Variant '{}' references unknown experience '{}'",
+                        variant.slug, selection.id
+                    ));
+                }
+            }
+            if let Some(id) = variant.skills.iter().find(|id| !skill_exists(id)) {
+                return Err(format!(
+                    "Variant '{}' references unknown skill '{}'",
+                    variant.slug, id
+                ));
+            }
+            for selection in &variant.projects {
+                if !self.projects.iter().any(|p| p.id == selection.id) {
+                    return Err(format!(
+                        "Variant '{}' references unknown project '{}'",
+                        variant.slug, selection.id
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn variant(&self, slug: &str) -> Option<&Variant> {
+        self.variants.iter().find(|v| v.slug == slug)
+    }
+
+    /// The whole master record, in file order.
+    pub fn full(&self) -> TailoredCv {
+        TailoredCv {
+            variant: None,
+            basics: self.basics.clone(),
+            experience: self.experience.clone(),
+            skills: self.skills.clone(),
+            projects: self.projects.clone(),
+        }
+    }
+
+    /// Builds the CV for a variant, keeping only the selected entries in the
+    /// variant's order and applying its wording overrides.
+    pub fn tailor(&self, slug: &str) -> Option<TailoredCv> {
+        let variant = self.variant(slug)?;
+
+        let mut basics = self.basics.clone();
+        if let Some(label) = &variant.label {
+            basics.label = label.clone();
+        }
+        if let Some(summary) = &variant.summary {
+            basics.summary = summary.clone();
+        }
+
+        let experience = variant
+            .experience
+            .iter()
+            .filter_map(|selection| {
+                let mut entry = self
+                    .experience
+                    .iter()
+                    .find(|e| e.id == selection.id)?
+                    .clone();
+                if let Some(position) = &selection.position {
+                    entry.position = position.clone();
+                }
+                if let Some(summary) = &selection.summary {
+                    entry.summary = summary.clone();
+                }
+                if let Some(highlights) = &selection.highlights {
+                    entry.highlights = highlights.clone();
+                }
+                Some(entry)
+            })
+            .collect();
+
+        let skills = variant
+            .skills
+            .iter()
+            .filter_map(|id| self.skills.iter().find(|s| &s.id == id).cloned())
+            .collect();
+
+        let projects = variant
+            .projects
+            .iter()
+            .filter_map(|selection| {
+                let mut entry = self.projects.iter().find(|p| p.id == selection.id)?.clone();
+                if let Some(description) = &selection.description {
+                    entry.description = description.clone();
+                }
+                if let Some(highlights) = &selection.highlights {
+                    entry.highlights = highlights.clone();
+                }
+                Some(entry)
+            })
+            .collect();
+
+        Some(TailoredCv {
+            variant: Some(variant.slug.clone()),
+            basics,
+            experience,
+            skills,
+            projects,
+        })
+    }
+}
+
+impl TailoredCv {
+    /// Drops contact details that must not appear on public pages.
+    pub fn without_private(mut self) -> Self {
+        self.basics.phone = None;
+        self
+    }
+
+    /// Skills grouped by category, keeping the order in which categories first appear.
+    pub fn skills_by_category(&self) -> Vec<(String, Vec<Skill>)> {
+        let mut groups: Vec<(String, Vec<Skill>)> = Vec::new();
+        for skill in &self.skills {
+            match groups.iter_mut().find(|(c, _)| c == &skill.category) {
+                Some((_, skills)) => skills.push(skill.clone()),
+                None => groups.push((skill.category.clone(), vec![skill.clone()])),
+            }
+        }
+        groups
+    }
+
+    /// Exports the CV in the JSON Resume format (https://jsonresume.org/schema).
+    pub fn to_json_resume(&self) -> serde_json::Value {
+        serde_json::json!({
+            "basics": {
+                "name": self.basics.full_name(),
+                "label": self.basics.label,
+                "image": self.basics.image,
+                "email": self.basics.email,
+                "phone": self.basics.phone,
+                "summary": self.basics.summary,
+                "profiles": self.basics.profiles.iter().map(|p| serde_json::json!({
+                    "network": p.network,
+                    "url": p.url,
+                })).collect::<Vec<_>>(),
+            },
+            "work": self.experience.iter().map(|e| serde_json::json!({
+                "name": e.organization,
+                "position": e.position,
+                "startDate": e.start,
+                "endDate": e.end,
+                "summary": e.summary,
+                "highlights": e.highlights,
+            })).collect::<Vec<_>>(),
+            "skills": self.skills.iter().map(|s| serde_json::json!({
+                "name": s.name,
+                "keywords": s.keywords,
+            })).collect::<Vec<_>>(),
+            "projects": self.projects.iter().map(|p| serde_json::json!({
+                "name": p.name,
+                "url": p.url,
+                "description": p.description,
+                "highlights": p.highlights,
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# {}\n\n**{}**\n\n{}\n\n",
+            self.basics.full_name(),
+            self.basics.label,
+            self.basics.summary
+        );
+
+        let mut contact = vec![format!("<{}>", self.basics.email)];
+        if let Some(phone) = &self.basics.phone {
+            contact.push(phone.clone());
+        }
+        contact.extend(
+            self.basics
+                .profiles
+                .iter()
+                .map(|p| format!("[{}]({})", p.network, p.url)),
+        );
+        out.push_str(&contact.join(" · "));
+        out.push_str("\n\n");
+
+        if !self.experience.is_empty() {
+            out.push_str("## Experience\n\n");
+            for e in &self.experience {
+                out.push_str(&format!(
+                    "### {} — {}\n\n*{} – {}*\n\n{}\n\n",
+                    e.position,
+                    e.organization,
+                    e.start,
+                    e.end.as_deref().unwrap_or("Present"),
+                    e.summary
+                ));
+                for highlight in &e.highlights {
+                    out.push_str(&format!("- {}\n", highlight));
+                }
+                if !e.highlights.is_empty() {
+                    out.push('\n');
+                }
+            }
+        }
+
+        if !self.skills.is_empty() {
+            out.push_str("## Skills\n\n");
+            for (category, skills) in self.skills_by_category() {
+                let names: Vec<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+                out.push_str(&format!("- **{}:** {}\n", category, names.join(", ")));
+            }
+            out.push('\n');
+        }
+
+        if !self.projects.is_empty() {
+            out.push_str("## Projects\n\n");
+            for p in &self.projects {
+                out.push_str(&format!(
+                    "### [{}]({})\n\n{}\n\n",
+                    p.name, p.url, p.description
+                ));
+                for highlight in &p.highlights {
+                    out.push_str(&format!("- {}\n", highlight));
+                }
+                if !p.highlights.is_empty() {
+                    out.push('\n');
+                }
+            }
+        }
+
+        out.trim_end().to_string() + "\n"
+    }
+}
+
+/// The master CV bundled with the server binary.
+#[cfg(feature = "server")]
+pub fn master() -> &'static Cv {
+    static CV: std::sync::OnceLock<Cv> = std::sync::OnceLock::new();
+    CV.get_or_init(|| {
+        Cv::from_toml(include_str!("../../content/cv.toml")).expect("content/cv.toml is invalid")
+    })
+}
+
+/// Loads the full CV, or the public variant named by `variant`.
+#[server]
+pub async fn get_cv(variant: Option<String>) -> Result<TailoredCv, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let cv = master();
+        let tailored = match variant {
+            Some(slug) => cv
+                .variant(&slug)
+                .filter(|v| v.public)
+                .and_then(|v| cv.tailor(&v.slug))
+                .ok_or_else(|| ServerFnError::new(format!("Unknown CV variant: {}", slug)))?,
+            None => cv.full(),
+        };
+        Ok(tailored.without_private())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// All variants, including private ones, for the owner's tools.
+#[server]
+pub async fn list_cv_variants() -> Result<Vec<VariantSummary>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        crate::backend::session::require_owner()?;
+        Ok(master()
+            .variants
+            .iter()
+            .map(|v| VariantSummary {
+                slug: v.slug.clone(),
+                name: v.name.clone(),
+                public: v.public,
+            })
+            .collect())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
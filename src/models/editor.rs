@@ -0,0 +1,455 @@
+//! The in-browser post editor. Posts saved here live in SQLite and replace
+//! any file in `content/posts` with the same slug (see `blog::with_posts`), so
+//! publishing does not need a redeploy.
+
+use crate::models::blog::{parse_post, Post, PostBlock, PostMeta, PostStatus};
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The editor form. Fields are kept as typed so half-finished input
+/// survives autosave; [`PostDraft::parse`] validates them. Fields missing
+/// from older autosaves are left empty.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct PostDraft {
+    pub slug: String,
+    pub title: String,
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub description: String,
+    /// Comma separated.
+    pub tags: String,
+    pub status: PostStatus,
+    /// `YYYY-MM-DDTHH:MM` in UTC, as produced by `datetime-local` inputs.
+    /// Only used for scheduled posts.
+    pub publish_at: String,
+    /// Series name; empty for standalone posts.
+    pub series: String,
+    /// Part number within the series, or empty.
+    pub series_part: String,
+    /// Where the post first appeared, or empty.
+    pub canonical_url: String,
+    /// Copies of the post elsewhere, one URL per line.
+    pub syndication: String,
+    /// Preview image URL, or empty.
+    pub image: String,
+    pub body: String,
+}
+
+#[derive(Serialize)]
+struct FrontMatterOut<'a> {
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<&'a str>,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    description: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<&'a str>,
+    status: PostStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    publish_at: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    series: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    series_part: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    canonical_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    syndication: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+}
+
+impl PostDraft {
+    /// An empty draft for a new post dated `today`.
+    pub fn new(slug: &str, today: NaiveDate) -> Self {
+        PostDraft {
+            slug: slug.to_string(),
+            date: today.to_string(),
+            status: PostStatus::Draft,
+            ..Default::default()
+        }
+    }
+
+    pub fn from_post(post: &Post) -> Self {
+        let meta = &post.meta;
+        PostDraft {
+            slug: meta.slug.clone(),
+            title: meta.title.clone(),
+            date: meta.date.to_string(),
+            description: meta.description.clone(),
+            tags: meta.tags.join(", "),
+            status: meta.status,
+            publish_at: meta
+                .publish_at
+                .map(|at| at.format("%Y-%m-%dT%H:%M").to_string())
+                .unwrap_or_default(),
+            series: meta.series.clone().unwrap_or_default(),
+            series_part: meta
+                .series_part
+                .map(|part| part.to_string())
+                .unwrap_or_default(),
+            canonical_url: meta.canonical_url.clone().unwrap_or_default(),
+            syndication: meta.syndication.join("\n"),
+            image: meta.image.clone().unwrap_or_default(),
+            body: post.markdown.clone(),
+        }
+    }
+
+    pub fn tag_list(&self) -> Vec<&str> {
+        self.tags
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+
+    pub fn syndication_list(&self) -> Vec<&str> {
+        self.syndication.split_whitespace().collect()
+    }
+
+    /// The post as a Markdown file with YAML front matter, in the format
+    /// `content/posts` uses.
+    pub fn to_source(&self) -> String {
+        let date = self.date.trim();
+        let publish_at = self.publish_at.trim();
+        let series = self.series.trim();
+        let canonical_url = self.canonical_url.trim();
+        let image = self.image.trim();
+        let front = FrontMatterOut {
+            title: self.title.trim(),
+            date: (!date.is_empty()).then_some(date),
+            description: self.description.trim(),
+            tags: self.tag_list(),
+            status: self.status,
+            publish_at: (self.status == PostStatus::Scheduled && !publish_at.is_empty())
+                .then_some(publish_at),
+            series: (!series.is_empty()).then_some(series),
+            series_part: self.series_part.trim().parse().ok(),
+            canonical_url: (!canonical_url.is_empty()).then_some(canonical_url),
+            syndication: self.syndication_list(),
+            image: (!image.is_empty()).then_some(image),
+        };
+        let yaml = serde_yaml::to_string(&front).unwrap_or_default();
+        format!("---\n{}---\n\n{}", yaml, self.body.trim_start())
+    }
+
+    /// Validates the draft the same way post files are.
+    pub fn parse(&self) -> Result<Post, String> {
+        let part = self.series_part.trim();
+        if !part.is_empty() && part.parse::<u32>().is_err() {
+            return Err(format!("Invalid series part '{}'", part));
+        }
+        parse_post(&self.slug, &self.to_source())
+    }
+
+    /// A copy with the status changed, for publishing and unpublishing.
+    pub fn with_status(&self, status: PostStatus) -> Self {
+        PostDraft {
+            status,
+            publish_at: if status == PostStatus::Scheduled {
+                self.publish_at.clone()
+            } else {
+                String::new()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Inserts `snippet` into the body as a paragraph of its own, at
+    /// `cursor` (a UTF-16 offset, as browsers report it) or at the end.
+    pub fn insert_block(&mut self, cursor: Option<usize>, snippet: &str) {
+        let mut at = self.body.len();
+        if let Some(cursor) = cursor {
+            let mut units = 0;
+            for (i, c) in self.body.char_indices() {
+                if units >= cursor {
+                    at = i;
+                    break;
+                }
+                units += c.len_utf16();
+            }
+        }
+        let (before, after) = self.body.split_at(at);
+        let before = before.trim_end_matches('\n');
+        let after = after.trim_start_matches('\n');
+        let mut body = before.to_string();
+        if !body.is_empty() {
+            body.push_str("\n\n");
+        }
+        body.push_str(snippet);
+        body.push('\n');
+        if !after.is_empty() {
+            body.push('\n');
+            body.push_str(after);
+        }
+        self.body = body;
+    }
+}
+
+/// Where a post's current version comes from.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostOrigin {
+    /// A file in `content/posts`.
+    File,
+    /// Saved from the editor.
+    Saved,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PostSummary {
+    pub meta: PostMeta,
+    pub origin: PostOrigin,
+    /// Unix time of an autosave not yet saved.
+    pub autosaved_at: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Autosave {
+    pub draft: PostDraft,
+    pub saved_at: i64,
+}
+
+/// What the editor opens with.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EditorState {
+    pub draft: PostDraft,
+    /// `None` for a post that does not exist yet.
+    pub origin: Option<PostOrigin>,
+    /// Unix time of the last save from the editor.
+    pub updated_at: Option<i64>,
+    pub autosave: Option<Autosave>,
+}
+
+/// The rendered body plus any problem that would stop the draft saving.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Preview {
+    pub blocks: Vec<PostBlock>,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "server")]
+fn connect() -> Result<rusqlite::Connection, ServerFnError> {
+    crate::backend::db::connect().map_err(ServerFnError::new)
+}
+
+/// Before the editor first overwrites a file post, keeps the file as a
+/// revision so the overwrite can be undone.
+#[cfg(feature = "server")]
+fn keep_file_version(conn: &rusqlite::Connection, slug: &str) -> Result<(), ServerFnError> {
+    use crate::backend::{db, posts};
+
+    if posts::get(conn, slug)
+        .map_err(ServerFnError::new)?
+        .is_some()
+    {
+        return Ok(());
+    }
+    let path = crate::models::blog::posts_dir().join(format!("{}.md", slug));
+    if let Ok(source) = std::fs::read_to_string(path) {
+        posts::record_file_revision(conn, slug, &source, db::now()).map_err(ServerFnError::new)?;
+    }
+    Ok(())
+}
+
+/// Every post, including drafts and scheduled ones, newest first.
+#[server]
+pub async fn list_all_posts() -> Result<Vec<PostSummary>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{posts, session};
+
+        session::require_editor()?;
+        let conn = connect()?;
+        let saved = posts::saved(&conn).map_err(ServerFnError::new)?;
+        let autosaves = posts::autosaves(&conn).map_err(ServerFnError::new)?;
+        crate::models::blog::with_posts(|_, posts| {
+            posts
+                .iter()
+                .map(|post| PostSummary {
+                    origin: if saved.iter().any(|s| s.slug == post.meta.slug) {
+                        PostOrigin::Saved
+                    } else {
+                        PostOrigin::File
+                    },
+                    autosaved_at: autosaves
+                        .iter()
+                        .find(|(slug, _)| *slug == post.meta.slug)
+                        .map(|(_, at)| *at),
+                    meta: post.meta.clone(),
+                })
+                .collect()
+        })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+#[server]
+pub async fn get_post_editor(slug: String) -> Result<EditorState, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{posts, session};
+
+        session::require_editor()?;
+        if !crate::models::blog::is_valid_slug(&slug) {
+            return Err(ServerFnError::new(format!("Invalid slug '{}'", slug)));
+        }
+        let conn = connect()?;
+        let saved = posts::get(&conn, &slug).map_err(ServerFnError::new)?;
+        let post = crate::models::blog::with_posts(|_, posts| {
+            posts.iter().find(|post| post.meta.slug == slug).cloned()
+        })?;
+        let autosave = match posts::autosave_for(&conn, &slug).map_err(ServerFnError::new)? {
+            Some((json, saved_at)) => Some(Autosave {
+                draft: serde_json::from_str(&json).map_err(ServerFnError::new)?,
+                saved_at,
+            }),
+            None => None,
+        };
+
+        Ok(EditorState {
+            draft: match &post {
+                Some(post) => PostDraft::from_post(post),
+                None => PostDraft::new(&slug, chrono::Utc::now().date_naive()),
+            },
+            origin: post.map(|_| {
+                if saved.is_some() {
+                    PostOrigin::Saved
+                } else {
+                    PostOrigin::File
+                }
+            }),
+            updated_at: saved.map(|saved| saved.updated_at),
+            autosave,
+        })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Renders a draft with the public renderer. Highlighting runs on the
+/// server, so the preview matches the published page.
+#[server]
+pub async fn preview_post(draft: PostDraft) -> Result<Preview, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        crate::backend::session::require_editor()?;
+        Ok(Preview {
+            blocks: crate::models::blog::render_blocks(&draft.body),
+            error: draft.parse().err(),
+        })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Stores the editor state without publishing anything. Returns the time
+/// of the autosave.
+#[server]
+pub async fn autosave_post(draft: PostDraft) -> Result<i64, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, posts, session};
+
+        session::require_editor()?;
+        if !crate::models::blog::is_valid_slug(&draft.slug) {
+            return Err(ServerFnError::new(format!("Invalid slug '{}'", draft.slug)));
+        }
+        let json = serde_json::to_string(&draft).map_err(ServerFnError::new)?;
+        let now = db::now();
+        posts::autosave(&connect()?, &draft.slug, &json, now).map_err(ServerFnError::new)?;
+        Ok(now)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Validates and saves a draft, which goes live according to its status.
+#[server]
+pub async fn save_post(draft: PostDraft) -> Result<PostMeta, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, posts, session};
+
+        let user = session::require_editor()?;
+        let post = draft.parse().map_err(ServerFnError::new)?;
+        let conn = connect()?;
+        keep_file_version(&conn, &draft.slug)?;
+        posts::save(
+            &conn,
+            &draft.slug,
+            &draft.to_source(),
+            &user.email,
+            db::now(),
+        )
+        .map_err(ServerFnError::new)?;
+        if post.meta.is_listed(chrono::Utc::now()) {
+            tokio::spawn(crate::backend::webmentions::send_new());
+        }
+        Ok(post.meta)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Publishes (`Published`) or unpublishes (`Draft`) a post as it currently
+/// is, leaving any autosave alone.
+#[server]
+pub async fn set_post_status(slug: String, status: PostStatus) -> Result<PostMeta, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, posts, session};
+
+        let user = session::require_editor()?;
+        let post = crate::models::blog::with_posts(|_, posts| {
+            posts.iter().find(|post| post.meta.slug == slug).cloned()
+        })?
+        .ok_or_else(|| ServerFnError::new(format!("Post not found: {}", slug)))?;
+        let draft = PostDraft::from_post(&post).with_status(status);
+        let meta = draft.parse().map_err(ServerFnError::new)?.meta;
+
+        let conn = connect()?;
+        keep_file_version(&conn, &slug)?;
+        let autosave = posts::autosave_for(&conn, &slug).map_err(ServerFnError::new)?;
+        posts::save(&conn, &slug, &draft.to_source(), &user.email, db::now())
+            .map_err(ServerFnError::new)?;
+        if let Some((json, saved_at)) = autosave {
+            posts::autosave(&conn, &slug, &json, saved_at).map_err(ServerFnError::new)?;
+        }
+        if meta.is_listed(chrono::Utc::now()) {
+            tokio::spawn(crate::backend::webmentions::send_new());
+        }
+        Ok(meta)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+#[server]
+pub async fn discard_autosave(slug: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{posts, session};
+
+        session::require_editor()?;
+        posts::clear_autosave(&connect()?, &slug).map_err(ServerFnError::new)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
@@ -0,0 +1,169 @@
+//! RSS 2.0 and Atom 1.0 feeds built from the blog post index.
+
+use crate::models::blog::Post;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Entries per feed.
+pub const FEED_LENGTH: usize = 20;
+
+/// Feed-level details. URLs are absolute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeedInfo {
+    pub title: String,
+    pub description: String,
+    pub author: String,
+    /// Base URL of the site, without a trailing slash.
+    pub site_url: String,
+    /// The page the feed mirrors, e.g. the blog or a tag archive.
+    pub page_url: String,
+    /// Where the feed itself is served.
+    pub feed_url: String,
+}
+
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Not allowed in XML 1.0 documents
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Posts are dated by day; feeds use midnight UTC.
+fn timestamp(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+/// The permanent URL of a post. Also used as its GUID/id, so it must not
+/// change once published. Entry links point at the canonical URL instead
+/// (see [`crate::models::blog::PostMeta::canonical`]), so aggregators that
+/// import the feed credit the original.
+pub fn post_url(site_url: &str, slug: &str) -> String {
+    format!("{}/blog/{}", site_url, slug)
+}
+
+/// Feed readers have no base URL for the content, so root-relative links
+/// and images are made absolute.
+fn absolute_links(html: &str, site_url: &str) -> String {
+    let mut html = html.to_string();
+    for attribute in ["href=\"/", "src=\"/"] {
+        let mut result = String::with_capacity(html.len());
+        let mut rest = html.as_str();
+        while let Some(i) = rest.find(attribute) {
+            // Split before the slash so the path stays in `rest`
+            let (before, after) = rest.split_at(i + attribute.len() - 1);
+            result.push_str(before);
+            if !after.starts_with("//") {
+                result.push_str(site_url);
+            }
+            result.push('/');
+            rest = &after[1..];
+        }
+        result.push_str(rest);
+        html = result;
+    }
+    html
+}
+
+fn entries(posts: &[Post]) -> impl Iterator<Item = &Post> {
+    posts.iter().take(FEED_LENGTH)
+}
+
+/// RSS 2.0 with full content in `content:encoded` and RFC 822 dates.
+pub fn rss(info: &FeedInfo, posts: &[Post]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\">\n<channel>\n");
+    xml.push_str(&format!(
+        "<title>{}</title>\n<link>{}</link>\n<description>{}</description>\n<language>en</language>\n",
+        escape_xml(&info.title),
+        escape_xml(&info.page_url),
+        escape_xml(&info.description)
+    ));
+    xml.push_str(&format!(
+        "<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_xml(&info.feed_url)
+    ));
+    if let Some(newest) = posts.first() {
+        xml.push_str(&format!(
+            "<lastBuildDate>{}</lastBuildDate>\n",
+            timestamp(newest.meta.date).to_rfc2822()
+        ));
+    }
+
+    for post in entries(posts) {
+        let url = escape_xml(&post_url(&info.site_url, &post.meta.slug));
+        xml.push_str("<item>\n");
+        xml.push_str(&format!(
+            "<title>{}</title>\n<link>{}</link>\n<guid isPermaLink=\"true\">{}</guid>\n<pubDate>{}</pubDate>\n<description>{}</description>\n",
+            escape_xml(&post.meta.title),
+            escape_xml(&post.meta.canonical(&info.site_url)),
+            url,
+            timestamp(post.meta.date).to_rfc2822(),
+            escape_xml(&post.meta.description)
+        ));
+        for tag in &post.meta.tags {
+            xml.push_str(&format!("<category>{}</category>\n", escape_xml(tag)));
+        }
+        xml.push_str(&format!(
+            "<content:encoded>{}</content:encoded>\n</item>\n",
+            escape_xml(&absolute_links(&post.html(), &info.site_url))
+        ));
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// Atom 1.0 with full HTML content and RFC 3339 dates.
+pub fn atom(info: &FeedInfo, posts: &[Post]) -> String {
+    let updated = posts
+        .first()
+        .map(|post| timestamp(post.meta.date))
+        .unwrap_or_else(|| timestamp(NaiveDate::default()));
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!(
+        "<title>{}</title>\n<subtitle>{}</subtitle>\n<id>{}</id>\n<link href=\"{}\"/>\n<link href=\"{}\" rel=\"self\" type=\"application/atom+xml\"/>\n<updated>{}</updated>\n<author><name>{}</name></author>\n",
+        escape_xml(&info.title),
+        escape_xml(&info.description),
+        escape_xml(&info.feed_url),
+        escape_xml(&info.page_url),
+        escape_xml(&info.feed_url),
+        updated.to_rfc3339(),
+        escape_xml(&info.author)
+    ));
+
+    for post in entries(posts) {
+        let url = escape_xml(&post_url(&info.site_url, &post.meta.slug));
+        let date = timestamp(post.meta.date).to_rfc3339();
+        xml.push_str("<entry>\n");
+        xml.push_str(&format!(
+            "<title>{}</title>\n<id>{}</id>\n<link href=\"{}\"/>\n<published>{}</published>\n<updated>{}</updated>\n<summary>{}</summary>\n",
+            escape_xml(&post.meta.title),
+            url,
+            escape_xml(&post.meta.canonical(&info.site_url)),
+            date,
+            date,
+            escape_xml(&post.meta.description)
+        ));
+        for tag in &post.meta.tags {
+            xml.push_str(&format!("<category term=\"{}\"/>\n", escape_xml(tag)));
+        }
+        xml.push_str(&format!(
+            "<content type=\"html\">{}</content>\n</entry>\n",
+            escape_xml(&absolute_links(&post.html(), &info.site_url))
+        ));
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
@@ -0,0 +1,209 @@
+//! Code blocks in blog posts: fence info parsing (`rust {3,5-7}`) and line
+//! rendering. Token colouring uses syntect on the server; other builds get
+//! the same markup without colours.
+
+use serde::{Deserialize, Serialize};
+
+/// What follows the opening fence: a language and the lines to emphasise.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FenceInfo {
+    pub language: Option<String>,
+    /// Inclusive, 1-based line ranges.
+    pub highlighted: Vec<(usize, usize)>,
+}
+
+impl FenceInfo {
+    /// Parses `rust`, `rust,ignore` or `rust {3,5-7}`. Malformed line ranges
+    /// are ignored.
+    pub fn parse(info: &str) -> Self {
+        let (language, rest) = match info.find('{') {
+            Some(i) => (&info[..i], &info[i + 1..]),
+            None => (info, ""),
+        };
+        let language = language
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .find(|s| !s.is_empty())
+            .map(str::to_lowercase);
+        let highlighted = rest
+            .split('}')
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|range| {
+                let range = range.trim();
+                let (start, end) = range.split_once('-').unwrap_or((range, range));
+                let (start, end) = (start.trim().parse().ok()?, end.trim().parse().ok()?);
+                (start >= 1 && start <= end).then_some((start, end))
+            })
+            .collect();
+
+        Self {
+            language,
+            highlighted,
+        }
+    }
+
+    pub fn is_highlighted(&self, line: usize) -> bool {
+        self.highlighted
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&line))
+    }
+}
+
+/// A rendered fenced or indented code block.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CodeBlock {
+    pub language: Option<String>,
+    /// The source, for copying to the clipboard.
+    pub code: String,
+    /// The `<pre>` element with one `.line` per source line.
+    pub html: String,
+}
+
+impl CodeBlock {
+    pub fn new(info: &str, code: String) -> Self {
+        let fence = FenceInfo::parse(info);
+        let lines: Vec<&str> = code.lines().collect();
+        let contents = highlight_lines(fence.language.as_deref(), &lines)
+            .unwrap_or_else(|| lines.iter().map(|line| escape_html(line)).collect());
+
+        let mut html = String::from("<pre class=\"code\"><code>");
+        for (i, content) in contents.iter().enumerate() {
+            let number = i + 1;
+            let class = if fence.is_highlighted(number) {
+                "line highlighted"
+            } else {
+                "line"
+            };
+            html.push_str(&format!(
+                "<span class=\"{}\"><span class=\"line-number\">{}</span><span class=\"line-content\">{}</span></span>",
+                class, number, content
+            ));
+        }
+        html.push_str("</code></pre>");
+
+        Self {
+            language: fence.language,
+            code,
+            html,
+        }
+    }
+
+    /// Standalone markup for places without the interactive component, such
+    /// as feeds.
+    pub fn to_html(&self) -> String {
+        let label = self
+            .language
+            .as_deref()
+            .map(|language| format!("<div class=\"code-header\">{}</div>", escape_html(language)))
+            .unwrap_or_default();
+        format!("<div class=\"code-block\">{}{}</div>", label, self.html)
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// One HTML fragment per line, or `None` when the language is unknown.
+#[cfg(feature = "server")]
+fn highlight_lines(language: Option<&str>, lines: &[&str]) -> Option<Vec<String>> {
+    use syntect::easy::HighlightLines;
+    use syntect::html::{styled_line_to_highlighted_html, IncludeBackground};
+
+    let (syntaxes, theme) = syntect_assets();
+    let syntax = syntaxes.find_syntax_by_token(language?)?;
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    lines
+        .iter()
+        .map(|line| {
+            // The bundled syntaxes expect lines with their newline
+            let line = format!("{}\n", line);
+            let regions = highlighter.highlight_line(&line, syntaxes).ok()?;
+            let html = styled_line_to_highlighted_html(&regions, IncludeBackground::No).ok()?;
+            Some(html.replace('\n', ""))
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "server"))]
+fn highlight_lines(_language: Option<&str>, _lines: &[&str]) -> Option<Vec<String>> {
+    None
+}
+
+/// The default syntaxes and a theme matching the site's dark palette.
+#[cfg(feature = "server")]
+fn syntect_assets() -> &'static (syntect::parsing::SyntaxSet, syntect::highlighting::Theme) {
+    use std::str::FromStr;
+    use syntect::highlighting::{
+        Color, FontStyle, ScopeSelectors, StyleModifier, Theme, ThemeItem, ThemeSettings,
+    };
+
+    static ASSETS: std::sync::OnceLock<(syntect::parsing::SyntaxSet, Theme)> =
+        std::sync::OnceLock::new();
+    ASSETS.get_or_init(|| {
+        let color = |hex: u32| Color {
+            r: (hex >> 16) as u8,
+            g: (hex >> 8) as u8,
+            b: hex as u8,
+            a: 0xff,
+        };
+        let rules: &[(&str, u32, Option<FontStyle>)] = &[
+            ("comment", 0x6b7280, Some(FontStyle::ITALIC)),
+            ("keyword, storage.modifier", 0x60a5fa, None),
+            (
+                "storage.type, entity.name.type, support.type",
+                0x67e8f9,
+                None,
+            ),
+            ("string, constant.character", 0x86efac, None),
+            ("constant.numeric, constant.language", 0xfbbf24, None),
+            ("entity.name.function, support.function", 0xc4b5fd, None),
+            (
+                "entity.name.tag, support.macro, entity.name.macro",
+                0x60a5fa,
+                None,
+            ),
+            (
+                "meta.attribute, entity.other.attribute-name",
+                0xf9a8d4,
+                None,
+            ),
+            ("variable.parameter", 0xfda4af, None),
+            ("punctuation, keyword.operator", 0x9ca3af, None),
+            ("invalid", 0xf87171, None),
+        ];
+        let theme = Theme {
+            name: Some("cv-dark".to_string()),
+            author: None,
+            settings: ThemeSettings {
+                foreground: Some(color(0xe5e7eb)),
+                background: Some(color(0x1a1d24)),
+                ..ThemeSettings::default()
+            },
+            scopes: rules
+                .iter()
+                .map(|&(scope, foreground, font_style)| ThemeItem {
+                    scope: ScopeSelectors::from_str(scope).expect("valid scope selector"),
+                    style: StyleModifier {
+                        foreground: Some(color(foreground)),
+                        background: None,
+                        font_style,
+                    },
+                })
+                .collect(),
+        };
+        (syntect::parsing::SyntaxSet::load_defaults_newlines(), theme)
+    })
+}
@@ -0,0 +1,78 @@
+//! Importing posts written elsewhere: a Medium export zip or a dev.to
+//! `articles.json` export (alone or zipped). Posts keep their dates, tags and
+//! original URL as `canonical_url`, and their images are copied into the
+//! media store.
+
+use crate::models::blog::PostMeta;
+use dioxus::fullstack::FileStream;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Largest export accepted, in bytes.
+pub const MAX_IMPORT_BYTES: usize = 50 * 1024 * 1024;
+pub const ACCEPTED_FILES: &str = ".zip,.json,application/zip,application/json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ImportReport {
+    pub imported: Vec<PostMeta>,
+    /// Posts left out, with the reason.
+    pub skipped: Vec<String>,
+    /// Problems with imported posts, such as images that stay remote.
+    pub warnings: Vec<String>,
+}
+
+/// Imports the posts of an export. Posts whose slug is already taken are
+/// left alone, so importing the same export twice adds nothing.
+#[server]
+pub async fn import_posts(mut file: FileStream) -> Result<ImportReport, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, import, media, session};
+        use futures::StreamExt;
+
+        let user = session::require_editor()?;
+        if file
+            .size()
+            .is_some_and(|size| size > MAX_IMPORT_BYTES as u64)
+        {
+            return Err(ServerFnError::new("The export is too large"));
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = file.next().await {
+            bytes.extend_from_slice(&chunk.map_err(|_| ServerFnError::new("Upload failed"))?);
+            if bytes.len() > MAX_IMPORT_BYTES {
+                return Err(ServerFnError::new("The export is too large"));
+            }
+        }
+
+        let today = chrono::Utc::now().date_naive();
+        let mut export = tokio::task::spawn_blocking(move || import::read_export(&bytes, today))
+            .await
+            .map_err(ServerFnError::new)?
+            .map_err(ServerFnError::new)?;
+        let existing: Vec<String> = crate::models::blog::with_posts(|_, posts| {
+            posts.iter().map(|post| post.meta.slug.clone()).collect()
+        })?;
+        import::assign_slugs(&mut export, &existing);
+
+        let client = crate::backend::webmentions::public_client_builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(ServerFnError::new)?;
+        let warnings =
+            import::localize_images(&client, &mut export.drafts, &media::media_dir(), true).await;
+
+        let conn = db::connect().map_err(ServerFnError::new)?;
+        let imported =
+            import::save(&conn, &mut export, &user.email, db::now()).map_err(ServerFnError::new)?;
+        Ok(ImportReport {
+            imported,
+            skipped: export.skipped,
+            warnings,
+        })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
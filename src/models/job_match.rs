@@ -0,0 +1,301 @@
+//! Scores the CV against a pasted job description.
+//!
+//! Extraction is a deterministic dictionary lookup: the text is tokenised and
+//! every run of up to three words is compared against the synonyms in
+//! `content/skills.toml`. No network access or models are involved.
+
+use crate::models::cv::{Cv, TailoredCv};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+const MAX_PHRASE_WORDS: usize = 3;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DictionarySkill {
+    pub id: String,
+    pub name: String,
+    pub synonyms: Vec<String>,
+    #[serde(default)]
+    pub related: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SkillDictionary {
+    skills: Vec<DictionarySkill>,
+    phrases: HashMap<String, usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchStatus {
+    Matched,
+    Partial,
+    Missing,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SkillMatch {
+    pub id: String,
+    pub name: String,
+    pub status: MatchStatus,
+    /// How often the posting mentions the skill.
+    pub mentions: usize,
+    /// For partial matches, the related CV skills that were found instead.
+    pub via: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VariantScore {
+    pub slug: String,
+    pub name: String,
+    pub score: u32,
+    /// Private variants are only reachable through share links.
+    pub public: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MatchReport {
+    /// 0 to 100, partial matches count half.
+    pub score: u32,
+    pub matched: Vec<SkillMatch>,
+    pub partial: Vec<SkillMatch>,
+    pub missing: Vec<SkillMatch>,
+    /// Variants ordered by score, best first.
+    pub variants: Vec<VariantScore>,
+}
+
+impl MatchReport {
+    pub fn suggested_variant(&self) -> Option<&VariantScore> {
+        self.variants.first()
+    }
+}
+
+/// Splits text into lowercase words, keeping characters that are part of
+/// skill names such as `c++`, `node.js` or `ci/cd`.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || "+#./-".contains(c)))
+        .map(|word| word.trim_matches(|c: char| ".,/-".contains(c)))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl SkillDictionary {
+    pub fn from_toml(source: &str) -> Result<Self, String> {
+        #[derive(Deserialize)]
+        struct File {
+            skills: Vec<DictionarySkill>,
+        }
+
+        let file: File =
+            toml::from_str(source).map_err(|e| format!("Invalid skill dictionary: {}", e))?;
+        let mut phrases = HashMap::new();
+        for (index, skill) in file.skills.iter().enumerate() {
+            for synonym in &skill.synonyms {
+                let phrase = tokenize(synonym).join(" ");
+                if let Some(existing) = phrases.insert(phrase.clone(), index) {
+                    if existing != index {
+                        return Err(format!(
+                            "Synonym '{}' is used by both '{}' and '{}'",
+                            phrase, file.skills[existing].id, skill.id
+                        ));
+                    }
+                }
+            }
+        }
+        for skill in &file.skills {
+            if let Some(id) = skill
+                .related
+                .iter()
+                .find(|id| !file.skills.iter().any(|s| &s.id == *id))
+            {
+                return Err(format!(
+                    "Skill '{}' is related to unknown skill '{}'",
+                    skill.id, id
+                ));
+            }
+        }
+
+        Ok(Self {
+            skills: file.skills,
+            phrases,
+        })
+    }
+
+    pub fn skill(&self, id: &str) -> Option<&DictionarySkill> {
+        self.skills.iter().find(|s| s.id == id)
+    }
+
+    /// Skill ids found in `text`, with the number of mentions. Longer phrases
+    /// win over the words they contain.
+    pub fn extract(&self, text: &str) -> BTreeMap<String, usize> {
+        let words = tokenize(text);
+        let mut found = BTreeMap::new();
+        let mut i = 0;
+        while i < words.len() {
+            let longest = (1..=MAX_PHRASE_WORDS.min(words.len() - i))
+                .rev()
+                .find_map(|n| {
+                    self.phrases
+                        .get(&words[i..i + n].join(" "))
+                        .map(|&index| (n, index))
+                });
+            match longest {
+                Some((n, index)) => {
+                    *found.entry(self.skills[index].id.clone()).or_insert(0) += 1;
+                    i += n;
+                }
+                None => i += 1,
+            }
+        }
+        found
+    }
+
+    /// Skills a CV demonstrates: its skill ids plus everything mentioned in
+    /// its text.
+    pub fn cv_skills(&self, cv: &TailoredCv) -> Vec<String> {
+        let mut text = vec![cv.basics.label.clone(), cv.basics.summary.clone()];
+        for e in &cv.experience {
+            text.push(e.position.clone());
+            text.push(e.summary.clone());
+            text.extend(e.highlights.iter().cloned());
+        }
+        for s in &cv.skills {
+            text.push(s.name.clone());
+            text.extend(s.keywords.iter().cloned());
+        }
+        for p in &cv.projects {
+            text.push(p.description.clone());
+            text.extend(p.highlights.iter().cloned());
+        }
+
+        let mut skills: Vec<String> = self.extract(&text.join("\n")).into_keys().collect();
+        for id in cv
+            .skills
+            .iter()
+            .map(|s| &s.id)
+            .chain(cv.experience.iter().flat_map(|e| &e.skills))
+            .chain(cv.projects.iter().flat_map(|p| &p.skills))
+        {
+            if self.skill(id).is_some() && !skills.contains(id) {
+                skills.push(id.clone());
+            }
+        }
+        skills
+    }
+
+    fn compare(&self, wanted: &BTreeMap<String, usize>, have: &[String]) -> (u32, Vec<SkillMatch>) {
+        let mut results: Vec<SkillMatch> = wanted
+            .iter()
+            .filter_map(|(id, &mentions)| {
+                let skill = self.skill(id)?;
+                let via: Vec<String> = skill
+                    .related
+                    .iter()
+                    .filter(|related| have.contains(related))
+                    .filter_map(|related| self.skill(related).map(|s| s.name.clone()))
+                    .collect();
+                let status = if have.contains(id) {
+                    MatchStatus::Matched
+                } else if !via.is_empty() {
+                    MatchStatus::Partial
+                } else {
+                    MatchStatus::Missing
+                };
+                Some(SkillMatch {
+                    id: id.clone(),
+                    name: skill.name.clone(),
+                    status,
+                    mentions,
+                    via: if status == MatchStatus::Partial {
+                        via
+                    } else {
+                        Vec::new()
+                    },
+                })
+            })
+            .collect();
+        results.sort_by(|a, b| b.mentions.cmp(&a.mentions).then(a.name.cmp(&b.name)));
+
+        let points: usize = results
+            .iter()
+            .map(|r| match r.status {
+                MatchStatus::Matched => 2,
+                MatchStatus::Partial => 1,
+                MatchStatus::Missing => 0,
+            })
+            .sum();
+        let score = if results.is_empty() {
+            0
+        } else {
+            ((points * 100) as f64 / (results.len() * 2) as f64).round() as u32
+        };
+        (score, results)
+    }
+
+    /// Compares a job description with the full CV and with every variant.
+    pub fn report(&self, job_description: &str, cv: &Cv) -> MatchReport {
+        let wanted = self.extract(job_description);
+        let (score, results) = self.compare(&wanted, &self.cv_skills(&cv.full()));
+
+        let mut variants: Vec<VariantScore> = cv
+            .variants
+            .iter()
+            .filter_map(|variant| {
+                let tailored = cv.tailor(&variant.slug)?;
+                let (score, _) = self.compare(&wanted, &self.cv_skills(&tailored));
+                Some(VariantScore {
+                    slug: variant.slug.clone(),
+                    name: variant.name.clone(),
+                    score,
+                    public: variant.public,
+                })
+            })
+            .collect();
+        // Stable sort keeps file order between equal scores
+        variants.sort_by_key(|v| std::cmp::Reverse(v.score));
+
+        let by_status = |status| {
+            results
+                .iter()
+                .filter(|r| r.status == status)
+                .cloned()
+                .collect()
+        };
+        MatchReport {
+            score,
+            matched: by_status(MatchStatus::Matched),
+            partial: by_status(MatchStatus::Partial),
+            missing: by_status(MatchStatus::Missing),
+            variants: if wanted.is_empty() {
+                Vec::new()
+            } else {
+                variants
+            },
+        }
+    }
+}
+
+/// The dictionary bundled with the server binary.
+#[cfg(feature = "server")]
+pub fn dictionary() -> &'static SkillDictionary {
+    static DICTIONARY: std::sync::OnceLock<SkillDictionary> = std::sync::OnceLock::new();
+    DICTIONARY.get_or_init(|| {
+        SkillDictionary::from_toml(include_str!("../../content/skills.toml"))
+            .expect("content/skills.toml is invalid")
+    })
+}
+
+#[server]
+pub async fn match_job_description(job_description: String) -> Result<MatchReport, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        crate::backend::session::require_owner()?;
+        Ok(dictionary().report(&job_description, crate::models::cv::master()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
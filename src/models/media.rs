@@ -0,0 +1,213 @@
+//! Images uploaded from the post editor. An upload is stored once under the
+//! hash of its content, next to resized WebP and AVIF variants. The file
+//! name records the hash and size of the image, so the Markdown renderer can
+//! build `srcset` from the URL alone.
+
+use crate::models::highlight::escape_html;
+use dioxus::fullstack::FileStream;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const MEDIA_PREFIX: &str = "/media/";
+/// Largest upload accepted, in bytes.
+pub const MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+/// Largest width or height accepted, in pixels.
+pub const MAX_DIMENSION: u32 = 12_000;
+pub const ALLOWED_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+/// Widths of the resized variants. Images are never scaled up, and the
+/// largest variant is at most the last width.
+pub const VARIANT_WIDTHS: [u32; 4] = [480, 960, 1440, 1920];
+/// Posts are at most `max-w-3xl` minus padding wide.
+const SIZES: &str = "(min-width: 48rem) 704px, 100vw";
+const HASH_LEN: usize = 16;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VariantFormat {
+    Avif,
+    Webp,
+}
+
+impl VariantFormat {
+    /// In order of preference for `<source>` elements.
+    pub const ALL: [VariantFormat; 2] = [VariantFormat::Avif, VariantFormat::Webp];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            VariantFormat::Avif => "avif",
+            VariantFormat::Webp => "webp",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            VariantFormat::Avif => "image/avif",
+            VariantFormat::Webp => "image/webp",
+        }
+    }
+}
+
+/// An uploaded image: `/media/<hash>-<width>x<height>.<jpg|png>`, with
+/// variants at `/media/<hash>-<w>.<avif|webp>` for each of [`Self::widths`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MediaImage {
+    /// Start of the SHA-256 of the uploaded bytes, hex encoded.
+    pub hash: String,
+    pub width: u32,
+    pub height: u32,
+    /// `jpg` for JPEG uploads, `png` for everything else.
+    pub extension: String,
+}
+
+impl MediaImage {
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}-{}x{}.{}",
+            self.hash, self.width, self.height, self.extension
+        )
+    }
+
+    pub fn url(&self) -> String {
+        format!("{}{}", MEDIA_PREFIX, self.file_name())
+    }
+
+    /// Parses a URL made by [`Self::url`]; anything else is not ours.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let name = url.strip_prefix(MEDIA_PREFIX)?;
+        let (stem, extension) = name.rsplit_once('.')?;
+        if !matches!(extension, "jpg" | "png") {
+            return None;
+        }
+        let (hash, size) = stem.split_once('-')?;
+        let (width, height) = size.split_once('x')?;
+        let image = MediaImage {
+            hash: hash.to_string(),
+            width: parse_dimension(width)?,
+            height: parse_dimension(height)?,
+            extension: extension.to_string(),
+        };
+        is_hash(hash).then_some(image)
+    }
+
+    /// Widths of the resized variants, smallest first.
+    pub fn widths(&self) -> Vec<u32> {
+        let largest = self.width.min(VARIANT_WIDTHS[VARIANT_WIDTHS.len() - 1]);
+        let mut widths: Vec<u32> = VARIANT_WIDTHS
+            .into_iter()
+            .filter(|w| *w < largest)
+            .collect();
+        widths.push(largest);
+        widths
+    }
+
+    pub fn variant_name(&self, width: u32, format: VariantFormat) -> String {
+        format!("{}-{}.{}", self.hash, width, format.extension())
+    }
+
+    pub fn srcset(&self, format: VariantFormat) -> String {
+        self.widths()
+            .into_iter()
+            .map(|w| format!("{}{} {}w", MEDIA_PREFIX, self.variant_name(w, format), w))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// A `<picture>` offering the variants, falling back to the original.
+    pub fn to_html(&self, alt: &str, title: &str) -> String {
+        let mut html = String::from("<picture>");
+        for format in VariantFormat::ALL {
+            html.push_str(&format!(
+                "<source type=\"{}\" srcset=\"{}\" sizes=\"{}\">",
+                format.mime(),
+                self.srcset(format),
+                SIZES
+            ));
+        }
+        html.push_str(&format!(
+            "<img src=\"{}\" alt=\"{}\" width=\"{}\" height=\"{}\" loading=\"lazy\" decoding=\"async\"",
+            self.url(),
+            escape_html(alt),
+            self.width,
+            self.height
+        ));
+        if !title.is_empty() {
+            html.push_str(&format!(" title=\"{}\"", escape_html(title)));
+        }
+        html.push_str("></picture>");
+        html
+    }
+
+    pub fn markdown(&self, alt: &str) -> String {
+        format!("![{}]({})", alt.replace(['[', ']'], ""), self.url())
+    }
+}
+
+fn parse_dimension(value: &str) -> Option<u32> {
+    if value.starts_with('0') || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok().filter(|n| *n <= MAX_DIMENSION)
+}
+
+fn is_hash(value: &str) -> bool {
+    value.len() == HASH_LEN
+        && value
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Whether `name` is an original or a variant file name, the only files the
+/// media route serves.
+pub fn is_media_file(name: &str) -> bool {
+    if MediaImage::from_url(&format!("{}{}", MEDIA_PREFIX, name)).is_some() {
+        return true;
+    }
+    let Some((stem, extension)) = name.rsplit_once('.') else {
+        return false;
+    };
+    let Some((hash, width)) = stem.split_once('-') else {
+        return false;
+    };
+    is_hash(hash)
+        && parse_dimension(width).is_some()
+        && VariantFormat::ALL
+            .iter()
+            .any(|format| format.extension() == extension)
+}
+
+/// Stores an image for use in posts. Only editors can upload, and the
+/// size and type limits are checked here whatever the browser claims.
+#[server]
+pub async fn upload_image(mut file: FileStream) -> Result<MediaImage, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{media, session};
+        use futures::StreamExt;
+
+        session::require_editor()?;
+        let content_type = file.content_type().unwrap_or_default().to_string();
+        if !ALLOWED_TYPES.contains(&content_type.as_str()) {
+            return Err(ServerFnError::new(media::MediaError::UnsupportedType));
+        }
+        if file
+            .size()
+            .is_some_and(|size| size > MAX_UPLOAD_BYTES as u64)
+        {
+            return Err(ServerFnError::new(media::MediaError::TooLarge));
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = file.next().await {
+            bytes.extend_from_slice(&chunk.map_err(|_| ServerFnError::new("Upload failed"))?);
+            if bytes.len() > MAX_UPLOAD_BYTES {
+                return Err(ServerFnError::new(media::MediaError::TooLarge));
+            }
+        }
+        tokio::task::spawn_blocking(move || media::store(&media::media_dir(), &bytes))
+            .await
+            .map_err(ServerFnError::new)?
+            .map_err(ServerFnError::new)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
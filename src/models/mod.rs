@@ -1,3 +1,28 @@
+pub mod archive;
+pub mod blog;
+pub mod comments;
+pub mod contact;
+pub mod cv;
+pub mod editor;
+pub mod feed;
+pub mod highlight;
+pub mod import;
+pub mod job_match;
+pub mod media;
+pub mod reactions;
+pub mod related;
+pub mod revisions;
+pub mod search;
+pub mod seo;
+pub mod series;
+pub mod share;
+pub mod site;
+pub mod sitemap;
+pub mod structured_data;
+pub mod tags;
+pub mod toc;
+pub mod webmentions;
+
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -14,18 +39,19 @@ pub async fn get_google_auth_url() -> Result<String, ServerFnError> {
         use oauth2::{AuthUrl, ClientId, ClientSecret, RedirectUrl, Scope};
 
         let client_id = std::env::var("GOOGLE_CLIENT_ID").ok();
-        let app_env = std::env::var("APP_ENV").unwrap_or_default().to_lowercase();
 
         // Mock and Dev strategy
         let is_placeholder = client_id
             .as_deref()
             .map(|id| id.contains("your_"))
             .unwrap_or(true);
-        let is_dev = app_env == "development" || app_env == "dev";
 
-        if is_placeholder || is_dev {
+        if crate::backend::session::is_dev() {
             return Ok("/auth/callback?code=mock_code_for_dev".to_string());
         }
+        if is_placeholder {
+            return Err(ServerFnError::new("Google login is not configured"));
+        }
 
         let client_id = client_id.unwrap();
         let client_secret = std::env::var("GOOGLE_CLIENT_SECRET")
@@ -66,14 +92,19 @@ pub async fn exchange_code_for_user(code: String) -> Result<AuthUser, ServerFnEr
             TokenUrl,
         };
 
-        // Dev/Mock strategy
-        let app_env = std::env::var("APP_ENV").unwrap_or_default().to_lowercase();
-        let is_dev = app_env == "development" || app_env == "dev";
-
-        if code == "mock_code_for_dev" || is_dev {
-            return Ok(AuthUser {
-                email: "dev.user@example.com".to_string(),
-            });
+        // Dev/Mock strategy: the mock login is a shared identity, so it is
+        // only honoured (and only gets a session) in development
+        if crate::backend::session::is_dev() {
+            let user = AuthUser {
+                email: crate::backend::session::DEV_USER_EMAIL.to_string(),
+            };
+            crate::backend::session::start(&user);
+            return Ok(user);
+        }
+        if code == "mock_code_for_dev" {
+            return Err(ServerFnError::new(
+                "Mock login is only available in development",
+            ));
         }
 
         let client_id =
@@ -116,7 +147,23 @@ pub async fn exchange_code_for_user(code: String) -> Result<AuthUser, ServerFnEr
             .ok_or_else(|| ServerFnError::new("Email not found in user info"))?
             .to_string();
 
-        Ok(AuthUser { email })
+        let user = AuthUser { email };
+        crate::backend::session::start(&user);
+        Ok(user)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Ends the server-side session started by `exchange_code_for_user`.
+#[server]
+pub async fn logout() -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        crate::backend::session::end();
+        Ok(())
     }
     #[cfg(not(feature = "server"))]
     {
@@ -0,0 +1,218 @@
+//! Emoji reactions and view counts on posts (see `backend::reactions`).
+//!
+//! Readers are told apart by their account when logged in. Anonymous views
+//! are keyed by a daily hash of the address and user agent, and anonymous
+//! reactions by a signed reader cookie. A reader reacts at most once per
+//! post, and a view counts once per reader per post and day.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum Reaction {
+    ThumbsUp,
+    Rocket,
+    Crab,
+    Heart,
+    Party,
+    Eyes,
+}
+
+impl Reaction {
+    pub const ALL: [Reaction; 6] = [
+        Reaction::ThumbsUp,
+        Reaction::Rocket,
+        Reaction::Crab,
+        Reaction::Heart,
+        Reaction::Party,
+        Reaction::Eyes,
+    ];
+
+    /// The spelling stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Reaction::ThumbsUp => "thumbs-up",
+            Reaction::Rocket => "rocket",
+            Reaction::Crab => "crab",
+            Reaction::Heart => "heart",
+            Reaction::Party => "party",
+            Reaction::Eyes => "eyes",
+        }
+    }
+
+    pub fn parse(reaction: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.as_str() == reaction)
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Reaction::ThumbsUp => "👍",
+            Reaction::Rocket => "🚀",
+            Reaction::Crab => "🦀",
+            Reaction::Heart => "❤️",
+            Reaction::Party => "🎉",
+            Reaction::Eyes => "👀",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Reaction::ThumbsUp => "Thumbs up",
+            Reaction::Rocket => "Rocket",
+            Reaction::Crab => "Crab",
+            Reaction::Heart => "Heart",
+            Reaction::Party => "Party",
+            Reaction::Eyes => "Eyes",
+        }
+    }
+}
+
+/// Views and reactions of one post.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct PostStats {
+    pub slug: String,
+    pub views: u64,
+    /// Reactions given at least once, in [`Reaction::ALL`] order.
+    pub reactions: Vec<(Reaction, u64)>,
+    /// The current reader's reaction. Only filled in for a single post.
+    pub mine: Option<Reaction>,
+}
+
+impl PostStats {
+    pub fn count(&self, reaction: Reaction) -> u64 {
+        self.reactions
+            .iter()
+            .find(|(r, _)| *r == reaction)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// "🚀 3 🦀 1".
+    pub fn reactions_summary(&self) -> String {
+        self.reactions
+            .iter()
+            .map(|(reaction, count)| format!("{} {}", reaction.emoji(), count))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Most posts [`get_post_stats`] answers for in one call.
+pub const MAX_STATS_BATCH: usize = 100;
+/// Views and reaction changes one client address can make per
+/// [`RATE_WINDOW_SECS`].
+pub const VIEW_RATE_LIMIT: usize = 60;
+pub const REACTION_RATE_LIMIT: usize = 30;
+pub const RATE_WINDOW_SECS: i64 = 10 * 60;
+
+/// Counts for several posts at once, in the order of `slugs`, for post
+/// lists.
+#[server]
+pub async fn get_post_stats(slugs: Vec<String>) -> Result<Vec<PostStats>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, reactions};
+
+        if slugs.len() > MAX_STATS_BATCH {
+            return Err(ServerFnError::new(format!(
+                "At most {} posts at a time",
+                MAX_STATS_BATCH
+            )));
+        }
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        slugs
+            .iter()
+            .map(|slug| reactions::stats(&conn, slug))
+            .collect::<Result<_, _>>()
+            .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Counts a view of a post by the current reader and returns its counts.
+#[server]
+pub async fn record_view(slug: String) -> Result<PostStats, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, reactions, session, throttle::Throttle};
+
+        static THROTTLE: std::sync::OnceLock<Throttle> = std::sync::OnceLock::new();
+        check_rate(THROTTLE.get_or_init(|| Throttle::new(VIEW_RATE_LIMIT, RATE_WINDOW_SECS)))?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        require_reachable(&slug)?;
+        let viewer = reactions::reader_key(&session::viewer_id());
+        reactions::record_view(&conn, &slug, &viewer, db::now())
+            .and_then(|_| match session::reader_id() {
+                Some(reader) => reactions::stats_for(&conn, &slug, &reactions::reader_key(&reader)),
+                None => reactions::stats(&conn, &slug),
+            })
+            .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Sets the current reader's reaction to a post, replacing any earlier one,
+/// or removes it. Anonymous readers get a reader cookie on their first
+/// reaction.
+#[server]
+pub async fn set_reaction(
+    slug: String,
+    reaction: Option<Reaction>,
+) -> Result<PostStats, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, reactions, session, throttle::Throttle};
+
+        static THROTTLE: std::sync::OnceLock<Throttle> = std::sync::OnceLock::new();
+        check_rate(THROTTLE.get_or_init(|| Throttle::new(REACTION_RATE_LIMIT, RATE_WINDOW_SECS)))?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        require_reachable(&slug)?;
+        let reader = reactions::reader_key(&session::start_reader());
+        reactions::set_reaction(&conn, &slug, &reader, reaction, db::now())
+            .and_then(|_| reactions::stats_for(&conn, &slug, &reader))
+            .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Counts a hit of the current client against `throttle`, failing when it
+/// is over the limit.
+#[cfg(feature = "server")]
+fn check_rate(throttle: &crate::backend::throttle::Throttle) -> Result<(), ServerFnError> {
+    use crate::backend::{db, session};
+
+    let ip = session::client_ip()
+        .ok_or_else(|| ServerFnError::new("Could not tell the client address"))?;
+    if throttle.allow(&ip.to_string(), db::now()) {
+        Ok(())
+    } else {
+        Err(ServerFnError::new("Too many requests, try again later"))
+    }
+}
+
+/// Fails unless the current reader can open `/blog/<slug>`.
+#[cfg(feature = "server")]
+fn require_reachable(slug: &str) -> Result<(), ServerFnError> {
+    use crate::backend::session;
+
+    let editor = session::current_user().is_some_and(|user| session::is_editor(&user.email));
+    let now = chrono::Utc::now();
+    if crate::models::blog::with_posts(|_, posts| {
+        posts
+            .iter()
+            .any(|post| post.meta.slug == slug && post.meta.is_reachable(now, editor))
+    })? {
+        Ok(())
+    } else {
+        Err(ServerFnError::new(format!("Post not found: {}", slug)))
+    }
+}
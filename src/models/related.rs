@@ -0,0 +1,178 @@
+//! "Related posts": other posts ranked by shared tags and by how similar
+//! their text is (cosine similarity of tf-idf term vectors).
+
+#[cfg(feature = "server")]
+use crate::models::blog::with_posts;
+use crate::models::blog::{plain_text, Post, PostMeta};
+use crate::models::series::in_series;
+use crate::models::tags::tag_slug;
+use dioxus::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How many related posts a post shows.
+pub const RELATED_LIMIT: usize = 3;
+/// Share of the score from tag overlap; the rest comes from the text.
+const TAG_WEIGHT: f64 = 0.5;
+/// Posts scoring less are not worth suggesting.
+const MIN_SCORE: f64 = 0.05;
+
+/// Words too common to say anything about a post.
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "all", "also", "and", "any", "are", "because", "been", "but", "can", "could",
+    "did", "does", "for", "from", "had", "has", "have", "how", "into", "its", "just", "like",
+    "more", "most", "not", "now", "one", "only", "other", "our", "out", "over", "same", "should",
+    "some", "than", "that", "the", "their", "them", "then", "there", "these", "they", "this",
+    "too", "use", "used", "using", "very", "was", "way", "were", "what", "when", "which", "while",
+    "who", "why", "will", "with", "would", "you", "your",
+];
+
+/// Lowercase words of three or more letters, without stop words.
+pub fn terms(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3 && !STOP_WORDS.contains(word))
+        .map(str::to_string)
+        .collect()
+}
+
+fn term_counts(post: &Post) -> BTreeMap<String, usize> {
+    let text = format!(
+        "{}\n{}\n{}",
+        post.meta.title,
+        post.meta.description,
+        plain_text(&post.markdown)
+    );
+    let mut counts = BTreeMap::new();
+    for term in terms(&text) {
+        *counts.entry(term).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Unit-length tf-idf vectors, one per document. Term frequency is
+/// dampened (`1 + ln tf`) so one repeated word cannot dominate a post.
+fn tf_idf(documents: &[BTreeMap<String, usize>]) -> Vec<BTreeMap<&str, f64>> {
+    let mut document_frequency: BTreeMap<&str, usize> = BTreeMap::new();
+    for counts in documents {
+        for term in counts.keys() {
+            *document_frequency.entry(term).or_insert(0) += 1;
+        }
+    }
+    let total = documents.len() as f64;
+    documents
+        .iter()
+        .map(|counts| {
+            let mut vector: BTreeMap<&str, f64> = counts
+                .iter()
+                .map(|(term, &count)| {
+                    let idf = (1.0 + total / document_frequency[term.as_str()] as f64).ln();
+                    (term.as_str(), (1.0 + (count as f64).ln()) * idf)
+                })
+                .collect();
+            let norm = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+            if norm > 0.0 {
+                vector.values_mut().for_each(|w| *w /= norm);
+            }
+            vector
+        })
+        .collect()
+}
+
+fn cosine(a: &BTreeMap<&str, f64>, b: &BTreeMap<&str, f64>) -> f64 {
+    a.iter()
+        .filter_map(|(term, w)| b.get(term).map(|v| w * v))
+        .sum()
+}
+
+/// Shared tags over all tags of the two posts.
+fn tag_overlap(a: &PostMeta, b: &PostMeta) -> f64 {
+    let a: BTreeSet<String> = a.tags.iter().map(|tag| tag_slug(tag)).collect();
+    let b: BTreeSet<String> = b.tags.iter().map(|tag| tag_slug(tag)).collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        0.0
+    } else {
+        a.intersection(&b).count() as f64 / union as f64
+    }
+}
+
+/// Up to `limit` posts from `candidates` most like `post`, best first.
+/// Posts from the same series are left out, as the series box lists them.
+pub fn related_posts(post: &Post, candidates: &[Post], limit: usize) -> Vec<PostMeta> {
+    let others: Vec<&Post> = candidates
+        .iter()
+        .filter(|other| other.meta.slug != post.meta.slug)
+        .filter(|other| {
+            post.meta
+                .series
+                .as_deref()
+                .is_none_or(|series| !in_series(&other.meta, series))
+        })
+        .collect();
+    let documents: Vec<_> = std::iter::once(post)
+        .chain(others.iter().copied())
+        .map(term_counts)
+        .collect();
+    let vectors = tf_idf(&documents);
+
+    let mut scored: Vec<(f64, &Post)> = others
+        .iter()
+        .zip(&vectors[1..])
+        .map(|(other, vector)| {
+            let score = TAG_WEIGHT * tag_overlap(&post.meta, &other.meta)
+                + (1.0 - TAG_WEIGHT) * cosine(&vectors[0], vector);
+            (score, *other)
+        })
+        .filter(|(score, _)| *score >= MIN_SCORE)
+        .collect();
+    // Stable, so equally related posts keep their (newest first) order
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, other)| other.meta.clone())
+        .collect()
+}
+
+#[server]
+pub async fn get_related_posts(slug: String) -> Result<Vec<PostMeta>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        type Cached = (u64, Vec<String>, Vec<PostMeta>);
+        static CACHE: std::sync::Mutex<BTreeMap<String, Cached>> =
+            std::sync::Mutex::new(BTreeMap::new());
+
+        let now = chrono::Utc::now();
+        with_posts(|version, posts| {
+            // Unlisted posts get suggestions too, but are never suggested
+            let Some(post) = posts.iter().find(|post| post.meta.slug == slug) else {
+                return Vec::new();
+            };
+            // Scheduled posts going live change the candidates without a reload
+            let candidates: Vec<String> = posts
+                .iter()
+                .filter(|post| post.meta.is_listed(now))
+                .map(|post| post.meta.slug.clone())
+                .collect();
+            let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some((cached_version, cached_candidates, related)) = cache.get(&slug) {
+                if *cached_version == version && *cached_candidates == candidates {
+                    return related.clone();
+                }
+            }
+            let listed: Vec<Post> = posts
+                .iter()
+                .filter(|post| post.meta.is_listed(now))
+                .cloned()
+                .collect();
+            let related = related_posts(post, &listed, RELATED_LIMIT);
+            // Only known slugs get here, so the cache cannot grow without bound
+            cache.insert(slug, (version, candidates, related.clone()));
+            related
+        })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
@@ -0,0 +1,196 @@
+//! Revision history of posts saved through the editor: every save is kept,
+//! so an overwrite can be inspected with a line diff and rolled back.
+
+use crate::models::blog::PostMeta;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Author recorded for the version of a post that came from
+/// `content/posts` before it was first saved in the editor.
+pub const FILE_AUTHOR: &str = "content/posts";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RevisionSummary {
+    pub id: i64,
+    pub slug: String,
+    /// Email of the editor who saved it, or [`FILE_AUTHOR`].
+    pub author: String,
+    pub created_at: i64,
+    /// SHA-256 of the source, hex encoded.
+    pub hash: String,
+}
+
+impl RevisionSummary {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(8)]
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+    /// 1-based line number in the old text, for `Same` and `Removed`.
+    pub old_line: Option<usize>,
+    /// 1-based line number in the new text, for `Same` and `Added`.
+    pub new_line: Option<usize>,
+}
+
+/// Largest LCS table [`diff_lines`] builds, in cells (about 8 MB). Changed
+/// stretches bigger than this are shown as one block replacement instead.
+pub const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// Line diff of `old` against `new` from their longest common subsequence.
+/// Removals come before additions within a changed stretch.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // The common prefix and suffix are unchanged and need no table
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j]: length of the LCS of a[i..] and b[j..]. Past the size cap
+    // there is no table and the stretch is replaced as a whole
+    let fits = (a.len() + 1).saturating_mul(b.len() + 1) <= MAX_DIFF_CELLS;
+    let mut lcs = vec![vec![0usize; if fits { b.len() + 1 } else { 0 }]; a.len() + 1];
+    if fits {
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i][j] = if a[i] == b[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let same = |lines: &mut Vec<DiffLine>, text: &str, i: usize, j: usize| {
+        lines.push(DiffLine {
+            kind: DiffKind::Same,
+            text: text.to_string(),
+            old_line: Some(i + 1),
+            new_line: Some(j + 1),
+        })
+    };
+    for (i, text) in old[..prefix].iter().enumerate() {
+        same(&mut lines, text, i, i);
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if fits && i < a.len() && j < b.len() && a[i] == b[j] {
+            same(&mut lines, a[i], prefix + i, prefix + j);
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || !fits || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(DiffLine {
+                kind: DiffKind::Removed,
+                text: a[i].to_string(),
+                old_line: Some(prefix + i + 1),
+                new_line: None,
+            });
+            i += 1;
+        } else {
+            lines.push(DiffLine {
+                kind: DiffKind::Added,
+                text: b[j].to_string(),
+                old_line: None,
+                new_line: Some(prefix + j + 1),
+            });
+            j += 1;
+        }
+    }
+    let (old_start, new_start) = (old.len() - suffix, new.len() - suffix);
+    for (k, text) in old[old_start..].iter().enumerate() {
+        same(&mut lines, text, old_start + k, new_start + k);
+    }
+    lines
+}
+
+/// Newest first.
+#[server]
+pub async fn list_revisions(slug: String) -> Result<Vec<RevisionSummary>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, posts, session};
+
+        session::require_editor()?;
+        let conn = db::connect().map_err(ServerFnError::new)?;
+        posts::revisions(&conn, &slug).map_err(ServerFnError::new)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// The changes from revision `from` to revision `to` of `slug`.
+#[server]
+pub async fn diff_revisions(
+    slug: String,
+    from: i64,
+    to: i64,
+) -> Result<Vec<DiffLine>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, posts, session};
+
+        session::require_editor()?;
+        let conn = db::connect().map_err(ServerFnError::new)?;
+        let source = |id| {
+            posts::revision_source(&conn, &slug, id)
+                .map_err(ServerFnError::new)?
+                .ok_or_else(|| ServerFnError::new(format!("No revision {} of {}", id, slug)))
+        };
+        Ok(diff_lines(&source(from)?, &source(to)?))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Saves an old revision as the current version. The restore is itself a
+/// new revision, so it can be undone the same way. Restoring a listed
+/// version sends its webmentions like a save from the editor.
+#[server]
+pub async fn restore_revision(slug: String, id: i64) -> Result<PostMeta, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, posts, session};
+
+        let user = session::require_editor()?;
+        let conn = db::connect().map_err(ServerFnError::new)?;
+        let source = posts::revision_source(&conn, &slug, id)
+            .map_err(ServerFnError::new)?
+            .ok_or_else(|| ServerFnError::new(format!("No revision {} of {}", id, slug)))?;
+        let meta = crate::models::blog::parse_post(&slug, &source)
+            .map_err(ServerFnError::new)?
+            .meta;
+        posts::save(&conn, &slug, &source, &user.email, db::now()).map_err(ServerFnError::new)?;
+        if meta.is_listed(chrono::Utc::now()) {
+            tokio::spawn(crate::backend::webmentions::send_new());
+        }
+        Ok(meta)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
@@ -0,0 +1,153 @@
+//! Site search over blog posts, projects and experience entries. The index
+//! itself is an SQLite FTS5 table on the server (`backend::search`); this
+//! module builds its documents and the query.
+
+use crate::models::blog::{plain_text, Post};
+use crate::models::cv::TailoredCv;
+use crate::Route;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Longest query accepted, in words.
+pub const MAX_QUERY_TERMS: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchKind {
+    Post,
+    Project,
+    Experience,
+}
+
+impl SearchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchKind::Post => "post",
+            SearchKind::Project => "project",
+            SearchKind::Experience => "experience",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "post" => Some(SearchKind::Post),
+            "project" => Some(SearchKind::Project),
+            "experience" => Some(SearchKind::Experience),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchKind::Post => "Post",
+            SearchKind::Project => "Project",
+            SearchKind::Experience => "Experience",
+        }
+    }
+}
+
+/// One indexed item.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchDocument {
+    pub kind: SearchKind,
+    /// Where the result links to.
+    pub path: String,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    pub path: String,
+    /// Escaped HTML with matches wrapped in `<mark>`.
+    pub title_html: String,
+    /// Escaped HTML with matches wrapped in `<mark>`.
+    pub snippet_html: String,
+}
+
+/// Public posts and the public CV, as search documents.
+pub fn documents(posts: &[Post], cv: &TailoredCv) -> Vec<SearchDocument> {
+    let mut documents: Vec<SearchDocument> = posts
+        .iter()
+        .map(|post| SearchDocument {
+            kind: SearchKind::Post,
+            path: Route::BlogPost {
+                slug: post.meta.slug.clone(),
+            }
+            .to_string(),
+            title: post.meta.title.clone(),
+            body: format!(
+                "{}\n{}\n{}",
+                post.meta.description,
+                post.meta.tags.join(" "),
+                plain_text(&post.markdown)
+            ),
+        })
+        .collect();
+
+    documents.extend(cv.projects.iter().map(|project| SearchDocument {
+        kind: SearchKind::Project,
+        path: "/#projects".to_string(),
+        title: project.name.clone(),
+        body: format!("{}\n{}", project.description, project.highlights.join("\n")),
+    }));
+    documents.extend(cv.experience.iter().map(|experience| SearchDocument {
+        kind: SearchKind::Experience,
+        path: "/#experience".to_string(),
+        title: format!("{} — {}", experience.position, experience.organization),
+        body: format!(
+            "{}\n{}",
+            experience.summary,
+            experience.highlights.join("\n")
+        ),
+    }));
+    documents
+}
+
+/// Turns user input into an FTS5 query: every word must match, the last
+/// one as a prefix so results show up while typing. Words are quoted, so
+/// FTS5 operators in the input are searched for literally.
+pub fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .take(MAX_QUERY_TERMS)
+        .map(|term| format!("\"{}\"", term.to_lowercase()))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+/// Markers used for matches in FTS5 `snippet()`/`highlight()` output, which
+/// is plain text. They are swapped for `<mark>` after escaping.
+pub const MATCH_START: char = '\u{2}';
+pub const MATCH_END: char = '\u{3}';
+
+pub fn marked_html(text: &str) -> String {
+    crate::models::highlight::escape_html(text)
+        .replace(MATCH_START, "<mark>")
+        .replace(MATCH_END, "</mark>")
+}
+
+#[server]
+pub async fn search(q: String) -> Result<Vec<SearchResult>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let Some(query) = fts_query(&q) else {
+            return Ok(Vec::new());
+        };
+        let posts = crate::models::blog::listed_posts()?;
+        let cv = crate::models::cv::master().full().without_private();
+
+        let conn = crate::backend::db::connect().map_err(ServerFnError::new)?;
+        crate::backend::search::ensure_index(&conn, &documents(&posts, &cv))
+            .map_err(ServerFnError::new)?;
+        crate::backend::search::search(&conn, &query, 20).map_err(ServerFnError::new)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
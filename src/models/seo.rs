@@ -0,0 +1,220 @@
+//! Per-page head metadata: the title, description and canonical URL, plus
+//! the Open Graph and Twitter card tags link previews are built from. Pages
+//! set what they know through the `Seo` component; the rest comes from the
+//! CV. Preview images are generated by the server under `/og/`.
+
+use crate::models::cv::Basics;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+pub const OG_PREFIX: &str = "/og/";
+/// Size of the generated preview images, the one Open Graph recommends.
+pub const OG_WIDTH: u32 = 1200;
+pub const OG_HEIGHT: u32 = 630;
+/// The preview of the full CV, used by the home page and as the fallback.
+pub const MAIN_CV: &str = "main";
+
+/// What a generated preview image shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OgKind {
+    Post,
+    Cv,
+}
+
+impl OgKind {
+    pub const ALL: [OgKind; 2] = [OgKind::Post, OgKind::Cv];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OgKind::Post => "post",
+            OgKind::Cv => "cv",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+}
+
+/// The site-relative URL of a generated preview image.
+pub fn og_image_path(kind: OgKind, slug: &str) -> String {
+    format!("{}{}/{}.png", OG_PREFIX, kind.as_str(), slug)
+}
+
+/// Site-wide fallbacks, taken from the CV basics. Provided as context by
+/// `App`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct SeoDefaults {
+    /// The owner's name, which page titles end with.
+    pub site_name: String,
+    pub description: String,
+    /// Used for pages without an image of their own. Absolute or
+    /// site-relative.
+    pub image: String,
+    /// `@handle` of a Twitter/X profile in the CV, if there is one.
+    pub twitter: Option<String>,
+}
+
+impl SeoDefaults {
+    pub fn from_basics(basics: &Basics) -> Self {
+        SeoDefaults {
+            site_name: basics.full_name(),
+            description: format!("{}. {}", basics.label, basics.summary),
+            image: og_image_path(OgKind::Cv, MAIN_CV),
+            twitter: basics
+                .profiles
+                .iter()
+                .find(|profile| ["twitter", "x"].contains(&profile.network.to_lowercase().as_str()))
+                .and_then(|profile| {
+                    profile
+                        .url
+                        .trim_end_matches('/')
+                        .rsplit('/')
+                        .next()
+                        .filter(|handle| !handle.is_empty() && !handle.contains('.'))
+                        .map(|handle| format!("@{}", handle.trim_start_matches('@')))
+                }),
+        }
+    }
+}
+
+/// The defaults for this site, from the bundled CV on the server.
+pub fn site_defaults() -> SeoDefaults {
+    #[cfg(feature = "server")]
+    {
+        SeoDefaults::from_basics(&crate::models::cv::master().basics)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        SeoDefaults::default()
+    }
+}
+
+/// The Open Graph type of a page.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum PageKind {
+    #[default]
+    Website,
+    Article,
+    Profile,
+}
+
+impl PageKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PageKind::Website => "website",
+            PageKind::Article => "article",
+            PageKind::Profile => "profile",
+        }
+    }
+}
+
+/// What a page says about itself. Empty fields fall back to
+/// [`SeoDefaults`].
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct PageMeta {
+    /// Empty for the home page, which is titled with the site name alone.
+    pub title: String,
+    pub description: String,
+    /// Absolute canonical URL.
+    pub url: String,
+    /// Absolute or site-relative.
+    pub image: Option<String>,
+    pub kind: PageKind,
+    pub published: Option<NaiveDate>,
+    pub tags: Vec<String>,
+}
+
+/// A `<meta>` tag. Open Graph tags use `property`, the others `name`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetaTag {
+    Name(&'static str, String),
+    Property(&'static str, String),
+}
+
+/// The `<title>`: the page title followed by the site name.
+pub fn full_title(title: &str, site_name: &str) -> String {
+    let title = title.trim();
+    if title.is_empty() || title == site_name {
+        site_name.to_string()
+    } else {
+        format!("{} · {}", title, site_name)
+    }
+}
+
+/// Makes a site-relative URL absolute.
+fn absolute(url: &str, site_url: &str) -> String {
+    if url.starts_with('/') && !url.starts_with("//") {
+        format!("{}{}", site_url.trim_end_matches('/'), url)
+    } else {
+        url.to_string()
+    }
+}
+
+/// The description, Open Graph and Twitter card tags for a page. Every page
+/// has a wide image (its own, or a generated one), so Twitter shows a large
+/// card.
+pub fn meta_tags(page: &PageMeta, defaults: &SeoDefaults, site_url: &str) -> Vec<MetaTag> {
+    let title = if page.title.trim().is_empty() {
+        defaults.site_name.clone()
+    } else {
+        page.title.trim().to_string()
+    };
+    let description = if page.description.trim().is_empty() {
+        defaults.description.clone()
+    } else {
+        page.description.trim().to_string()
+    };
+    let image = page.image.as_deref().unwrap_or(&defaults.image);
+    let generated = image.starts_with(OG_PREFIX);
+    let image = absolute(image, site_url);
+
+    let mut tags = vec![
+        MetaTag::Name("description", description.clone()),
+        MetaTag::Property("og:type", page.kind.as_str().to_string()),
+        MetaTag::Property("og:site_name", defaults.site_name.clone()),
+        MetaTag::Property("og:title", title.clone()),
+        MetaTag::Property("og:description", description.clone()),
+        MetaTag::Property("og:url", page.url.clone()),
+    ];
+    if !image.is_empty() {
+        tags.push(MetaTag::Property("og:image", image.clone()));
+        tags.push(MetaTag::Property("og:image:alt", title.clone()));
+    }
+    if generated {
+        tags.push(MetaTag::Property("og:image:type", "image/png".to_string()));
+        tags.push(MetaTag::Property("og:image:width", OG_WIDTH.to_string()));
+        tags.push(MetaTag::Property("og:image:height", OG_HEIGHT.to_string()));
+    }
+    if page.kind == PageKind::Article {
+        if let Some(date) = page.published {
+            tags.push(MetaTag::Property(
+                "article:published_time",
+                date.to_string(),
+            ));
+        }
+        tags.push(MetaTag::Property(
+            "article:author",
+            defaults.site_name.clone(),
+        ));
+        for tag in &page.tags {
+            tags.push(MetaTag::Property("article:tag", tag.clone()));
+        }
+    }
+
+    tags.push(MetaTag::Name(
+        "twitter:card",
+        "summary_large_image".to_string(),
+    ));
+    tags.push(MetaTag::Name("twitter:title", title.clone()));
+    tags.push(MetaTag::Name("twitter:description", description));
+    if !image.is_empty() {
+        tags.push(MetaTag::Name("twitter:image", image));
+        tags.push(MetaTag::Name("twitter:image:alt", title));
+    }
+    if let Some(handle) = &defaults.twitter {
+        tags.push(MetaTag::Name("twitter:site", handle.clone()));
+        tags.push(MetaTag::Name("twitter:creator", handle.clone()));
+    }
+    tags
+}
@@ -0,0 +1,82 @@
+//! Multi-part series: posts sharing a `series` name in their front matter,
+//! read in order.
+
+use crate::models::archive::PostLink;
+#[cfg(feature = "server")]
+use crate::models::blog::public_posts;
+use crate::models::blog::PostMeta;
+use crate::models::tags::tag_slug;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A series as seen from one of its parts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Series {
+    pub name: String,
+    pub parts: Vec<PostLink>,
+    /// Index of the part being read.
+    pub current: usize,
+}
+
+impl Series {
+    pub fn prev(&self) -> Option<&PostLink> {
+        self.current.checked_sub(1).and_then(|i| self.parts.get(i))
+    }
+
+    pub fn next(&self) -> Option<&PostLink> {
+        self.parts.get(self.current + 1)
+    }
+}
+
+/// Whether `post` is part of the series called `name`. Names are compared
+/// by slug, so "Rust Web" and "rust web" are the same series.
+pub fn in_series(post: &PostMeta, name: &str) -> bool {
+    post.series
+        .as_deref()
+        .is_some_and(|series| tag_slug(series) == tag_slug(name))
+}
+
+/// The parts of a series in reading order: numbered parts first, then the
+/// rest oldest first.
+pub fn series_parts<'a>(posts: &'a [PostMeta], name: &str) -> Vec<&'a PostMeta> {
+    let mut parts: Vec<&PostMeta> = posts.iter().filter(|post| in_series(post, name)).collect();
+    parts.sort_by(|a, b| {
+        (a.series_part.is_none(), a.series_part, a.date, &a.slug).cmp(&(
+            b.series_part.is_none(),
+            b.series_part,
+            b.date,
+            &b.slug,
+        ))
+    });
+    parts
+}
+
+/// The series `slug` belongs to, if any.
+pub fn series_of(posts: &[PostMeta], slug: &str) -> Option<Series> {
+    let post = posts.iter().find(|post| post.slug == slug)?;
+    let name = post.series.clone()?;
+    let parts = series_parts(posts, &name);
+    Some(Series {
+        current: parts.iter().position(|part| part.slug == slug)?,
+        parts: parts
+            .into_iter()
+            .map(|part| PostLink {
+                slug: part.slug.clone(),
+                title: part.title.clone(),
+            })
+            .collect(),
+        name,
+    })
+}
+
+#[server]
+pub async fn get_series(slug: String) -> Result<Option<Series>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        Ok(series_of(&public_posts()?, &slug))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
@@ -0,0 +1,156 @@
+//! Private share links for CV variants.
+
+use crate::models::cv::TailoredCv;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ShareLink {
+    pub id: String,
+    pub token: String,
+    pub variant: String,
+    pub label: String,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub revoked: bool,
+    pub views: i64,
+}
+
+/// One logged open of a share link.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ShareView {
+    pub viewed_at: i64,
+    pub referrer: Option<String>,
+}
+
+impl ShareLink {
+    pub fn path(&self) -> String {
+        format!("/s/{}", self.token)
+    }
+}
+
+/// Creates a share link for `variant` that expires after `days` days.
+#[server]
+pub async fn create_share_link(
+    variant: String,
+    label: String,
+    days: u32,
+) -> Result<ShareLink, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, session, share};
+
+        session::require_owner()?;
+        if crate::models::cv::master().variant(&variant).is_none() {
+            return Err(ServerFnError::new(format!(
+                "Unknown CV variant: {}",
+                variant
+            )));
+        }
+        if days == 0 || days > 365 {
+            return Err(ServerFnError::new("Expiry must be between 1 and 365 days"));
+        }
+
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        let now = db::now();
+        share::create(
+            &conn,
+            session::secret(),
+            &variant,
+            label.trim(),
+            now,
+            now + days as i64 * 24 * 60 * 60,
+        )
+        .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+#[server]
+pub async fn list_share_links() -> Result<Vec<ShareLink>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, session, share};
+
+        session::require_owner()?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        share::list(&conn, session::secret()).map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Logged views of a share link, newest first.
+#[server]
+pub async fn list_share_link_views(id: String) -> Result<Vec<ShareView>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, session, share};
+
+        session::require_owner()?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        let views = share::views(&conn, &id).map_err(|e| ServerFnError::new(e.to_string()))?;
+        Ok(views
+            .into_iter()
+            .map(|(viewed_at, referrer)| ShareView {
+                viewed_at,
+                referrer,
+            })
+            .collect())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+#[server]
+pub async fn revoke_share_link(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, session, share};
+
+        session::require_owner()?;
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        share::revoke(&conn, &id).map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
+
+/// Opens a share link, logging the view, and returns the unlocked variant
+/// including private contact details.
+#[server]
+pub async fn open_share_link(token: String) -> Result<TailoredCv, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, session, share};
+        use dioxus::fullstack::http::header;
+
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        let referrer = session::request_header(header::REFERER);
+        let variant = share::open(
+            &conn,
+            session::secret(),
+            &token,
+            db::now(),
+            referrer.as_deref(),
+        )
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        crate::models::cv::master()
+            .tailor(&variant)
+            .ok_or_else(|| ServerFnError::new(format!("Unknown CV variant: {}", variant)))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
@@ -0,0 +1,31 @@
+//! The public base URL of the site, shared with the client so pages can build
+//! absolute links for metadata.
+
+/// Provided as context by `App`; read it with `use_context::<SiteUrl>()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SiteUrl(pub String);
+
+impl SiteUrl {
+    /// Joins a site-relative path (`/blog/post`) onto the base URL.
+    pub fn absolute(&self, path: &str) -> String {
+        format!(
+            "{}/{}",
+            self.0.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+}
+
+/// `SITE_URL` on the server; in the browser, the current origin.
+pub fn site_url() -> String {
+    #[cfg(feature = "server")]
+    {
+        crate::backend::site_url()
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        web_sys::window()
+            .and_then(|window| window.location().origin().ok())
+            .unwrap_or_default()
+    }
+}
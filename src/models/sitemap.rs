@@ -0,0 +1,153 @@
+//! `sitemap.xml` and `robots.txt`, derived from the `Route` enum so new pages
+//! are picked up (or kept out) without editing a list here.
+
+use crate::models::archive::months;
+use crate::models::blog::PostMeta;
+use crate::models::feed::escape_xml;
+use crate::models::tags::tag_counts;
+use crate::Route;
+use chrono::{Datelike, NaiveDate};
+use dioxus::router::routable::{Routable, SegmentType};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SitemapEntry {
+    pub route: Route,
+    pub lastmod: Option<NaiveDate>,
+}
+
+/// Every indexable page: the static routes plus posts, tags, month archives
+/// and public CV variants. `posts` must be public posts, newest first.
+pub fn entries(posts: &[PostMeta], variants: &[String]) -> Vec<SitemapEntry> {
+    let newest = posts.first().map(|post| post.date);
+    let mut entries: Vec<SitemapEntry> = Route::static_routes()
+        .into_iter()
+        .map(|route| SitemapEntry {
+            lastmod: match route {
+                Route::Blog {} => newest,
+                _ => None,
+            },
+            route,
+        })
+        .collect();
+
+    // Posts first published elsewhere point search engines there instead
+    entries.extend(
+        posts
+            .iter()
+            .filter(|post| post.canonical_url.is_none())
+            .map(|post| SitemapEntry {
+                route: Route::BlogPost {
+                    slug: post.slug.clone(),
+                },
+                lastmod: Some(post.date),
+            }),
+    );
+    entries.extend(tag_counts(posts).into_iter().map(|tag| {
+        SitemapEntry {
+            lastmod: posts
+                .iter()
+                .find(|post| post.has_tag(&tag.slug))
+                .map(|post| post.date),
+            route: Route::BlogTag { tag: tag.slug },
+        }
+    }));
+    entries.extend(months(posts).into_iter().map(|month| {
+        SitemapEntry {
+            route: Route::BlogArchive {
+                year: month.year,
+                month: month.month,
+            },
+            lastmod: posts
+                .iter()
+                .find(|post| post.date.year() == month.year && post.date.month() == month.month)
+                .map(|post| post.date),
+        }
+    }));
+    entries.extend(variants.iter().map(|slug| SitemapEntry {
+        route: Route::CvVariant {
+            variant: slug.clone(),
+        },
+        lastmod: None,
+    }));
+
+    entries.retain(|entry| entry.route.is_indexable());
+    entries
+}
+
+pub fn sitemap_xml(site_url: &str, entries: &[SitemapEntry]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for entry in entries {
+        xml.push_str(&format!(
+            "<url><loc>{}</loc>",
+            escape_xml(&format!("{}{}", site_url, entry.route))
+        ));
+        if let Some(date) = entry.lastmod {
+            xml.push_str(&format!("<lastmod>{}</lastmod>", date));
+        }
+        xml.push_str("</url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Path prefixes of routes that must not be crawled. Dynamic segments are
+/// filled with a placeholder to find out which route a path belongs to; the
+/// rule covers everything up to the first dynamic segment.
+pub fn disallowed_paths() -> Vec<String> {
+    let mut paths = Vec::new();
+    for segments in Route::flatten_site_map() {
+        let mut prefix = String::new();
+        let mut sample = String::new();
+        let mut dynamic = false;
+        for segment in segments.iter() {
+            match segment {
+                SegmentType::Static(s) => {
+                    sample.push('/');
+                    sample.push_str(s);
+                    if !dynamic {
+                        prefix = sample.clone();
+                    }
+                }
+                SegmentType::Dynamic(_) | SegmentType::CatchAll(_) => {
+                    sample.push_str("/1");
+                    if !dynamic {
+                        prefix.push('/');
+                    }
+                    dynamic = true;
+                }
+                _ => {}
+            }
+        }
+        let protected = sample
+            .parse::<Route>()
+            .map(|route| !route.is_indexable())
+            .unwrap_or(false);
+        // A protected route at the root would block the whole site
+        if protected && prefix != "/" && !paths.contains(&prefix) {
+            paths.push(prefix);
+        }
+    }
+    paths.sort();
+    paths
+}
+
+/// `robots.txt` allowing everything except protected routes, or nothing at
+/// all when `disallow_all` is set (staging sites). `extra` is appended as is.
+pub fn robots_txt(site_url: &str, disallow_all: bool, extra: Option<&str>) -> String {
+    let mut robots = String::from("User-agent: *\n");
+    if disallow_all {
+        robots.push_str("Disallow: /\n");
+    } else {
+        for path in disallowed_paths() {
+            robots.push_str(&format!("Disallow: {}\n", path));
+        }
+    }
+    if let Some(extra) = extra.map(str::trim).filter(|extra| !extra.is_empty()) {
+        robots.push('\n');
+        robots.push_str(extra);
+        robots.push('\n');
+    }
+    robots.push_str(&format!("\nSitemap: {}/sitemap.xml\n", site_url));
+    robots
+}
@@ -0,0 +1,79 @@
+//! Schema.org JSON-LD documents for search engine rich results.
+
+use crate::models::cv::{Basics, TailoredCv};
+use serde_json::{json, Value};
+
+/// The fields of a blog post that end up in a `BlogPosting` document.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Article {
+    pub title: String,
+    pub description: String,
+    /// Absolute URL of the post.
+    pub url: String,
+    /// ISO 8601 date, e.g. `2025-01-31`.
+    pub date_published: String,
+    pub date_modified: Option<String>,
+    pub tags: Vec<String>,
+    /// Copies of the post on other sites.
+    pub syndication: Vec<String>,
+}
+
+/// A `Person` built from the CV basics, with `sameAs` pointing at the social
+/// profiles.
+pub fn person(basics: &Basics, site_url: &str) -> Value {
+    json!({
+        "@type": "Person",
+        "@id": format!("{}/#person", site_url),
+        "name": basics.full_name(),
+        "givenName": basics.first_name,
+        "familyName": basics.last_name,
+        "jobTitle": basics.label,
+        "description": basics.summary,
+        "email": format!("mailto:{}", basics.email),
+        "image": basics.image,
+        "url": site_url,
+        "sameAs": basics.profiles.iter().map(|p| p.url.as_str()).collect::<Vec<_>>(),
+    })
+}
+
+/// A `ProfilePage` whose main entity is the CV owner. `page_url` is the
+/// absolute URL of the page being rendered.
+pub fn profile_page(cv: &TailoredCv, site_url: &str, page_url: &str) -> Value {
+    let mut person = person(&cv.basics, site_url);
+    person["knowsAbout"] = cv.skills.iter().map(|s| s.name.as_str()).collect();
+    json!({
+        "@context": "https://schema.org",
+        "@type": "ProfilePage",
+        "url": page_url,
+        "name": format!("{} — {}", cv.basics.full_name(), cv.basics.label),
+        "mainEntity": person,
+    })
+}
+
+/// A `BlogPosting` authored by the CV owner.
+pub fn blog_posting(article: &Article, author: &Basics, site_url: &str) -> Value {
+    let mut document = json!({
+        "@context": "https://schema.org",
+        "@type": "BlogPosting",
+        "headline": article.title,
+        "description": article.description,
+        "url": article.url,
+        "mainEntityOfPage": article.url,
+        "datePublished": article.date_published,
+        "dateModified": article.date_modified.as_deref().unwrap_or(&article.date_published),
+        "author": person(author, site_url),
+    });
+    if !article.tags.is_empty() {
+        document["keywords"] = article.tags.join(", ").into();
+    }
+    if !article.syndication.is_empty() {
+        document["sameAs"] = article.syndication.clone().into();
+    }
+    document
+}
+
+/// Serialises a document for an inline `<script>`, escaping `<` so values
+/// cannot close the tag early.
+pub fn to_script(document: &Value) -> String {
+    document.to_string().replace('<', "\\u003c")
+}
@@ -0,0 +1,105 @@
+//! Post tags. Tags are compared by slug, so "Rust", "rust" and " RUST " are
+//! the same tag; the most common spelling is used for display.
+
+use crate::models::blog::PostMeta;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TagCount {
+    pub slug: String,
+    pub name: String,
+    pub count: usize,
+}
+
+/// A tag and its posts, newest first.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TagArchive {
+    pub tag: TagCount,
+    pub posts: Vec<PostMeta>,
+}
+
+/// URL-safe form of a tag: lowercase words joined by dashes. `+` and `#`
+/// are spelled out so `C++` and `C#` stay distinct from `C`.
+pub fn tag_slug(tag: &str) -> String {
+    let mut slug = String::new();
+    for c in tag.trim().to_lowercase().chars() {
+        match c {
+            '+' => slug.push_str("-plus-"),
+            '#' => slug.push_str("-sharp-"),
+            c if c.is_alphanumeric() => slug.push(c),
+            _ => slug.push('-'),
+        }
+    }
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Trims tags and drops empty ones and repeats of the same slug, keeping
+/// the first spelling.
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut seen = Vec::new();
+    let mut normalized = Vec::new();
+    for tag in tags {
+        let slug = tag_slug(&tag);
+        if !slug.is_empty() && !seen.contains(&slug) {
+            seen.push(slug);
+            normalized.push(tag.trim().to_string());
+        }
+    }
+    normalized
+}
+
+/// Every tag used by `posts`, most used first.
+pub fn tag_counts<'a>(posts: impl IntoIterator<Item = &'a PostMeta>) -> Vec<TagCount> {
+    let mut spellings: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+    for post in posts {
+        for tag in &post.tags {
+            *spellings
+                .entry(tag_slug(tag))
+                .or_default()
+                .entry(tag.clone())
+                .or_default() += 1;
+        }
+    }
+
+    let mut counts: Vec<TagCount> = spellings
+        .into_iter()
+        .map(|(slug, names)| {
+            let count = names.values().sum();
+            // Most common spelling; ties go to the alphabetically first one
+            let name = names
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                .map(|(name, _)| name.clone())
+                .unwrap_or_default();
+            TagCount { slug, name, count }
+        })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then(a.slug.cmp(&b.slug)));
+    counts
+}
+
+#[server]
+pub async fn get_tag_archive(tag: String) -> Result<TagArchive, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let slug = tag_slug(&tag);
+        let posts: Vec<PostMeta> = crate::models::blog::public_posts()?
+            .into_iter()
+            .filter(|meta| meta.has_tag(&slug))
+            .collect();
+        let tag = tag_counts(&posts)
+            .into_iter()
+            .find(|t| t.slug == slug)
+            .ok_or_else(|| ServerFnError::new(format!("No posts tagged {}", tag)))?;
+        Ok(TagArchive { tag, posts })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
@@ -0,0 +1,84 @@
+//! Heading ids and the table of contents of a post.
+
+use serde::{Deserialize, Serialize};
+
+/// A heading and the headings nested under it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TocEntry {
+    /// 1 for `#`, 2 for `##`, ...
+    pub level: u8,
+    pub id: String,
+    pub title: String,
+    pub children: Vec<TocEntry>,
+}
+
+impl TocEntry {
+    /// Ids of this entry and everything under it, in document order.
+    pub fn ids(&self) -> Vec<String> {
+        let mut ids = vec![self.id.clone()];
+        ids.extend(self.children.iter().flat_map(TocEntry::ids));
+        ids
+    }
+}
+
+/// The id of a heading, as GitHub makes them: lowercase, words joined by
+/// `-`, punctuation dropped. The same title always gives the same id, so
+/// links to a section survive edits elsewhere in the post.
+pub fn heading_slug(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() || c == '_' {
+            slug.push(c);
+        } else if (c.is_whitespace() || c == '-') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Hands out heading ids, numbering repeats (`setup`, `setup-1`, ...).
+#[derive(Default)]
+pub struct HeadingIds {
+    used: Vec<String>,
+}
+
+impl HeadingIds {
+    pub fn id_for(&mut self, title: &str) -> String {
+        let base = heading_slug(title);
+        let mut id = base.clone();
+        let mut n = 0;
+        while self.used.contains(&id) {
+            n += 1;
+            id = format!("{}-{}", base, n);
+        }
+        self.used.push(id.clone());
+        id
+    }
+}
+
+/// Nests headings listed in document order under the closest preceding
+/// heading of a higher level.
+pub fn build_toc(headings: &[(u8, String, String)]) -> Vec<TocEntry> {
+    let mut entries = Vec::new();
+    let mut i = 0;
+    while i < headings.len() {
+        let (level, id, title) = &headings[i];
+        let end = headings[i + 1..]
+            .iter()
+            .position(|(next, _, _)| next <= level)
+            .map_or(headings.len(), |offset| i + 1 + offset);
+        entries.push(TocEntry {
+            level: *level,
+            id: id.clone(),
+            title: title.clone(),
+            children: build_toc(&headings[i + 1..end]),
+        });
+        i = end;
+    }
+    entries
+}
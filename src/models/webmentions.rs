@@ -0,0 +1,257 @@
+//! Webmentions (https://www.w3.org/TR/webmention/): other sites telling us
+//! they link to a post, and us telling them when a post links to theirs.
+//! The HTTP side lives in `backend::webmentions`; this module has what is
+//! shown under posts and the parsing both directions share.
+
+use dioxus::prelude::*;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// Longest page title kept for a mention.
+pub const MAX_TITLE_CHARS: usize = 200;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WebmentionStatus {
+    /// Received, the source has not been checked yet.
+    #[default]
+    Pending,
+    /// The source links to the post.
+    Verified,
+    /// The source does not link to the post (any more), or is gone.
+    Rejected,
+}
+
+impl WebmentionStatus {
+    pub const ALL: [WebmentionStatus; 3] = [
+        WebmentionStatus::Pending,
+        WebmentionStatus::Verified,
+        WebmentionStatus::Rejected,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebmentionStatus::Pending => "pending",
+            WebmentionStatus::Verified => "verified",
+            WebmentionStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_str() == status)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WebmentionStatus::Pending => "Pending",
+            WebmentionStatus::Verified => "Verified",
+            WebmentionStatus::Rejected => "Rejected",
+        }
+    }
+}
+
+/// A verified mention of a post, as shown under it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Webmention {
+    /// The page that links to the post.
+    pub source: String,
+    /// Title of the source page, empty when it has none.
+    pub title: String,
+    pub verified_at: i64,
+}
+
+impl Webmention {
+    /// The host of the source, shown when there is no title.
+    pub fn host(&self) -> String {
+        Url::parse(&self.source)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+}
+
+/// Parses an absolute `http` or `https` URL.
+pub fn parse_http_url(url: &str) -> Option<Url> {
+    Url::parse(url.trim())
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some())
+}
+
+/// A start tag found by [`tags`]: its lowercase name and attributes, with
+/// lowercase names and decoded values.
+struct Tag {
+    name: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl Tag {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the space-separated `rel` attribute contains `rel`.
+    fn has_rel(&self, rel: &str) -> bool {
+        self.attribute("rel").is_some_and(|rels| {
+            rels.split_ascii_whitespace()
+                .any(|r| r.eq_ignore_ascii_case(rel))
+        })
+    }
+}
+
+pub(crate) fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The start tags of an HTML document in order, skipping comments. Good
+/// enough to find links without a full HTML parser.
+fn tags(html: &str) -> Vec<Tag> {
+    let mut tags = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let name_len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        if name_len == 0 {
+            continue;
+        }
+        let name = rest[..name_len].to_ascii_lowercase();
+        rest = &rest[name_len..];
+
+        let mut attributes = Vec::new();
+        loop {
+            rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+            if rest.is_empty() || rest.starts_with('>') {
+                break;
+            }
+            let key_len = rest
+                .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '>' || c == '/')
+                .unwrap_or(rest.len())
+                .max(1);
+            let key = rest[..key_len].to_ascii_lowercase();
+            rest = rest[key_len..].trim_start();
+            let mut value = String::new();
+            if let Some(after) = rest.strip_prefix('=') {
+                let after = after.trim_start();
+                let (raw, remaining) = match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let inner = &after[1..];
+                        let end = inner.find(quote).unwrap_or(inner.len());
+                        (&inner[..end], inner.get(end + 1..).unwrap_or(""))
+                    }
+                    _ => {
+                        let end = after
+                            .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                            .unwrap_or(after.len());
+                        (&after[..end], &after[end..])
+                    }
+                };
+                value = decode_entities(raw);
+                rest = remaining;
+            }
+            attributes.push((key, value));
+        }
+        tags.push(Tag { name, attributes });
+    }
+    tags
+}
+
+/// Every `<a href>` of `html`, resolved against `base`.
+pub fn links(html: &str, base: &Url) -> Vec<Url> {
+    tags(html)
+        .iter()
+        .filter(|tag| tag.name == "a")
+        .filter_map(|tag| tag.attribute("href"))
+        .filter_map(|href| base.join(href.trim()).ok())
+        .collect()
+}
+
+/// Whether `html`, served from `base`, links to `target`. Fragments are
+/// ignored on both sides.
+pub fn links_to(html: &str, base: &Url, target: &Url) -> bool {
+    let without_fragment = |url: &Url| {
+        let mut url = url.clone();
+        url.set_fragment(None);
+        url
+    };
+    let target = without_fragment(target);
+    links(html, base)
+        .iter()
+        .any(|link| without_fragment(link) == target)
+}
+
+/// The contents of the `<title>` element, with whitespace collapsed.
+pub fn page_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title = decode_entities(&html[start..end])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!title.is_empty()).then(|| title.chars().take(MAX_TITLE_CHARS).collect())
+}
+
+/// The first `rel="webmention"` URL of `Link` header values
+/// (`<https://example.com/webmention>; rel="webmention"`).
+fn endpoint_from_link_headers(headers: &[String], base: &Url) -> Option<Url> {
+    headers
+        .iter()
+        .flat_map(|header| header.split(','))
+        .find_map(|link| {
+            let (url, params) = link.trim().strip_prefix('<')?.split_once('>')?;
+            let is_webmention = params.split(';').any(|param| {
+                param.trim().split_once('=').is_some_and(|(key, value)| {
+                    key.trim().eq_ignore_ascii_case("rel")
+                        && value
+                            .trim()
+                            .trim_matches('"')
+                            .split_ascii_whitespace()
+                            .any(|rel| rel.eq_ignore_ascii_case("webmention"))
+                })
+            });
+            is_webmention.then(|| base.join(url.trim()).ok()).flatten()
+        })
+}
+
+/// The Webmention endpoint a page at `target` advertises, from its `Link`
+/// headers first and then the first `<link>` or `<a>` with
+/// `rel="webmention"`. An empty `href` means the page itself.
+pub fn discover_endpoint(target: &Url, link_headers: &[String], html: &str) -> Option<Url> {
+    endpoint_from_link_headers(link_headers, target).or_else(|| {
+        tags(html)
+            .iter()
+            .filter(|tag| matches!(tag.name.as_str(), "link" | "a") && tag.has_rel("webmention"))
+            .find_map(|tag| tag.attribute("href"))
+            .and_then(|href| target.join(href.trim()).ok())
+    })
+}
+
+/// Verified mentions of a post, oldest first.
+#[server]
+pub async fn get_webmentions(slug: String) -> Result<Vec<Webmention>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        use crate::backend::{db, webmentions};
+
+        let conn = db::connect().map_err(|e| ServerFnError::new(e.to_string()))?;
+        webmentions::for_post(&conn, &slug).map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server only"))
+    }
+}
@@ -0,0 +1,109 @@
+use crate::components::{LoadingSpinner, SectionCard, Seo};
+use crate::models::comments::{list_comments, moderate_comment, CommentStatus};
+use crate::Route;
+use dioxus::prelude::*;
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+#[component]
+pub fn AdminComments() -> Element {
+    let mut status = use_signal(|| CommentStatus::Pending);
+    let mut comments = use_resource(move || list_comments(status()));
+    let mut error = use_signal(|| None::<String>);
+
+    let moderate = move |id: i64, to: CommentStatus| {
+        spawn(async move {
+            match moderate_comment(id, to).await {
+                Ok(_) => {
+                    error.set(None);
+                    comments.restart();
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        Seo { title: "Comments" }
+        div { class: "p-8 max-w-5xl",
+            h1 { class: "text-4xl font-bold mb-8 text-blue-500", "Comments" }
+
+            div { class: "flex flex-wrap gap-2 mb-6",
+                for s in CommentStatus::ALL {
+                    button {
+                        class: if s == status() { "px-4 py-2 rounded-lg bg-blue-600 text-white text-sm font-bold" } else { "px-4 py-2 rounded-lg bg-gray-900 border border-gray-800 text-gray-400 hover:text-white text-sm transition-colors" },
+                        onclick: move |_| status.set(s),
+                        "{s.label()}"
+                    }
+                }
+            }
+            if let Some(e) = error() {
+                p { class: "text-red-400 mb-4", "{e}" }
+            }
+
+            SectionCard {
+                title: format!("{} Comments", status().label()),
+                icon: "fa-comments".to_string(),
+                match comments() {
+                    Some(Ok(items)) if items.is_empty() => rsx! {
+                        p { class: "text-gray-500", "Nothing here." }
+                    },
+                    Some(Ok(items)) => rsx! {
+                        div { class: "space-y-4",
+                            for item in items {
+                                div {
+                                    key: "{item.comment.id}",
+                                    class: "p-4 rounded-lg bg-gray-900 border border-gray-800",
+                                    div { class: "flex flex-wrap items-baseline gap-x-3 gap-y-1 text-sm mb-2",
+                                        span { class: "font-bold text-white", "{item.email}" }
+                                        span { class: "text-gray-500", "on" }
+                                        Link {
+                                            to: Route::BlogPost { slug: item.post_slug.clone() },
+                                            class: "text-blue-500 hover:text-white transition-colors",
+                                            "{item.post_title}"
+                                        }
+                                        span { class: "text-xs text-gray-500",
+                                            "{format_timestamp(item.comment.created_at)}"
+                                            if item.comment.parent_id.is_some() {
+                                                " · reply"
+                                            }
+                                            if item.comment.edited {
+                                                " · edited"
+                                            }
+                                        }
+                                    }
+                                    div { class: "comment-body text-sm", dangerous_inner_html: "{item.comment.html}" }
+                                    div { class: "flex gap-4 mt-3 text-sm",
+                                        for to in [CommentStatus::Approved, CommentStatus::Rejected, CommentStatus::Spam] {
+                                            if to != item.comment.status {
+                                                button {
+                                                    class: "text-gray-400 hover:text-white transition-colors",
+                                                    onclick: move |_| moderate(item.comment.id, to),
+                                                    match to {
+                                                        CommentStatus::Approved => "Approve",
+                                                        CommentStatus::Rejected => "Reject",
+                                                        _ => "Mark as spam",
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        p { class: "text-red-400", "{e}" }
+                    },
+                    None => rsx! {
+                        LoadingSpinner { message: "Loading comments...".to_string() }
+                    },
+                }
+            }
+        }
+    }
+}
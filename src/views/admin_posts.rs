@@ -0,0 +1,197 @@
+use crate::components::{LoadingSpinner, SectionCard, Seo};
+use crate::models::blog::{is_valid_slug, PostStatus};
+use crate::models::editor::{list_all_posts, set_post_status, PostOrigin};
+use crate::models::import::{import_posts, ImportReport, ACCEPTED_FILES};
+use crate::Route;
+use dioxus::prelude::*;
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+#[component]
+pub fn AdminPosts() -> Element {
+    let mut posts = use_resource(list_all_posts);
+    let mut slug = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut importing = use_signal(|| false);
+    let mut report = use_signal(|| None::<Result<ImportReport, String>>);
+    let navigator = use_navigator();
+
+    let create = move |evt: FormEvent| {
+        evt.prevent_default();
+        let slug = slug().trim().to_string();
+        if is_valid_slug(&slug) {
+            navigator.push(Route::EditPost { slug });
+        } else {
+            error.set(Some(
+                "Use lowercase letters, digits and single dashes".to_string(),
+            ));
+        }
+    };
+
+    let change_status = move |slug: String, status: PostStatus| {
+        spawn(async move {
+            match set_post_status(slug, status).await {
+                Ok(_) => {
+                    error.set(None);
+                    posts.restart();
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let import = move |evt: FormEvent| {
+        let Some(file) = evt.files().into_iter().next() else {
+            return;
+        };
+        spawn(async move {
+            importing.set(true);
+            report.set(None);
+            let result = import_posts(file.into()).await;
+            if result.is_ok() {
+                posts.restart();
+            }
+            report.set(Some(result.map_err(|e| e.to_string())));
+            importing.set(false);
+        });
+    };
+
+    rsx! {
+        Seo { title: "Posts" }
+        div { class: "p-8 max-w-5xl",
+            h1 { class: "text-4xl font-bold mb-8 text-blue-500", "Posts" }
+
+            div { class: "grid gap-8",
+                SectionCard {
+                    title: "New Post".to_string(),
+                    icon: "fa-pen".to_string(),
+                    form { class: "flex flex-col md:flex-row gap-4 md:items-end", onsubmit: create,
+                        div { class: "flex-grow",
+                            label { class: "block text-xs uppercase text-gray-500 mb-1", "Slug" }
+                            input {
+                                class: "w-full p-3 rounded-lg bg-gray-900 border border-gray-800 text-white font-mono",
+                                placeholder: "my-new-post",
+                                value: "{slug}",
+                                oninput: move |evt| slug.set(evt.value()),
+                            }
+                        }
+                        button {
+                            class: "bg-blue-600 hover:bg-blue-700 text-white font-bold py-3 px-6 rounded-xl transition-all",
+                            r#type: "submit",
+                            "Write"
+                        }
+                    }
+                    if let Some(e) = error() {
+                        p { class: "text-red-400 mt-4", "{e}" }
+                    }
+                }
+
+                SectionCard {
+                    title: "Import".to_string(),
+                    icon: "fa-file-import".to_string(),
+                    p { class: "text-gray-400 mb-4",
+                        "Upload a Medium export (.zip) or a dev.to export (articles.json, or its .zip). "
+                        "Dates, tags and original URLs are kept, and images are copied here."
+                    }
+                    label { class: "inline-block bg-gray-800 hover:bg-gray-700 text-white font-bold py-3 px-6 rounded-xl transition-all cursor-pointer",
+                        i { class: "fas fa-upload mr-2" }
+                        if importing() {
+                            "Importing..."
+                        } else {
+                            "Choose export"
+                        }
+                        input {
+                            r#type: "file",
+                            class: "hidden",
+                            accept: ACCEPTED_FILES,
+                            disabled: importing(),
+                            onchange: import,
+                        }
+                    }
+                    match report() {
+                        Some(Ok(report)) => rsx! {
+                            div { class: "mt-4 space-y-2 text-sm",
+                                p { class: "text-green-400",
+                                    "Imported {report.imported.len()} posts, skipped {report.skipped.len()}."
+                                }
+                                for line in report.skipped.iter().chain(report.warnings.iter()) {
+                                    p { class: "text-amber-400", "{line}" }
+                                }
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "text-red-400 mt-4", "{e}" }
+                        },
+                        None => rsx! {},
+                    }
+                }
+
+                SectionCard {
+                    title: "All Posts".to_string(),
+                    icon: "fa-list".to_string(),
+                    match posts() {
+                        Some(Ok(list)) if list.is_empty() => rsx! {
+                            p { class: "text-gray-500", "No posts yet." }
+                        },
+                        Some(Ok(list)) => rsx! {
+                            div { class: "space-y-4",
+                                for post in list {
+                                    div {
+                                        key: "{post.meta.slug}",
+                                        class: "p-4 rounded-lg bg-gray-900 border border-gray-800 flex flex-col md:flex-row md:items-center gap-4",
+                                        div { class: "flex-grow overflow-hidden",
+                                            Link {
+                                                to: Route::EditPost { slug: post.meta.slug.clone() },
+                                                class: "font-bold text-white hover:text-blue-400",
+                                                "{post.meta.title}"
+                                            }
+                                            p { class: "text-xs text-gray-500 mt-1",
+                                                "{post.meta.status.label()} · {post.meta.date} · "
+                                                match post.origin {
+                                                    PostOrigin::File => "file",
+                                                    PostOrigin::Saved => "saved in editor",
+                                                }
+                                                if let Some(at) = post.autosaved_at {
+                                                    " · unsaved changes from {format_timestamp(at)}"
+                                                }
+                                            }
+                                        }
+                                        if post.meta.status == PostStatus::Published {
+                                            button {
+                                                class: "text-sm text-gray-400 hover:text-red-400 transition-colors",
+                                                onclick: {
+                                                    let slug = post.meta.slug.clone();
+                                                    move |_| change_status(slug.clone(), PostStatus::Draft)
+                                                },
+                                                "Unpublish"
+                                            }
+                                        } else {
+                                            button {
+                                                class: "text-sm text-gray-400 hover:text-green-400 transition-colors",
+                                                onclick: {
+                                                    let slug = post.meta.slug.clone();
+                                                    move |_| change_status(slug.clone(), PostStatus::Published)
+                                                },
+                                                "Publish"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "text-red-400", "{e}" }
+                        },
+                        None => rsx! {
+                            LoadingSpinner { message: "Loading posts...".to_string() }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::components::{LoadingSpinner, SectionCard, Seo};
+use crate::models::share::{
+    create_share_link, list_share_link_views, list_share_links, revoke_share_link,
+};
+use dioxus::prelude::*;
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// The view log of one link, loaded when it is expanded.
+#[component]
+fn ShareLinkViews(id: String) -> Element {
+    let views = use_resource(move || list_share_link_views(id.clone()));
+
+    match views() {
+        Some(Ok(views)) if views.is_empty() => rsx! {
+            p { class: "text-xs text-gray-500 mt-2", "Not opened yet." }
+        },
+        Some(Ok(views)) => rsx! {
+            ul { class: "text-xs text-gray-400 mt-2 space-y-1",
+                for view in views {
+                    li {
+                        "{format_timestamp(view.viewed_at)} · "
+                        {view.referrer.unwrap_or_else(|| "no referrer".to_string())}
+                    }
+                }
+            }
+        },
+        Some(Err(e)) => rsx! {
+            p { class: "text-xs text-red-400 mt-2", "{e}" }
+        },
+        None => rsx! {
+            p { class: "text-xs text-gray-500 mt-2", "Loading views..." }
+        },
+    }
+}
+
+#[component]
+pub fn AdminShareLinks() -> Element {
+    let mut links = use_resource(list_share_links);
+    let variants = use_resource(crate::models::cv::list_cv_variants);
+    let mut variant = use_signal(String::new);
+    let mut label = use_signal(String::new);
+    let mut days = use_signal(|| 14u32);
+    let mut error = use_signal(|| None::<String>);
+    let mut expanded = use_signal(|| None::<String>);
+
+    let create = move |evt: FormEvent| {
+        evt.prevent_default();
+        spawn(async move {
+            match create_share_link(variant(), label(), days()).await {
+                Ok(_) => {
+                    error.set(None);
+                    label.set(String::new());
+                    links.restart();
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        Seo { title: "Share Links" }
+        div { class: "p-8 max-w-5xl",
+            h1 { class: "text-4xl font-bold mb-8 text-blue-500", "Share Links" }
+
+            div { class: "grid gap-8",
+                SectionCard {
+                    title: "New Link".to_string(),
+                    icon: "fa-link".to_string(),
+                    form { class: "grid grid-cols-1 md:grid-cols-4 gap-4 items-end", onsubmit: create,
+                        div {
+                            label { class: "block text-xs uppercase text-gray-500 mb-1", "Variant" }
+                            select {
+                                class: "w-full p-3 rounded-lg bg-gray-900 border border-gray-800 text-white",
+                                onchange: move |evt| variant.set(evt.value()),
+                                option { value: "", "Choose..." }
+                                if let Some(Ok(variants)) = variants() {
+                                    for v in variants {
+                                        option { value: "{v.slug}",
+                                            if v.public { "{v.name}" } else { "{v.name} (private)" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div {
+                            label { class: "block text-xs uppercase text-gray-500 mb-1", "Label" }
+                            input {
+                                class: "w-full p-3 rounded-lg bg-gray-900 border border-gray-800 text-white",
+                                placeholder: "Acme Corp application",
+                                value: "{label}",
+                                oninput: move |evt| label.set(evt.value()),
+                            }
+                        }
+                        div {
+                            label { class: "block text-xs uppercase text-gray-500 mb-1", "Expires in (days)" }
+                            input {
+                                class: "w-full p-3 rounded-lg bg-gray-900 border border-gray-800 text-white",
+                                r#type: "number",
+                                min: "1",
+                                max: "365",
+                                value: "{days}",
+                                oninput: move |evt| days.set(evt.value().parse().unwrap_or(14)),
+                            }
+                        }
+                        button {
+                            class: "bg-blue-600 hover:bg-blue-700 text-white font-bold py-3 px-6 rounded-xl transition-all",
+                            r#type: "submit",
+                            "Create link"
+                        }
+                    }
+                    if let Some(e) = error() {
+                        p { class: "text-red-400 mt-4", "{e}" }
+                    }
+                }
+
+                SectionCard {
+                    title: "Existing Links".to_string(),
+                    icon: "fa-list".to_string(),
+                    match links() {
+                        Some(Ok(links)) if links.is_empty() => rsx! {
+                            p { class: "text-gray-500", "No share links yet." }
+                        },
+                        Some(Ok(list)) => rsx! {
+                            div { class: "space-y-4",
+                                for link in list {
+                                    div {
+                                        key: "{link.id}",
+                                        class: "p-4 rounded-lg bg-gray-900 border border-gray-800 flex flex-col md:flex-row md:items-center gap-4",
+                                        div { class: "flex-grow overflow-hidden",
+                                            p { class: "font-bold text-white",
+                                                "{link.label} "
+                                                span { class: "text-blue-500 font-medium", "({link.variant})" }
+                                            }
+                                            a { class: "text-sm text-gray-400 break-all", href: "{link.path()}", "{link.path()}" }
+                                            p { class: "text-xs text-gray-500 mt-1",
+                                                "{link.views} views · expires {format_timestamp(link.expires_at)} · "
+                                                button {
+                                                    class: "hover:text-blue-400 transition-colors",
+                                                    onclick: {
+                                                        let id = link.id.clone();
+                                                        move |_| {
+                                                            let open = expanded() == Some(id.clone());
+                                                            expanded.set(if open { None } else { Some(id.clone()) });
+                                                        }
+                                                    },
+                                                    if expanded() == Some(link.id.clone()) { "Hide views" } else { "Show views" }
+                                                }
+                                            }
+                                            if expanded() == Some(link.id.clone()) {
+                                                ShareLinkViews { id: link.id.clone() }
+                                            }
+                                        }
+                                        if link.revoked {
+                                            span { class: "text-xs uppercase text-red-400 font-bold", "Revoked" }
+                                        } else {
+                                            button {
+                                                class: "text-sm text-gray-400 hover:text-red-400 transition-colors",
+                                                onclick: {
+                                                    let id = link.id.clone();
+                                                    move |_| {
+                                                        let id = id.clone();
+                                                        spawn(async move {
+                                                            if revoke_share_link(id).await.is_ok() {
+                                                                links.restart();
+                                                            }
+                                                        });
+                                                    }
+                                                },
+                                                "Revoke"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "text-red-400", "{e}" }
+                        },
+                        None => rsx! {
+                            LoadingSpinner { message: "Loading links...".to_string() }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::components::{LoadingSpinner, PostList, Seo};
+use crate::models::archive::month_label;
+use crate::Route;
+use dioxus::prelude::*;
+
+#[component]
+pub fn BlogArchive(year: i32, month: u32) -> Element {
+    let posts = use_server_future(use_reactive!(|year, month| {
+        crate::models::archive::get_month_archive(year, month)
+    }))?;
+    let back = rsx! {
+        Link {
+            to: Route::Blog {},
+            class: "text-sm text-blue-500 hover:text-white transition-colors",
+            i { class: "fas fa-arrow-left mr-2" }
+            "All posts"
+        }
+    };
+    let Some(label) = month_label(year, month) else {
+        return rsx! {
+            div { class: "p-8",
+                {back}
+                h1 { class: "text-4xl font-bold mt-6 mb-4 text-blue-500", "Archive not found" }
+                p { class: "text-gray-400", "There is no month \"{year}/{month}\"." }
+            }
+        };
+    };
+
+    rsx! {
+        Seo { title: "Posts from {label}", description: "Posts from {label}" }
+        div { class: "p-8",
+            {back}
+            h1 { class: "text-4xl font-bold mt-6 mb-8 text-blue-500", "{label}" }
+            match posts() {
+                Some(Ok(posts)) if posts.is_empty() => rsx! {
+                    p { class: "text-gray-400", "No posts from {label}." }
+                },
+                Some(Ok(posts)) => rsx! {
+                    PostList { posts }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: "text-red-400", "Failed to load posts: {e}" }
+                },
+                None => rsx! {
+                    LoadingSpinner { message: "Loading posts...".to_string() }
+                },
+            }
+        }
+    }
+}
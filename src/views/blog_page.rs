@@ -0,0 +1,78 @@
+use crate::components::{LoadingSpinner, Pager, PostList, Seo, TagCloud};
+use crate::models::seo::SeoDefaults;
+use crate::Route;
+use dioxus::prelude::*;
+
+fn page_route(page: usize) -> Route {
+    if page == 1 {
+        Route::Blog {}
+    } else {
+        Route::BlogPage { page }
+    }
+}
+
+#[component]
+pub fn BlogPage(page: usize) -> Element {
+    let index = use_server_future(use_reactive!(
+        |page| crate::models::archive::get_blog_index(page)
+    ))?;
+
+    let defaults = use_context::<SeoDefaults>();
+    let title = if page == 1 {
+        "Blog".to_string()
+    } else {
+        format!("Blog · page {}", page)
+    };
+
+    rsx! {
+        Seo { title, description: "Posts by {defaults.site_name}" }
+        div { class: "p-8",
+            h1 { class: "text-4xl font-bold mb-8 text-blue-500", "Blog" }
+            match index() {
+                Some(Ok(index)) if index.page.posts.is_empty() => rsx! {
+                    p { class: "text-gray-400", "No posts yet." }
+                },
+                Some(Ok(index)) => rsx! {
+                    div { class: "grid grid-cols-1 lg:grid-cols-4 gap-10",
+                        div { class: "lg:col-span-3",
+                            PostList { posts: index.page.posts.clone() }
+                            Pager {
+                                newer: index.page.prev().map(page_route),
+                                newer_label: "Newer posts",
+                                older: index.page.next().map(page_route),
+                                older_label: "Older posts",
+                            }
+                        }
+                        aside { class: "space-y-10",
+                            div {
+                                h2 { class: "text-sm font-bold uppercase tracking-widest text-gray-500 mb-4", "Tags" }
+                                TagCloud { tags: index.tags.clone() }
+                            }
+                            div {
+                                h2 { class: "text-sm font-bold uppercase tracking-widest text-gray-500 mb-4", "Archive" }
+                                ul { class: "space-y-2",
+                                    for month in index.months.iter() {
+                                        li { key: "{month.year}-{month.month}",
+                                            Link {
+                                                to: Route::BlogArchive { year: month.year, month: month.month },
+                                                class: "text-gray-400 hover:text-blue-500 transition-colors",
+                                                "{month.label()}"
+                                                span { class: "ml-2 text-xs text-gray-600", "{month.count}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(_)) => rsx! {
+                    p { class: "text-gray-400", "There is no page {page}." }
+                },
+                None => rsx! {
+                    LoadingSpinner { message: "Loading posts...".to_string() }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::components::{
+    CommentSection, JsonLd, LoadingSpinner, Pager, PostBody, ReactionBar, RelatedPosts, Seo,
+    SeriesNav, SyndicationLinks, TableOfContents, TagChip, WebmentionList,
+};
+use crate::models::blog::PostStatus;
+use crate::models::seo::{og_image_path, OgKind, PageKind};
+use crate::models::site::SiteUrl;
+use crate::models::structured_data::{blog_posting, Article};
+use crate::Route;
+use dioxus::prelude::*;
+
+#[component]
+pub fn BlogPost(slug: String) -> Element {
+    let site_url = use_context::<SiteUrl>();
+    let post = use_server_future(use_reactive!(|slug| crate::models::blog::get_post(slug)))?;
+    let author = use_server_future(|| crate::models::cv::get_cv(None))?;
+    let neighbours =
+        use_server_future(use_reactive!(
+            |slug| crate::models::archive::get_neighbours(slug)
+        ))?;
+    let series = use_server_future(use_reactive!(|slug| crate::models::series::get_series(
+        slug
+    )))?;
+    let related = use_server_future(use_reactive!(|slug| {
+        crate::models::related::get_related_posts(slug)
+    }))?;
+    let mentions = use_server_future(use_reactive!(|slug| {
+        crate::models::webmentions::get_webmentions(slug)
+    }))?;
+
+    match post() {
+        Some(Ok(post)) => {
+            let meta = &post.meta;
+            let status = meta.status_at(chrono::Utc::now());
+            let status_label = match (status, meta.publish_at) {
+                (PostStatus::Scheduled, Some(at)) => {
+                    format!("Scheduled · {}", at.format("%B %-d, %Y %H:%M UTC"))
+                }
+                _ => status.label().to_string(),
+            };
+            let canonical = meta.canonical(&site_url.0);
+            let article = Article {
+                title: meta.title.clone(),
+                description: meta.description.clone(),
+                url: canonical.clone(),
+                date_published: meta.date.to_string(),
+                date_modified: None,
+                tags: meta.tags.clone(),
+                syndication: meta.syndication.clone(),
+            };
+
+            rsx! {
+                Seo {
+                    title: meta.title.clone(),
+                    description: meta.description.clone(),
+                    canonical: canonical.clone(),
+                    image: meta
+                        .image
+                        .clone()
+                        .unwrap_or_else(|| og_image_path(OgKind::Post, &meta.slug)),
+                    kind: PageKind::Article,
+                    published: meta.date,
+                    tags: meta.tags.clone(),
+                    noindex: status != PostStatus::Published,
+                }
+                document::Link { rel: "webmention", href: "/webmention" }
+                if let Some(Ok(cv)) = author() {
+                    JsonLd { data: blog_posting(&article, &cv.basics, &site_url.0) }
+                }
+
+                div { class: "xl:flex xl:justify-center",
+                    article { class: "p-8 max-w-3xl w-full mx-auto xl:mx-0",
+                        Link {
+                            to: Route::Blog {},
+                            class: "text-sm text-blue-500 hover:text-white transition-colors",
+                            i { class: "fas fa-arrow-left mr-2" }
+                            "All posts"
+                        }
+                        header { class: "mt-6 mb-10",
+                            h1 { class: "text-5xl font-black mb-4 leading-tight", "{meta.title}" }
+                            div { class: "flex flex-wrap items-center gap-3 text-gray-500 text-sm",
+                                time { datetime: "{meta.date}", "{meta.date.format(\"%B %-d, %Y\")}" }
+                                span { "{meta.reading_minutes()} min read" }
+                                if status != PostStatus::Published {
+                                    span { class: "px-2 py-0.5 rounded bg-amber-500/10 border border-amber-500/40 text-amber-400 text-xs font-bold uppercase tracking-widest",
+                                        "{status_label}"
+                                    }
+                                }
+                                for tag in meta.tags.iter() {
+                                    TagChip { key: "{tag}", name: tag.clone() }
+                                }
+                            }
+                        }
+                        if let Some(Ok(Some(series))) = series() {
+                            SeriesNav { series }
+                        }
+                        PostBody { blocks: post.blocks.clone() }
+                        SyndicationLinks {
+                            canonical_url: meta.canonical_url.clone(),
+                            links: meta.syndication.clone(),
+                        }
+                        ReactionBar { key: "{slug}", slug: slug.clone() }
+                        if let Some(Ok(posts)) = related() {
+                            RelatedPosts { posts }
+                        }
+                        if let Some(Ok(mentions)) = mentions() {
+                            WebmentionList { mentions }
+                        }
+                        CommentSection { key: "{slug}", slug: slug.clone() }
+                        if let Some(Ok(neighbours)) = neighbours() {
+                            Pager {
+                                newer: neighbours.newer.as_ref().map(|p| Route::BlogPost { slug: p.slug.clone() }),
+                                newer_label: neighbours.newer.as_ref().map(|p| p.title.clone()).unwrap_or_default(),
+                                older: neighbours.older.as_ref().map(|p| Route::BlogPost { slug: p.slug.clone() }),
+                                older_label: neighbours.older.as_ref().map(|p| p.title.clone()).unwrap_or_default(),
+                            }
+                        }
+                    }
+                    if !post.toc.is_empty() {
+                        aside { class: "hidden xl:block w-64 shrink-0 py-8",
+                            div { class: "sticky top-8 max-h-[calc(100vh-4rem)] overflow-y-auto",
+                                TableOfContents { key: "{slug}", entries: post.toc.clone() }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Some(Err(_)) => {
+            rsx! {
+                div { class: "p-8",
+                    h1 { class: "text-4xl font-bold mb-4 text-blue-500", "Post not found" }
+                    p { class: "text-gray-400", "There is no post at \"/blog/{slug}\"." }
+                }
+            }
+        }
+        None => {
+            rsx! {
+                LoadingSpinner { message: "Loading post...".to_string() }
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::components::{LoadingSpinner, PostList, Seo};
+use crate::Route;
+use dioxus::prelude::*;
+
+#[component]
+pub fn BlogTag(tag: String) -> Element {
+    let archive = use_server_future(use_reactive!(|tag| crate::models::tags::get_tag_archive(
+        tag
+    )))?;
+
+    rsx! {
+        div { class: "p-8",
+            Link {
+                to: Route::Blog {},
+                class: "text-sm text-blue-500 hover:text-white transition-colors",
+                i { class: "fas fa-arrow-left mr-2" }
+                "All posts"
+            }
+            match archive() {
+                Some(Ok(archive)) => rsx! {
+                    Seo {
+                        title: "#{archive.tag.name}",
+                        description: "Posts tagged {archive.tag.name}",
+                    }
+                    document::Link {
+                        rel: "alternate",
+                        r#type: "application/rss+xml",
+                        title: "#{archive.tag.name} (RSS)",
+                        href: "/blog/tag/{archive.tag.slug}/feed.xml",
+                    }
+                    document::Link {
+                        rel: "alternate",
+                        r#type: "application/atom+xml",
+                        title: "#{archive.tag.name} (Atom)",
+                        href: "/blog/tag/{archive.tag.slug}/atom.xml",
+                    }
+                    h1 { class: "text-4xl font-bold mt-6 mb-2 text-blue-500", "#{archive.tag.name}" }
+                    p { class: "text-gray-500 mb-8",
+                        if archive.tag.count == 1 {
+                            "1 post"
+                        } else {
+                            "{archive.tag.count} posts"
+                        }
+                    }
+                    PostList { posts: archive.posts }
+                },
+                Some(Err(_)) => rsx! {
+                    h1 { class: "text-4xl font-bold mt-6 mb-4 text-blue-500", "Tag not found" }
+                    p { class: "text-gray-400", "No posts are tagged \"{tag}\"." }
+                },
+                None => rsx! {
+                    LoadingSpinner { message: "Loading posts...".to_string() }
+                },
+            }
+        }
+    }
+}
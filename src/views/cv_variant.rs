@@ -0,0 +1,67 @@
+use crate::components::{
+    ExperienceSection, IntroductionSection, JsonLd, LoadingSpinner, ProjectsSection, Seo,
+    SkillsSection,
+};
+use crate::models::seo::{og_image_path, OgKind, PageKind};
+use crate::models::site::SiteUrl;
+use crate::models::structured_data::profile_page;
+use dioxus::prelude::*;
+
+#[component]
+pub fn CvVariant(variant: String) -> Element {
+    let site_url = use_context::<SiteUrl>();
+    let cv = use_server_future(use_reactive!(|variant| crate::models::cv::get_cv(Some(
+        variant
+    ))))?;
+
+    match cv() {
+        Some(Ok(cv)) => {
+            rsx! {
+                Seo {
+                    title: cv.basics.label.clone(),
+                    description: cv.basics.summary.clone(),
+                    image: og_image_path(OgKind::Cv, &variant),
+                    kind: PageKind::Profile,
+                }
+                JsonLd { data: profile_page(&cv, &site_url.0, &site_url.absolute(&format!("/cv/{variant}"))) }
+                div { class: "w-full",
+                    IntroductionSection { basics: cv.basics.clone() }
+
+                    div { class: "px-12 flex gap-4 max-w-4xl mx-auto",
+                        a {
+                            href: "/cv/{variant}/resume.json",
+                            download: "{variant}-resume.json",
+                            class: "flex items-center gap-2 text-blue-500 font-medium hover:text-white transition-colors",
+                            i { class: "fas fa-file-code" }
+                            "JSON Resume"
+                        }
+                        a {
+                            href: "/cv/{variant}/cv.md",
+                            download: "{variant}-cv.md",
+                            class: "flex items-center gap-2 text-blue-500 font-medium hover:text-white transition-colors",
+                            i { class: "fas fa-file-lines" }
+                            "Markdown"
+                        }
+                    }
+
+                    ExperienceSection { experience: cv.experience.clone() }
+                    SkillsSection { groups: cv.skills_by_category() }
+                    ProjectsSection { projects: cv.projects.clone() }
+                }
+            }
+        }
+        Some(Err(_)) => {
+            rsx! {
+                div { class: "p-8",
+                    h1 { class: "text-4xl font-bold mb-4 text-blue-500", "CV not found" }
+                    p { class: "text-gray-400", "There is no CV variant named \"{variant}\"." }
+                }
+            }
+        }
+        None => {
+            rsx! {
+                LoadingSpinner { message: "Loading CV...".to_string() }
+            }
+        }
+    }
+}
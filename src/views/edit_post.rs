@@ -0,0 +1,390 @@
+use crate::components::{LoadingSpinner, PostBody, Seo};
+use crate::models::blog::PostStatus;
+use crate::models::editor::{
+    autosave_post, discard_autosave, get_post_editor, preview_post, save_post, EditorState,
+    PostDraft, PostOrigin,
+};
+use crate::models::media::{upload_image, ALLOWED_TYPES};
+use crate::Route;
+use dioxus::prelude::*;
+
+const INPUT: &str = "w-full p-3 rounded-lg bg-gray-900 border border-gray-800 text-white";
+const LABEL: &str = "block text-xs uppercase text-gray-500 mb-1";
+const BODY_ID: &str = "post-body";
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Waits in the browser. Resources call this before hitting the server, so
+/// a burst of keystrokes restarts the wait instead of sending requests.
+async fn pause(ms: u32) {
+    let _ = document::eval(&format!(
+        "await new Promise(resolve => setTimeout(resolve, {})); return true;",
+        ms
+    ))
+    .await;
+}
+
+#[component]
+pub fn EditPost(slug: String) -> Element {
+    let state = use_resource(use_reactive!(|slug| get_post_editor(slug)));
+
+    rsx! {
+        Seo { title: "Editing {slug}" }
+        match state() {
+            Some(Ok(state)) => rsx! {
+                PostEditor { key: "{slug}", state }
+            },
+            Some(Err(e)) => rsx! {
+                div { class: "p-8",
+                    h1 { class: "text-4xl font-bold mb-4 text-blue-500", "Cannot edit this post" }
+                    p { class: "text-red-400", "{e}" }
+                }
+            },
+            None => rsx! {
+                LoadingSpinner { message: "Loading editor...".to_string() }
+            },
+        }
+    }
+}
+
+#[component]
+fn PostEditor(state: EditorState) -> Element {
+    let mut draft = use_signal(|| state.draft.clone());
+    // The last version stored as the post, to tell whether there are changes
+    let mut saved = use_signal(|| state.draft.clone());
+    let mut pending_autosave = use_signal(|| {
+        state
+            .autosave
+            .clone()
+            .filter(|autosave| autosave.draft != state.draft)
+    });
+    let mut origin = use_signal(|| state.origin);
+    let mut message = use_signal(|| match state.updated_at {
+        Some(at) => format!("Last saved {}", format_timestamp(at)),
+        None => String::new(),
+    });
+    let mut error = use_signal(|| None::<String>);
+    let mut uploading = use_signal(|| 0usize);
+
+    let _autosave = use_resource(move || async move {
+        let current = draft();
+        pause(1500).await;
+        if current == *saved.peek() || pending_autosave.peek().is_some() {
+            return;
+        }
+        match autosave_post(current).await {
+            Ok(at) => message.set(format!("Autosaved {}", format_timestamp(at))),
+            Err(e) => error.set(Some(e.to_string())),
+        }
+    });
+
+    let preview = use_resource(move || async move {
+        let current = draft();
+        pause(300).await;
+        preview_post(current).await
+    });
+
+    let save = move |next: PostDraft| {
+        spawn(async move {
+            match save_post(next.clone()).await {
+                Ok(meta) => {
+                    error.set(None);
+                    message.set(format!("Saved as {}", meta.status.label().to_lowercase()));
+                    origin.set(Some(PostOrigin::Saved));
+                    saved.set(next.clone());
+                    draft.set(next);
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    // Uploads are inserted where the cursor is once they are stored
+    let upload = move |evt: FormEvent| {
+        for file in evt.files() {
+            let name = file.name();
+            let alt = name
+                .rsplit_once('.')
+                .map_or(name.as_str(), |(stem, _)| stem)
+                .to_string();
+            spawn(async move {
+                *uploading.write() += 1;
+                match upload_image(file.into()).await {
+                    Ok(image) => {
+                        let cursor = document::eval(&format!(
+                            "return document.getElementById('{}').selectionStart;",
+                            BODY_ID
+                        ))
+                        .join::<usize>()
+                        .await
+                        .ok();
+                        draft.write().insert_block(cursor, &image.markdown(&alt));
+                        error.set(None);
+                    }
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+                *uploading.write() -= 1;
+            });
+        }
+    };
+
+    let current = draft();
+    let slug = current.slug.clone();
+    let accept = ALLOWED_TYPES.join(",");
+    let dirty = current != saved();
+
+    rsx! {
+        div { class: "p-8",
+            div { class: "flex flex-col md:flex-row md:items-center gap-4 mb-6",
+                div { class: "flex-grow",
+                    Link {
+                        to: Route::AdminPosts {},
+                        class: "text-sm text-blue-500 hover:text-white transition-colors",
+                        i { class: "fas fa-arrow-left mr-2" }
+                        "All posts"
+                    }
+                    h1 { class: "text-3xl font-bold mt-2 text-white font-mono", "{slug}" }
+                    p { class: "text-xs text-gray-500 mt-1",
+                        if dirty {
+                            "Unsaved changes"
+                        } else {
+                            "No changes"
+                        }
+                        if !message().is_empty() {
+                            " · {message}"
+                        }
+                        if origin() == Some(PostOrigin::File) {
+                            " · saving replaces content/posts/{slug}.md"
+                        }
+                    }
+                }
+                if origin().is_some() {
+                    Link {
+                        to: Route::PostHistory { slug: slug.clone() },
+                        class: "text-sm text-gray-400 hover:text-white",
+                        "History"
+                    }
+                    Link {
+                        to: Route::BlogPost { slug: slug.clone() },
+                        class: "text-sm text-gray-400 hover:text-white",
+                        "View"
+                    }
+                }
+                button {
+                    class: "bg-gray-800 hover:bg-gray-700 text-white font-bold py-2 px-5 rounded-xl transition-all",
+                    onclick: move |_| save(draft()),
+                    "Save"
+                }
+                if saved().status == PostStatus::Published {
+                    button {
+                        class: "bg-gray-800 hover:bg-red-900 text-white font-bold py-2 px-5 rounded-xl transition-all",
+                        onclick: move |_| save(draft().with_status(PostStatus::Draft)),
+                        "Unpublish"
+                    }
+                } else {
+                    button {
+                        class: "bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-5 rounded-xl transition-all",
+                        onclick: move |_| save(draft().with_status(PostStatus::Published)),
+                        "Publish"
+                    }
+                }
+            }
+
+            if let Some(e) = error() {
+                p { class: "text-red-400 mb-4", "{e}" }
+            }
+            if let Some(autosave) = pending_autosave() {
+                div { class: "p-4 mb-6 rounded-lg border border-amber-500/40 bg-amber-500/10 text-amber-300 flex flex-wrap items-center gap-4",
+                    span { class: "flex-grow", "There are unsaved changes from {format_timestamp(autosave.saved_at)}." }
+                    button {
+                        class: "font-bold hover:text-white",
+                        onclick: move |_| {
+                            draft.set(autosave.draft.clone());
+                            pending_autosave.set(None);
+                        },
+                        "Restore"
+                    }
+                    button {
+                        class: "hover:text-white",
+                        onclick: move |_| {
+                            let slug = draft.peek().slug.clone();
+                            pending_autosave.set(None);
+                            spawn(async move {
+                                let _ = discard_autosave(slug).await;
+                            });
+                        },
+                        "Discard"
+                    }
+                }
+            }
+
+            div { class: "grid grid-cols-1 xl:grid-cols-2 gap-8",
+                div { class: "space-y-4",
+                    div {
+                        label { class: LABEL, "Title" }
+                        input {
+                            class: INPUT,
+                            value: "{current.title}",
+                            oninput: move |evt| draft.write().title = evt.value(),
+                        }
+                    }
+                    div { class: "grid grid-cols-1 md:grid-cols-3 gap-4",
+                        div {
+                            label { class: LABEL, "Date" }
+                            input {
+                                class: INPUT,
+                                r#type: "date",
+                                value: "{current.date}",
+                                oninput: move |evt| draft.write().date = evt.value(),
+                            }
+                        }
+                        div {
+                            label { class: LABEL, "Status" }
+                            select {
+                                class: INPUT,
+                                onchange: move |evt| {
+                                    if let Some(status) = PostStatus::parse(&evt.value()) {
+                                        draft.write().status = status;
+                                    }
+                                },
+                                for status in PostStatus::ALL {
+                                    option {
+                                        value: status.as_str(),
+                                        selected: status == current.status,
+                                        "{status.label()}"
+                                    }
+                                }
+                            }
+                        }
+                        if current.status == PostStatus::Scheduled {
+                            div {
+                                label { class: LABEL, "Publish at (UTC)" }
+                                input {
+                                    class: INPUT,
+                                    r#type: "datetime-local",
+                                    value: "{current.publish_at}",
+                                    oninput: move |evt| draft.write().publish_at = evt.value(),
+                                }
+                            }
+                        }
+                    }
+                    div {
+                        label { class: LABEL, "Description" }
+                        input {
+                            class: INPUT,
+                            value: "{current.description}",
+                            oninput: move |evt| draft.write().description = evt.value(),
+                        }
+                    }
+                    div {
+                        label { class: LABEL, "Tags (comma separated)" }
+                        input {
+                            class: INPUT,
+                            value: "{current.tags}",
+                            oninput: move |evt| draft.write().tags = evt.value(),
+                        }
+                    }
+                    div { class: "grid grid-cols-1 md:grid-cols-3 gap-4",
+                        div { class: "md:col-span-2",
+                            label { class: LABEL, "Series" }
+                            input {
+                                class: INPUT,
+                                value: "{current.series}",
+                                oninput: move |evt| draft.write().series = evt.value(),
+                            }
+                        }
+                        div {
+                            label { class: LABEL, "Part" }
+                            input {
+                                class: INPUT,
+                                r#type: "number",
+                                min: "1",
+                                value: "{current.series_part}",
+                                oninput: move |evt| draft.write().series_part = evt.value(),
+                            }
+                        }
+                    }
+                    div {
+                        label { class: LABEL, "Canonical URL (if first published elsewhere)" }
+                        input {
+                            class: INPUT,
+                            r#type: "url",
+                            placeholder: "https://",
+                            value: "{current.canonical_url}",
+                            oninput: move |evt| draft.write().canonical_url = evt.value(),
+                        }
+                    }
+                    div {
+                        label { class: LABEL, "Preview image (for link cards)" }
+                        input {
+                            class: INPUT,
+                            placeholder: "/media/... or https://",
+                            value: "{current.image}",
+                            oninput: move |evt| draft.write().image = evt.value(),
+                        }
+                    }
+                    div {
+                        label { class: LABEL, "Syndicated to (one URL per line)" }
+                        textarea {
+                            class: "{INPUT} font-mono text-sm h-20 resize-y",
+                            value: "{current.syndication}",
+                            oninput: move |evt| draft.write().syndication = evt.value(),
+                        }
+                    }
+                    div {
+                        div { class: "flex items-baseline justify-between",
+                            label { class: LABEL, "Markdown" }
+                            label { class: "text-xs text-blue-500 hover:text-white cursor-pointer transition-colors",
+                                i { class: "fas fa-image mr-1" }
+                                if uploading() > 0 {
+                                    "Uploading..."
+                                } else {
+                                    "Insert image"
+                                }
+                                input {
+                                    r#type: "file",
+                                    class: "hidden",
+                                    accept: "{accept}",
+                                    multiple: true,
+                                    onchange: upload,
+                                }
+                            }
+                        }
+                        textarea {
+                            id: BODY_ID,
+                            class: "{INPUT} font-mono text-sm h-[60vh] resize-y",
+                            spellcheck: "true",
+                            value: "{current.body}",
+                            oninput: move |evt| draft.write().body = evt.value(),
+                        }
+                    }
+                }
+
+                div { class: "rounded-xl border border-gray-800 bg-[#0a0c10] p-8 overflow-y-auto xl:max-h-[calc(100vh-12rem)] xl:sticky xl:top-8",
+                    h1 { class: "text-4xl font-black mb-6 leading-tight", "{current.title}" }
+                    match preview() {
+                        Some(Ok(preview)) => rsx! {
+                            if let Some(problem) = preview.error {
+                                p { class: "text-sm text-amber-400 mb-4",
+                                    i { class: "fas fa-triangle-exclamation mr-2" }
+                                    "{problem}"
+                                }
+                            }
+                            PostBody { blocks: preview.blocks }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "text-red-400", "{e}" }
+                        },
+                        None => rsx! {
+                            p { class: "text-gray-500", "Rendering..." }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
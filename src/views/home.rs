@@ -1,12 +1,41 @@
-use crate::components::{AboutSection, IntroductionSection};
+use crate::components::{
+    AboutSection, ContactCard, ExperienceSection, IntroductionSection, JsonLd, LoadingSpinner,
+    ProjectsSection, Seo, SkillsSection,
+};
+use crate::models::seo::PageKind;
+use crate::models::site::SiteUrl;
+use crate::models::structured_data::profile_page;
 use dioxus::prelude::*;
 
 #[component]
 pub fn Home() -> Element {
-    rsx! {
-        div { class: "w-full",
-            IntroductionSection {}
-            AboutSection {}
+    let site_url = use_context::<SiteUrl>();
+    let cv = use_server_future(|| crate::models::cv::get_cv(None))?;
+
+    match cv() {
+        Some(Ok(cv)) => {
+            rsx! {
+                Seo { kind: PageKind::Profile }
+                JsonLd { data: profile_page(&cv, &site_url.0, &site_url.absolute("/")) }
+                div { class: "w-full",
+                    IntroductionSection { basics: cv.basics.clone() }
+                    AboutSection {}
+                    ExperienceSection { experience: cv.experience.clone() }
+                    SkillsSection { groups: cv.skills_by_category() }
+                    ProjectsSection { projects: cv.projects.clone() }
+                    ContactCard { email: cv.basics.email.clone() }
+                }
+            }
+        }
+        Some(Err(e)) => {
+            rsx! {
+                div { class: "p-8 text-red-400", "Failed to load CV: {e}" }
+            }
+        }
+        None => {
+            rsx! {
+                LoadingSpinner { message: "Loading CV...".to_string() }
+            }
         }
     }
 }
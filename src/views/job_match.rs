@@ -0,0 +1,120 @@
+use crate::components::{SectionCard, Seo, SkillMatchList};
+use crate::models::job_match::{match_job_description, MatchReport};
+use crate::Route;
+use dioxus::prelude::*;
+
+#[component]
+pub fn JobMatch() -> Element {
+    let mut description = use_signal(String::new);
+    let mut report = use_signal(|| None::<MatchReport>);
+    let mut error = use_signal(|| None::<String>);
+
+    let analyze = move |evt: FormEvent| {
+        evt.prevent_default();
+        spawn(async move {
+            match match_job_description(description()).await {
+                Ok(result) => {
+                    error.set(None);
+                    report.set(Some(result));
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        Seo { title: "Job Match" }
+        div { class: "p-8 max-w-5xl",
+            h1 { class: "text-4xl font-bold mb-8 text-blue-500", "Job Match" }
+
+            div { class: "grid gap-8",
+                SectionCard {
+                    title: "Job Description".to_string(),
+                    icon: "fa-briefcase".to_string(),
+                    form { class: "flex flex-col gap-4", onsubmit: analyze,
+                        textarea {
+                            class: "w-full h-64 p-4 rounded-lg bg-gray-900 border border-gray-800 text-gray-200 font-mono text-sm",
+                            placeholder: "Paste the job posting here...",
+                            value: "{description}",
+                            oninput: move |evt| description.set(evt.value()),
+                        }
+                        button {
+                            class: "self-start bg-blue-600 hover:bg-blue-700 text-white font-bold py-3 px-6 rounded-xl transition-all",
+                            r#type: "submit",
+                            "Analyze"
+                        }
+                    }
+                    if let Some(e) = error() {
+                        p { class: "text-red-400 mt-4", "{e}" }
+                    }
+                }
+
+                if let Some(report) = report() {
+                    SectionCard {
+                        title: "Result".to_string(),
+                        icon: "fa-chart-simple".to_string(),
+                        div { class: "flex items-baseline gap-4 mb-8",
+                            span { class: "text-6xl font-black text-white", "{report.score}" }
+                            span { class: "text-gray-500", "/ 100 match" }
+                        }
+
+                        if let Some(best) = report.suggested_variant() {
+                            p { class: "text-gray-300 mb-8",
+                                "Suggested CV: "
+                                if best.public {
+                                    Link {
+                                        to: Route::CvVariant { variant: best.slug.clone() },
+                                        class: "text-blue-500 font-bold hover:text-blue-400",
+                                        "{best.name}"
+                                    }
+                                } else {
+                                    span { class: "text-white font-bold", "{best.name}" }
+                                    span { class: "text-gray-500", " (private, " }
+                                    Link {
+                                        to: Route::AdminShareLinks {},
+                                        class: "text-blue-500 hover:text-blue-400",
+                                        "create a share link"
+                                    }
+                                    span { class: "text-gray-500", ")" }
+                                }
+                                span { class: "text-gray-500", " ({best.score}/100)" }
+                            }
+                        }
+
+                        div { class: "grid grid-cols-1 md:grid-cols-3 gap-8",
+                            SkillMatchList {
+                                title: "Matched".to_string(),
+                                icon: "fa-circle-check".to_string(),
+                                color: "text-green-400".to_string(),
+                                skills: report.matched.clone(),
+                            }
+                            SkillMatchList {
+                                title: "Partial".to_string(),
+                                icon: "fa-circle-half-stroke".to_string(),
+                                color: "text-yellow-400".to_string(),
+                                skills: report.partial.clone(),
+                            }
+                            SkillMatchList {
+                                title: "Missing".to_string(),
+                                icon: "fa-circle-xmark".to_string(),
+                                color: "text-red-400".to_string(),
+                                skills: report.missing.clone(),
+                            }
+                        }
+
+                        if report.variants.len() > 1 {
+                            div { class: "mt-8 pt-6 border-t border-gray-700",
+                                h3 { class: "text-sm uppercase tracking-widest text-gray-500 mb-3", "All variants" }
+                                ul { class: "space-y-1 text-gray-400",
+                                    for variant in report.variants.iter() {
+                                        li { key: "{variant.slug}", "{variant.name}: {variant.score}/100" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -12,3 +12,42 @@ pub use callback::AuthCallback;
 
 mod blog;
 pub use blog::Blog;
+
+mod blog_archive;
+pub use blog_archive::BlogArchive;
+
+mod blog_page;
+pub use blog_page::BlogPage;
+
+mod blog_post;
+pub use blog_post::BlogPost;
+
+mod blog_tag;
+pub use blog_tag::BlogTag;
+
+mod search;
+pub use search::Search;
+
+mod cv_variant;
+pub use cv_variant::CvVariant;
+
+mod shared_cv;
+pub use shared_cv::SharedCv;
+
+mod admin_share_links;
+pub use admin_share_links::AdminShareLinks;
+
+mod job_match;
+pub use job_match::JobMatch;
+
+mod admin_posts;
+pub use admin_posts::AdminPosts;
+
+mod edit_post;
+pub use edit_post::EditPost;
+
+mod post_history;
+pub use post_history::PostHistory;
+
+mod admin_comments;
+pub use admin_comments::AdminComments;
@@ -1,4 +1,4 @@
-use crate::components::{LoginButton, Logo, NavItem, UserProfileCard};
+use crate::components::{LoginButton, Logo, NavItem, SearchBox, UserProfileCard};
 use crate::Route;
 use dioxus::prelude::*;
 
@@ -51,6 +51,10 @@ pub fn Navbar() -> Element {
 
                 Logo {}
 
+                div { class: "px-4 mb-4",
+                    SearchBox { onsubmit: move |_| is_menu_open.set(false) }
+                }
+
                 div { class: "flex-grow px-4 space-y-2",
                     NavItem {
                         to: Route::Home {},
@@ -97,6 +101,9 @@ pub fn Navbar() -> Element {
                             email: user.email,
                             on_logout: move |_| {
                                 auth_user.set(None);
+                                spawn(async move {
+                                    let _ = crate::models::logout().await;
+                                });
                                 #[cfg(feature = "web")]
                                 {
                                     if let Some(window) = web_sys::window() {
@@ -0,0 +1,175 @@
+use crate::components::{LoadingSpinner, SectionCard, Seo};
+use crate::models::revisions::{diff_revisions, list_revisions, restore_revision, DiffKind};
+use crate::Route;
+use dioxus::prelude::*;
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+#[component]
+pub fn PostHistory(slug: String) -> Element {
+    let mut revisions = use_resource(use_reactive!(|slug| list_revisions(slug)));
+    // Revisions being compared; by default the latest change
+    let mut from = use_signal(|| None::<i64>);
+    let mut to = use_signal(|| None::<i64>);
+    let mut message = use_signal(|| None::<Result<String, String>>);
+
+    use_effect(move || {
+        if let Some(Ok(list)) = &*revisions.read() {
+            to.set(list.first().map(|r| r.id));
+            from.set(list.get(1).or(list.first()).map(|r| r.id));
+        }
+    });
+
+    let diff = use_resource(use_reactive!(|slug| async move {
+        match (from(), to()) {
+            (Some(from), Some(to)) => diff_revisions(slug, from, to).await.map(Some),
+            _ => Ok(None),
+        }
+    }));
+
+    let restore = {
+        let slug = slug.clone();
+        move |id: i64| {
+            let slug = slug.clone();
+            spawn(async move {
+                match restore_revision(slug, id).await {
+                    Ok(_) => {
+                        message.set(Some(Ok(format!("Restored revision {}", id))));
+                        revisions.restart();
+                    }
+                    Err(e) => message.set(Some(Err(e.to_string()))),
+                }
+            });
+        }
+    };
+
+    rsx! {
+        Seo { title: "History of {slug}" }
+        div { class: "p-8 max-w-6xl",
+            Link {
+                to: Route::EditPost { slug: slug.clone() },
+                class: "text-sm text-blue-500 hover:text-white transition-colors",
+                i { class: "fas fa-arrow-left mr-2" }
+                "Back to editor"
+            }
+            h1 { class: "text-4xl font-bold mt-2 mb-8 text-blue-500",
+                "History of "
+                span { class: "font-mono", "{slug}" }
+            }
+            match message() {
+                Some(Ok(text)) => rsx! {
+                    p { class: "text-green-400 mb-4", "{text}" }
+                },
+                Some(Err(text)) => rsx! {
+                    p { class: "text-red-400 mb-4", "{text}" }
+                },
+                None => rsx! {},
+            }
+
+            div { class: "grid grid-cols-1 xl:grid-cols-3 gap-8",
+                SectionCard {
+                    title: "Revisions".to_string(),
+                    icon: "fa-clock-rotate-left".to_string(),
+                    match revisions() {
+                        Some(Ok(list)) if list.is_empty() => rsx! {
+                            p { class: "text-gray-500", "This post has not been saved from the editor yet." }
+                        },
+                        Some(Ok(list)) => rsx! {
+                            div { class: "space-y-3",
+                                for (i, revision) in list.into_iter().enumerate() {
+                                    div {
+                                        key: "{revision.id}",
+                                        class: "p-3 rounded-lg bg-gray-900 border border-gray-800",
+                                        p { class: "text-sm text-white",
+                                            "{format_timestamp(revision.created_at)}"
+                                            if i == 0 {
+                                                span { class: "ml-2 text-xs uppercase text-green-400 font-bold", "Current" }
+                                            }
+                                        }
+                                        p { class: "text-xs text-gray-500 break-all",
+                                            "{revision.author} · "
+                                            span { class: "font-mono", title: "{revision.hash}", "{revision.short_hash()}" }
+                                        }
+                                        div { class: "flex gap-3 mt-2 text-xs",
+                                            button {
+                                                class: if from() == Some(revision.id) { "text-red-300 font-bold" } else { "text-gray-400 hover:text-white" },
+                                                onclick: move |_| from.set(Some(revision.id)),
+                                                "From"
+                                            }
+                                            button {
+                                                class: if to() == Some(revision.id) { "text-green-300 font-bold" } else { "text-gray-400 hover:text-white" },
+                                                onclick: move |_| to.set(Some(revision.id)),
+                                                "To"
+                                            }
+                                            if i > 0 {
+                                                button {
+                                                    class: "ml-auto text-gray-400 hover:text-amber-400",
+                                                    onclick: {
+                                                        let restore = restore.clone();
+                                                        move |_| restore(revision.id)
+                                                    },
+                                                    "Restore"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "text-red-400", "{e}" }
+                        },
+                        None => rsx! {
+                            LoadingSpinner { message: "Loading history...".to_string() }
+                        },
+                    }
+                }
+
+                div { class: "xl:col-span-2 rounded-xl border border-gray-800 bg-[#0a0c10] overflow-x-auto",
+                    match diff() {
+                        Some(Ok(Some(lines))) if lines.iter().all(|l| l.kind == DiffKind::Same) => rsx! {
+                            p { class: "p-6 text-gray-500", "No differences." }
+                        },
+                        Some(Ok(Some(lines))) => rsx! {
+                            pre { class: "text-sm font-mono py-4",
+                                for line in lines {
+                                    div {
+                                        class: match line.kind {
+                                            DiffKind::Same => "flex text-gray-400",
+                                            DiffKind::Added => "flex bg-green-500/10 text-green-300",
+                                            DiffKind::Removed => "flex bg-red-500/10 text-red-300",
+                                        },
+                                        span { class: "w-12 shrink-0 text-right pr-2 text-gray-600 select-none",
+                                            "{line.old_line.map(|n| n.to_string()).unwrap_or_default()}"
+                                        }
+                                        span { class: "w-12 shrink-0 text-right pr-2 text-gray-600 select-none",
+                                            "{line.new_line.map(|n| n.to_string()).unwrap_or_default()}"
+                                        }
+                                        span { class: "w-6 shrink-0 text-center select-none",
+                                            match line.kind {
+                                                DiffKind::Same => " ",
+                                                DiffKind::Added => "+",
+                                                DiffKind::Removed => "-",
+                                            }
+                                        }
+                                        span { class: "whitespace-pre pr-4", "{line.text}" }
+                                    }
+                                }
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "p-6 text-red-400", "{e}" }
+                        },
+                        _ => rsx! {
+                            p { class: "p-6 text-gray-500", "Pick two revisions to compare." }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::components::{InfoField, LoginCard, SectionCard};
+use crate::components::{InfoField, LoginCard, SectionCard, Seo};
 use dioxus::prelude::*;
 
 #[component]
@@ -8,6 +8,7 @@ pub fn Profile() -> Element {
     match auth_user() {
         Some(user) => {
             rsx! {
+                Seo { title: "Account Settings" }
                 div { class: "p-8 max-w-4xl",
                     h1 { class: "text-4xl font-bold mb-8 text-blue-500", "Account Settings" }
 
@@ -29,6 +30,28 @@ pub fn Profile() -> Element {
                             }
                         }
 
+                        SectionCard {
+                            title: "Owner Tools".to_string(),
+                            icon: "fa-toolbox".to_string(),
+                            div { class: "flex flex-col gap-3",
+                                Link {
+                                    to: crate::Route::AdminShareLinks {},
+                                    class: "text-blue-500 hover:text-blue-400 font-medium",
+                                    "Manage CV share links"
+                                }
+                                Link {
+                                    to: crate::Route::AdminPosts {},
+                                    class: "text-blue-500 hover:text-blue-400 font-medium",
+                                    "Write and manage blog posts"
+                                }
+                                Link {
+                                    to: crate::Route::JobMatch {},
+                                    class: "text-blue-500 hover:text-blue-400 font-medium",
+                                    "Match CV against a job description"
+                                }
+                            }
+                        }
+
                         SectionCard {
                             title: "Security".to_string(),
                             icon: "fa-shield-halved".to_string(),
@@ -42,6 +65,7 @@ pub fn Profile() -> Element {
         }
         None => {
             rsx! {
+                Seo { title: "Sign in" }
                 LoginCard {
                     on_login: move |_| {
                         spawn(async move {
@@ -0,0 +1,47 @@
+use crate::components::{LoadingSpinner, SearchBox, Seo};
+use dioxus::prelude::*;
+
+#[component]
+pub fn Search(q: String) -> Element {
+    let results = use_server_future(use_reactive!(|q| crate::models::search::search(q)))?;
+
+    rsx! {
+        Seo { title: if q.trim().is_empty() { "Search".to_string() } else { format!("Search: {}", q) } }
+        div { class: "p-8 max-w-3xl",
+            h1 { class: "text-4xl font-bold mb-6 text-blue-500", "Search" }
+            SearchBox { initial: q.clone() }
+            match results() {
+                Some(Ok(_)) if q.trim().is_empty() => rsx! {},
+                Some(Ok(results)) if results.is_empty() => rsx! {
+                    p { class: "text-gray-400 mt-8", "Nothing matches \"{q}\"." }
+                },
+                Some(Ok(results)) => rsx! {
+                    ol { class: "search-results mt-8 space-y-6",
+                        for result in results {
+                            li { key: "{result.path}{result.title_html}",
+                                span { class: "text-xs font-bold uppercase tracking-widest text-gray-500",
+                                    "{result.kind.label()}"
+                                }
+                                a {
+                                    href: "{result.path}",
+                                    class: "block text-xl font-semibold text-white hover:text-blue-400 transition-colors",
+                                    dangerous_inner_html: "{result.title_html}",
+                                }
+                                p {
+                                    class: "text-gray-400 mt-1",
+                                    dangerous_inner_html: "{result.snippet_html}",
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-red-400 mt-8", "Search failed: {e}" }
+                },
+                None => rsx! {
+                    LoadingSpinner { message: "Searching...".to_string() }
+                },
+            }
+        }
+    }
+}
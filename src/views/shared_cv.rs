@@ -0,0 +1,38 @@
+use crate::components::{
+    ExperienceSection, IntroductionSection, LoadingSpinner, ProjectsSection, Seo, SkillsSection,
+};
+use dioxus::prelude::*;
+
+#[component]
+pub fn SharedCv(token: String) -> Element {
+    let cv = use_server_future(use_reactive!(
+        |token| crate::models::share::open_share_link(token)
+    ))?;
+
+    rsx! {
+        Seo { title: "Shared CV" }
+
+        match cv() {
+            Some(Ok(cv)) => rsx! {
+                div { class: "w-full",
+                    IntroductionSection { basics: cv.basics.clone() }
+                    ExperienceSection { experience: cv.experience.clone() }
+                    SkillsSection { groups: cv.skills_by_category() }
+                    ProjectsSection { projects: cv.projects.clone() }
+                }
+            },
+            Some(Err(e)) => rsx! {
+                div { class: "flex items-center justify-center min-h-[60vh]",
+                    div { class: "text-center",
+                        i { class: "fas fa-link-slash text-8xl text-gray-800 mb-6" }
+                        h2 { class: "text-3xl font-bold text-white mb-4", "Link unavailable" }
+                        p { class: "text-gray-500 max-w-xs mx-auto", "{e}" }
+                    }
+                }
+            },
+            None => rsx! {
+                LoadingSpinner { message: "Opening shared CV...".to_string() }
+            },
+        }
+    }
+}
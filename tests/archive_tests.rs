@@ -0,0 +1,94 @@
+use cv::models::archive::{in_month, month_label, months, neighbours, paginate};
+use cv::models::blog::{parse_post, PostMeta};
+
+/// Posts newest first, as the index provides them.
+fn posts() -> Vec<PostMeta> {
+    [
+        ("e", "2026-10-20"),
+        ("d", "2026-10-02"),
+        ("c", "2026-09-15"),
+        ("b", "2025-12-31"),
+        ("a", "2025-01-01"),
+    ]
+    .iter()
+    .map(|(slug, date)| {
+        let source = format!("---\ntitle: Post {}\ndate: {}\n---\n", slug, date);
+        parse_post(slug, &source).unwrap().meta
+    })
+    .collect()
+}
+
+fn slugs(posts: &[PostMeta]) -> Vec<&str> {
+    posts.iter().map(|p| p.slug.as_str()).collect()
+}
+
+#[test]
+fn test_paginate() {
+    let posts = posts();
+
+    let first = paginate(&posts, 1, 2).unwrap();
+    assert_eq!(slugs(&first.posts), vec!["e", "d"]);
+    assert_eq!(first.total_pages, 3);
+    assert_eq!(first.prev(), None);
+    assert_eq!(first.next(), Some(2));
+
+    let last = paginate(&posts, 3, 2).unwrap();
+    assert_eq!(slugs(&last.posts), vec!["a"]);
+    assert_eq!(last.prev(), Some(2));
+    assert_eq!(last.next(), None);
+
+    assert!(paginate(&posts, 0, 2).is_none());
+    assert!(paginate(&posts, 4, 2).is_none());
+}
+
+#[test]
+fn test_paginate_empty_and_zero_size() {
+    let empty = paginate(&[], 1, 10).unwrap();
+    assert!(empty.posts.is_empty());
+    assert_eq!(empty.total_pages, 1);
+    assert!(paginate(&[], 2, 10).is_none());
+
+    // A page size of zero is treated as one
+    assert_eq!(paginate(&posts(), 5, 0).unwrap().posts.len(), 1);
+}
+
+#[test]
+fn test_months() {
+    let months = months(&posts());
+    let keys: Vec<(i32, u32, usize)> = months.iter().map(|m| (m.year, m.month, m.count)).collect();
+
+    assert_eq!(
+        keys,
+        vec![(2026, 10, 2), (2026, 9, 1), (2025, 12, 1), (2025, 1, 1)]
+    );
+    assert_eq!(months[0].label(), "October 2026");
+}
+
+#[test]
+fn test_in_month() {
+    assert_eq!(slugs(&in_month(&posts(), 2026, 10)), vec!["e", "d"]);
+    assert!(in_month(&posts(), 2024, 10).is_empty());
+}
+
+#[test]
+fn test_month_label_out_of_range() {
+    assert_eq!(month_label(2026, 10).as_deref(), Some("October 2026"));
+    assert_eq!(month_label(2026, 13), None);
+    assert_eq!(month_label(2026, 0), None);
+}
+
+#[test]
+fn test_neighbours() {
+    let posts = posts();
+
+    let middle = neighbours(&posts, "c");
+    assert_eq!(middle.newer.unwrap().slug, "d");
+    assert_eq!(middle.older.unwrap().slug, "b");
+
+    let newest = neighbours(&posts, "e");
+    assert!(newest.newer.is_none());
+    assert_eq!(newest.older.unwrap().title, "Post d");
+
+    let unknown = neighbours(&posts, "zzz");
+    assert!(unknown.newer.is_none() && unknown.older.is_none());
+}
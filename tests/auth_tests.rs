@@ -1,5 +1,4 @@
 use cv::models::AuthUser;
-use serde_json;
 
 #[test]
 fn test_auth_user_serialization() {
@@ -0,0 +1,299 @@
+use chrono::{TimeZone, Utc};
+use cv::models::blog::{
+    is_safe_url, is_valid_slug, load_posts, parse_post, render_markdown, PostStatus,
+};
+use std::path::Path;
+
+#[test]
+fn test_parse_yaml_front_matter() {
+    let source = "---\ntitle: Hello\ndate: 2025-01-12\ndescription: First\ntags: [Rust, web]\n---\n\n# Body\n";
+    let post = parse_post("hello", source).unwrap();
+
+    assert_eq!(post.meta.slug, "hello");
+    assert_eq!(post.meta.title, "Hello");
+    assert_eq!(post.meta.date.to_string(), "2025-01-12");
+    assert_eq!(post.meta.tags, vec!["Rust", "web"]);
+    assert_eq!(post.meta.status, PostStatus::Published);
+    assert_eq!(post.markdown, "# Body\n");
+    assert_eq!(
+        post.html(),
+        "<h1 id=\"body\">Body<a class=\"heading-anchor\" href=\"#body\" aria-label=\"Link to this section\">#</a></h1>\n"
+    );
+    assert_eq!(post.toc.len(), 1);
+    assert_eq!(post.meta.word_count, 1);
+}
+
+#[test]
+fn test_parse_toml_front_matter() {
+    let source = "+++\ntitle = \"Hello\"\ndate = 2025-01-12\ndraft = true\n+++\nBody\n";
+    let post = parse_post("hello", source).unwrap();
+
+    assert_eq!(post.meta.date.to_string(), "2025-01-12");
+    assert_eq!(post.meta.status, PostStatus::Draft);
+    assert!(!post.meta.is_listed(Utc::now()));
+    assert_eq!(post.meta.description, "");
+}
+
+#[test]
+fn test_post_lifecycle() {
+    let post = |front: &str| parse_post("p", &format!("---\ntitle: P\n{}---\n", front));
+    let before = Utc.with_ymd_and_hms(2026, 11, 2, 8, 59, 0).unwrap();
+    let after = Utc.with_ymd_and_hms(2026, 11, 2, 9, 0, 0).unwrap();
+
+    // publish_at alone schedules the post and dates it
+    let scheduled = post("publish_at: 2026-11-02T10:00:00+01:00\n")
+        .unwrap()
+        .meta;
+    assert_eq!(scheduled.status, PostStatus::Scheduled);
+    assert_eq!(scheduled.date.to_string(), "2026-11-02");
+    assert_eq!(scheduled.status_at(before), PostStatus::Scheduled);
+    assert!(!scheduled.is_listed(before));
+    assert!(!scheduled.is_reachable(before, false));
+    assert!(scheduled.is_reachable(before, true));
+    assert_eq!(scheduled.status_at(after), PostStatus::Published);
+    assert!(scheduled.is_listed(after));
+
+    let unlisted = post("date: 2025-01-01\nstatus: unlisted\n").unwrap().meta;
+    assert!(!unlisted.is_listed(after));
+    assert!(unlisted.is_reachable(after, false));
+
+    let draft = post("date: 2025-01-01\nstatus: draft\n").unwrap().meta;
+    assert!(!draft.is_listed(after));
+    assert!(!draft.is_reachable(after, false));
+    assert!(draft.is_reachable(after, true));
+
+    let toml = parse_post(
+        "p",
+        "+++\ntitle = \"P\"\ndate = 2025-01-01\npublish_at = 2026-11-02T09:00:00Z\n+++\n",
+    )
+    .unwrap();
+    assert_eq!(toml.meta.publish_at, Some(after));
+
+    // Contradictions are rejected
+    assert!(post("date: 2025-01-01\nstatus: scheduled\n").is_err());
+    assert!(post("date: 2025-01-01\nstatus: unlisted\npublish_at: 2026-11-02\n").is_err());
+    assert!(post("date: 2025-01-01\ndraft: true\nstatus: published\n").is_err());
+    assert!(post("publish_at: soon\n").is_err());
+}
+
+#[test]
+fn test_parse_rejects_bad_front_matter() {
+    assert!(parse_post("hello", "# No front matter\n").is_err());
+    assert!(parse_post("hello", "---\ntitle: Hello\ndate: 2025-01-12\n").is_err());
+    assert!(parse_post("hello", "---\ntitle: Hello\n---\n").is_err());
+    assert!(parse_post(
+        "hello",
+        "---\ntitle: Hello\ndate: 2025-01-12\nauthor: x\n---\n"
+    )
+    .is_err());
+    assert!(parse_post("Hello World", "---\ntitle: Hello\ndate: 2025-01-12\n---\n").is_err());
+}
+
+#[test]
+fn test_valid_slugs() {
+    assert!(is_valid_slug("hello-dioxus-2"));
+    assert!(!is_valid_slug(""));
+    assert!(!is_valid_slug("Hello"));
+    assert!(!is_valid_slug("-hello"));
+    assert!(!is_valid_slug("a--b"));
+    assert!(!is_valid_slug("../etc"));
+}
+
+#[test]
+fn test_render_escapes_raw_html() {
+    let html = render_markdown("Hi <script>alert(1)</script>\n\n<div onclick=\"x\">block</div>\n");
+
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("<div"));
+    assert!(html.contains("&lt;script&gt;"));
+}
+
+#[test]
+fn test_render_neutralises_script_urls() {
+    let html = render_markdown(
+        "[a](javascript:alert(1)) [b](JaVaScRiPt:alert(1)) ![c](data:image/svg+xml,x) [d](https://example.com) [e](/blog)",
+    );
+
+    assert!(!html.to_lowercase().contains("javascript:"));
+    assert!(!html.contains("data:"));
+    assert!(html.contains("href=\"https://example.com\""));
+    assert!(html.contains("href=\"/blog\""));
+}
+
+#[test]
+fn test_safe_urls() {
+    assert!(is_safe_url("https://example.com"));
+    assert!(is_safe_url("mailto:me@example.com"));
+    assert!(is_safe_url("#section"));
+    assert!(is_safe_url("../post?a=b:c"));
+    assert!(!is_safe_url("java\tscript:alert(1)"));
+    assert!(!is_safe_url(" vbscript:x"));
+}
+
+#[test]
+fn test_bundled_posts_load() {
+    let posts = load_posts(Path::new("content/posts")).unwrap();
+
+    assert!(posts.len() >= 2);
+    // Newest first
+    assert!(posts.windows(2).all(|w| w[0].meta.date >= w[1].meta.date));
+    assert!(posts.iter().any(|p| p.meta.status == PostStatus::Draft));
+    assert!(posts.iter().any(|p| p.html().contains("<table>")));
+}
+
+#[test]
+fn test_load_posts_skips_broken_files() {
+    let dir = std::env::temp_dir().join(format!("cv-blog-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("good.md"),
+        "---\ntitle: Good\ndate: 2026-01-01\n---\nBody\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("broken.md"), "---\ntitle: Broken\n").unwrap();
+
+    let posts = load_posts(&dir).unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].meta.slug, "good");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_reading_time() {
+    let post = |words: usize| {
+        let body = "word ".repeat(words);
+        parse_post(
+            "p",
+            &format!("---\ntitle: P\ndate: 2025-01-12\n---\n{}\n", body),
+        )
+        .unwrap()
+    };
+    assert_eq!(post(0).meta.reading_minutes(), 1);
+    assert_eq!(post(220).meta.word_count, 220);
+    assert_eq!(post(220).meta.reading_minutes(), 1);
+    assert_eq!(post(221).meta.reading_minutes(), 2);
+    assert_eq!(post(1000).meta.reading_minutes(), 5);
+}
+
+#[test]
+fn test_series_front_matter() {
+    let post = |front: &str| {
+        parse_post(
+            "p",
+            &format!("---\ntitle: P\ndate: 2025-01-12\n{}---\n", front),
+        )
+    };
+
+    let meta = post("series: \"  Rust Web  \"\nseries_part: 3\n")
+        .unwrap()
+        .meta;
+    assert_eq!(meta.series.as_deref(), Some("Rust Web"));
+    assert_eq!(meta.series_part, Some(3));
+
+    let meta = post("series: Rust Web\n").unwrap().meta;
+    assert_eq!(meta.series_part, None);
+    assert_eq!(post("").unwrap().meta.series, None);
+    assert_eq!(post("series: \"  \"\n").unwrap().meta.series, None);
+
+    assert!(post("series_part: 2\n").is_err());
+    assert!(post("series: Rust Web\nseries_part: 0\n").is_err());
+    assert!(post("series: Rust Web\nseries_part: -1\n").is_err());
+
+    let toml = parse_post(
+        "p",
+        "+++\ntitle = \"P\"\ndate = 2025-01-12\nseries = \"Rust Web\"\nseries_part = 1\n+++\n",
+    )
+    .unwrap();
+    assert_eq!(toml.meta.series_part, Some(1));
+}
+
+#[test]
+fn test_canonical_url_front_matter() {
+    let post = |front: &str| {
+        parse_post(
+            "p",
+            &format!("---\ntitle: P\ndate: 2025-01-12\n{}---\n", front),
+        )
+    };
+
+    let meta = post("canonical_url: \" https://dev.to/me/p-1a2b \"\n")
+        .unwrap()
+        .meta;
+    assert_eq!(
+        meta.canonical_url.as_deref(),
+        Some("https://dev.to/me/p-1a2b")
+    );
+    assert_eq!(post("").unwrap().meta.canonical_url, None);
+    assert_eq!(
+        post("canonical_url: \"\"\n").unwrap().meta.canonical_url,
+        None
+    );
+
+    assert!(post("canonical_url: dev.to/me/p\n").is_err());
+    assert!(post("canonical_url: ftp://example.com/p\n").is_err());
+    assert!(post("canonical_url: \"https://example.com/a b\"\n").is_err());
+}
+
+#[test]
+fn test_syndication_front_matter() {
+    let post = |front: &str| {
+        parse_post(
+            "p",
+            &format!("---\ntitle: P\ndate: 2025-01-12\n{}---\n", front),
+        )
+    };
+
+    let meta = post(
+        "syndication:\n  - https://dev.to/me/p-1a2b\n  - \" https://hashnode.example/p \"\n  - \"\"\n  - https://dev.to/me/p-1a2b\n",
+    )
+    .unwrap()
+    .meta;
+    assert_eq!(
+        meta.syndication,
+        vec!["https://dev.to/me/p-1a2b", "https://hashnode.example/p"]
+    );
+    assert!(post("").unwrap().meta.syndication.is_empty());
+    assert!(post("syndication: [dev.to/me/p]\n").is_err());
+
+    assert_eq!(
+        meta.canonical("https://me.example"),
+        "https://me.example/blog/p"
+    );
+    let meta = post("canonical_url: https://dev.to/me/p-1a2b\n")
+        .unwrap()
+        .meta;
+    assert_eq!(
+        meta.canonical("https://me.example"),
+        "https://dev.to/me/p-1a2b"
+    );
+}
+
+#[test]
+fn test_image_front_matter() {
+    let post = |front: &str| {
+        parse_post(
+            "p",
+            &format!("---\ntitle: P\ndate: 2025-01-12\n{}---\n", front),
+        )
+    };
+
+    assert_eq!(
+        post("image: /media/abc-800.png\n")
+            .unwrap()
+            .meta
+            .image
+            .as_deref(),
+        Some("/media/abc-800.png")
+    );
+    assert_eq!(
+        post("image: https://cdn.example/p.png\n")
+            .unwrap()
+            .meta
+            .image
+            .as_deref(),
+        Some("https://cdn.example/p.png")
+    );
+    assert_eq!(post("").unwrap().meta.image, None);
+    assert!(post("image: media/p.png\n").is_err());
+    assert!(post("image: //cdn.example/p.png\n").is_err());
+}
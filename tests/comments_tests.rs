@@ -0,0 +1,313 @@
+use cv::models::comments::{
+    display_name, render_comment, threads, Comment, CommentStatus, EDIT_WINDOW_SECS,
+};
+
+fn comment(id: i64, parent_id: Option<i64>) -> Comment {
+    Comment {
+        id,
+        parent_id,
+        author: "reader".to_string(),
+        by_owner: false,
+        html: "<p>Hi</p>\n".to_string(),
+        body: String::new(),
+        status: CommentStatus::Approved,
+        created_at: 1_000,
+        edited: false,
+        deleted: false,
+        mine: false,
+    }
+}
+
+#[test]
+fn test_comment_markdown_subset() {
+    assert_eq!(
+        render_comment("Some *emphasis*, **bold** and `code`."),
+        "<p>Some <em>emphasis</em>, <strong>bold</strong> and <code>code</code>.</p>\n"
+    );
+    assert_eq!(
+        render_comment("[site](https://example.com)"),
+        "<p><a href=\"https://example.com\" rel=\"nofollow ugc noopener\">site</a></p>\n"
+    );
+    assert_eq!(
+        render_comment("<jane@example.com>"),
+        "<p><a href=\"mailto:jane@example.com\" rel=\"nofollow ugc noopener\">jane@example.com</a></p>\n"
+    );
+    // Headings are flattened and images reduced to their alt text
+    assert_eq!(
+        render_comment("# Loud\n\n![tracking pixel](https://evil.example/p.gif)"),
+        "<p>Loud</p>\n<p>tracking pixel</p>\n"
+    );
+    assert_eq!(
+        render_comment("```rust\nfn main() {}\n```"),
+        "<pre><code>fn main() {}\n</code></pre>\n"
+    );
+}
+
+#[test]
+fn test_comment_html_is_escaped() {
+    let html = render_comment(
+        "<script>alert(1)</script>\n\nHi <img src=x onerror=alert(1)> [x](javascript:alert(1)) [y](\"onmouseover=\"x)",
+    );
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("<img"));
+    assert!(!html.to_lowercase().contains("javascript:"));
+    assert!(!html.contains("href=\"\"onmouseover"));
+    assert!(html.contains("&lt;script&gt;"));
+    // The text of a dropped link stays
+    assert!(html.contains(" x "));
+}
+
+#[test]
+fn test_display_name_hides_the_address() {
+    assert_eq!(display_name("jane.doe@example.com"), "jane.doe");
+    assert_eq!(display_name("no-at-sign"), "no-at-sign");
+}
+
+#[test]
+fn test_edit_window() {
+    let mut mine = comment(1, None);
+    mine.mine = true;
+    assert!(mine.can_edit(1_000));
+    assert!(mine.can_edit(1_000 + EDIT_WINDOW_SECS - 1));
+    assert!(!mine.can_edit(1_000 + EDIT_WINDOW_SECS));
+    assert!(!comment(1, None).can_edit(1_000));
+}
+
+#[test]
+fn test_threads_nest_one_level() {
+    let mut deleted_with_reply = comment(4, None);
+    deleted_with_reply.deleted = true;
+    let mut deleted_alone = comment(6, None);
+    deleted_alone.deleted = true;
+    let mut deleted_reply = comment(7, Some(1));
+    deleted_reply.deleted = true;
+
+    let threads = threads(vec![
+        comment(1, None),
+        comment(2, Some(1)),
+        comment(3, None),
+        deleted_with_reply,
+        comment(5, Some(4)),
+        deleted_alone,
+        deleted_reply,
+        comment(8, Some(1)),
+        // The parent is hidden
+        comment(9, Some(100)),
+    ]);
+    let shape: Vec<(i64, Vec<i64>)> = threads
+        .iter()
+        .map(|t| (t.comment.id, t.replies.iter().map(|r| r.id).collect()))
+        .collect();
+    assert_eq!(shape, vec![(1, vec![2, 8]), (3, vec![]), (4, vec![5])]);
+}
+
+#[test]
+fn test_status_round_trips() {
+    for status in CommentStatus::ALL {
+        assert_eq!(CommentStatus::parse(status.as_str()), Some(status));
+    }
+    assert_eq!(CommentStatus::parse("deleted"), None);
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use cv::backend::comments::{
+        add, check_rate, delete, edit, for_post, get, set_status, visible, with_status,
+        CommentError,
+    };
+    use cv::backend::db;
+    use cv::models::comments::{threads, CommentStatus, EDIT_WINDOW_SECS, RATE_LIMIT};
+    use rusqlite::Connection;
+
+    const READER: &str = "reader@example.com";
+    const OTHER: &str = "other@example.com";
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        conn
+    }
+
+    fn post(conn: &Connection, parent_id: Option<i64>, author: &str, now: i64) -> i64 {
+        add(
+            conn,
+            "hello",
+            parent_id,
+            author,
+            "Nice post",
+            CommentStatus::Pending,
+            now,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pending_comments_are_only_shown_to_their_author() {
+        let conn = conn();
+        let id = post(&conn, None, READER, 100);
+
+        let stored = for_post(&conn, "hello").unwrap();
+        assert!(visible(stored.clone(), None).is_empty());
+        assert!(visible(stored.clone(), Some(OTHER)).is_empty());
+        let own = visible(stored, Some(READER));
+        assert_eq!(own.len(), 1);
+        assert!(own[0].mine);
+        assert_eq!(own[0].author, "reader");
+        assert_eq!(own[0].body, "Nice post");
+
+        // Authors see their spam as still pending
+        set_status(&conn, id, CommentStatus::Spam).unwrap();
+        let own = visible(for_post(&conn, "hello").unwrap(), Some(READER));
+        assert_eq!(own[0].status, CommentStatus::Pending);
+
+        set_status(&conn, id, CommentStatus::Approved).unwrap();
+        let public = visible(for_post(&conn, "hello").unwrap(), None);
+        assert_eq!(public.len(), 1);
+        assert_eq!(public[0].html, "<p>Nice post</p>\n");
+        // The source is only sent to the author
+        assert_eq!(public[0].body, "");
+        assert_eq!(
+            set_status(&conn, 999, CommentStatus::Approved),
+            Err(CommentError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_replies_go_one_level_deep() {
+        let conn = conn();
+        let top = post(&conn, None, READER, 100);
+        let reply = post(&conn, Some(top), OTHER, 110);
+        let add_reply = |parent: i64, slug: &str| {
+            add(
+                &conn,
+                slug,
+                Some(parent),
+                OTHER,
+                "Me too",
+                CommentStatus::Pending,
+                120,
+            )
+        };
+
+        assert_eq!(add_reply(reply, "hello"), Err(CommentError::InvalidParent));
+        assert_eq!(
+            add_reply(top, "other-post"),
+            Err(CommentError::InvalidParent)
+        );
+        assert_eq!(add_reply(999, "hello"), Err(CommentError::InvalidParent));
+        assert_eq!(get(&conn, reply).unwrap().unwrap().parent_id, Some(top));
+    }
+
+    #[test]
+    fn test_bodies_are_checked() {
+        let conn = conn();
+        let add_body = |body: &str| {
+            add(
+                &conn,
+                "hello",
+                None,
+                READER,
+                body,
+                CommentStatus::Pending,
+                100,
+            )
+        };
+        assert_eq!(add_body(" \n "), Err(CommentError::Empty));
+        assert_eq!(add_body(&"x".repeat(5001)), Err(CommentError::TooLong));
+        let id = add_body("  trimmed \n").unwrap();
+        assert_eq!(get(&conn, id).unwrap().unwrap().body, "trimmed");
+    }
+
+    #[test]
+    fn test_rate_limit_per_author() {
+        let conn = conn();
+        for i in 0..RATE_LIMIT as i64 {
+            check_rate(&conn, READER, 1_000 + i).unwrap();
+            post(&conn, None, READER, 1_000 + i);
+        }
+        assert_eq!(
+            check_rate(&conn, READER, 1_010),
+            Err(CommentError::RateLimited)
+        );
+        check_rate(&conn, OTHER, 1_010).unwrap();
+        // The window slides
+        check_rate(&conn, READER, 1_000 + 10 * 60).unwrap();
+    }
+
+    #[test]
+    fn test_edit_and_delete_windows() {
+        let conn = conn();
+        let id = post(&conn, None, READER, 100);
+        set_status(&conn, id, CommentStatus::Approved).unwrap();
+
+        assert_eq!(
+            edit(&conn, id, OTHER, "Mine now", false, 110),
+            Err(CommentError::Forbidden)
+        );
+        assert_eq!(
+            edit(&conn, id, READER, "Too late", false, 100 + EDIT_WINDOW_SECS),
+            Err(CommentError::EditWindowClosed)
+        );
+
+        // Edits go back to moderation
+        edit(&conn, id, READER, "Changed", false, 110).unwrap();
+        let stored = get(&conn, id).unwrap().unwrap();
+        assert_eq!(stored.body, "Changed");
+        assert_eq!(stored.status, CommentStatus::Pending);
+        assert_eq!(stored.edited_at, Some(110));
+        assert_eq!(with_status(&conn, CommentStatus::Pending).unwrap().len(), 1);
+
+        // Spam stays spam
+        set_status(&conn, id, CommentStatus::Spam).unwrap();
+        edit(&conn, id, READER, "Please approve", false, 120).unwrap();
+        assert_eq!(get(&conn, id).unwrap().unwrap().status, CommentStatus::Spam);
+
+        assert_eq!(
+            delete(&conn, id, READER, 100 + EDIT_WINDOW_SECS),
+            Err(CommentError::EditWindowClosed)
+        );
+        delete(&conn, id, READER, 130).unwrap();
+        assert_eq!(
+            edit(&conn, id, READER, "Back", false, 140),
+            Err(CommentError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_deleted_comments_keep_their_replies() {
+        let conn = conn();
+        let top = post(&conn, None, READER, 100);
+        let reply = post(&conn, Some(top), OTHER, 110);
+        for id in [top, reply] {
+            set_status(&conn, id, CommentStatus::Approved).unwrap();
+        }
+        delete(&conn, top, READER, 120).unwrap();
+        let stored = get(&conn, top).unwrap().unwrap();
+        assert_eq!(stored.body, "");
+        assert!(with_status(&conn, CommentStatus::Approved)
+            .unwrap()
+            .iter()
+            .all(|c| c.id != top));
+
+        let shown = threads(visible(for_post(&conn, "hello").unwrap(), None));
+        assert_eq!(shown.len(), 1);
+        assert!(shown[0].comment.deleted);
+        assert_eq!(shown[0].comment.author, "");
+        assert_eq!(shown[0].comment.html, "");
+        assert_eq!(shown[0].replies[0].id, reply);
+
+        // A deleted comment cannot be replied to
+        assert_eq!(
+            add(
+                &conn,
+                "hello",
+                Some(top),
+                OTHER,
+                "Hello?",
+                CommentStatus::Pending,
+                130
+            ),
+            Err(CommentError::InvalidParent)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use cv::components::{BlogPostCard, SectionCard, SocialLink};
+use cv::components::{BlogPostCard, SectionCard, Seo, SocialLink, SyndicationLinks};
 use dioxus::prelude::*;
 
 #[test]
@@ -17,6 +17,19 @@ fn test_blog_post_card_creation() {
     };
 }
 
+#[test]
+fn test_blog_post_card_with_reading_time() {
+    let _element = rsx! {
+        BlogPostCard {
+            title: "Test Post".to_string(),
+            description: "Test Description".to_string(),
+            date: "2025-01-12".to_string(),
+            reading_minutes: 4,
+            index: 1
+        }
+    };
+}
+
 #[test]
 fn test_section_card_creation() {
     let title = "Test Section".to_string();
@@ -435,3 +448,56 @@ fn test_component_performance_with_large_content() {
         }
     };
 }
+
+#[test]
+fn test_blog_post_card_with_stats() {
+    use cv::models::reactions::{PostStats, Reaction};
+
+    let _element = rsx! {
+        BlogPostCard {
+            title: "Test Post".to_string(),
+            description: "Test Description".to_string(),
+            stats: PostStats {
+                slug: "test-post".to_string(),
+                views: 42,
+                reactions: vec![(Reaction::Rocket, 3)],
+                mine: None,
+            },
+            index: 1
+        }
+    };
+}
+
+#[test]
+fn test_syndication_links() {
+    let _element = rsx! {
+        SyndicationLinks {
+            canonical_url: "https://medium.com/@me/hello-abc".to_string(),
+            links: vec!["https://dev.to/me/hello-1a2b".to_string()],
+        }
+    };
+    let _empty = rsx! {
+        SyndicationLinks { canonical_url: None, links: Vec::new() }
+    };
+}
+
+#[test]
+fn test_seo_creation() {
+    use cv::models::seo::PageKind;
+
+    let _page = rsx! {
+        Seo { title: "Blog" }
+    };
+    let _article = rsx! {
+        Seo {
+            title: "Hello",
+            description: "First post",
+            canonical: "https://dev.to/me/hello-1a2b".to_string(),
+            image: "/media/cover.png".to_string(),
+            kind: PageKind::Article,
+            published: chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            tags: vec!["rust".to_string()],
+            noindex: true,
+        }
+    };
+}
@@ -0,0 +1,99 @@
+use cv::models::contact::{qr_svg, vcard};
+use cv::models::cv::{Basics, Cv, Profile};
+
+fn basics() -> Basics {
+    Basics {
+        first_name: "Taian".to_string(),
+        last_name: "Feitosa".to_string(),
+        label: "Fullstack Developer & AI Engineer".to_string(),
+        summary: String::new(),
+        email: "taian@example.com".to_string(),
+        phone: None,
+        image: "https://example.com/photo.jpg".to_string(),
+        profiles: vec![Profile {
+            network: "Github".to_string(),
+            url: "https://github.com/taianf".to_string(),
+            icon: "fa-github".to_string(),
+        }],
+    }
+}
+
+#[test]
+fn test_vcard_structure() {
+    let card = vcard(&basics(), "https://example.com");
+    let lines: Vec<&str> = card.split("\r\n").collect();
+
+    assert_eq!(lines[0], "BEGIN:VCARD");
+    assert_eq!(lines[1], "VERSION:4.0");
+    assert!(lines.contains(&"FN:Taian Feitosa"));
+    assert!(lines.contains(&"N:Feitosa;Taian;;;"));
+    assert!(lines.contains(&"EMAIL;TYPE=work:taian@example.com"));
+    assert!(lines.contains(&"URL:https://example.com"));
+    assert!(lines.contains(&"URL;TYPE=github:https://github.com/taianf"));
+    assert!(lines.contains(&"PHOTO:https://example.com/photo.jpg"));
+    assert!(card.ends_with("END:VCARD\r\n"));
+}
+
+#[test]
+fn test_vcard_escapes_values() {
+    let mut basics = basics();
+    basics.label = "Engineer, AI; Rust\\Web".to_string();
+    let card = vcard(&basics, "https://example.com");
+
+    assert!(card.contains(r"TITLE:Engineer\, AI\; Rust\\Web"));
+}
+
+#[test]
+fn test_vcard_folds_long_lines() {
+    let mut basics = basics();
+    basics.image = format!("https://example.com/{}.jpg", "a".repeat(200));
+    let card = vcard(&basics, "https://example.com");
+
+    for line in card.split("\r\n") {
+        assert!(line.len() <= 75, "line too long: {}", line);
+    }
+    // Unfolding restores the original value
+    let unfolded = card.replace("\r\n ", "");
+    assert!(unfolded.contains(&basics.image));
+}
+
+#[test]
+fn test_vcard_includes_phone_only_when_present() {
+    let card = vcard(&basics(), "https://example.com");
+    assert!(!card.contains("TEL"));
+
+    let mut basics = basics();
+    basics.phone = Some("+55 11 5555 0000".to_string());
+    let card = vcard(&basics, "https://example.com");
+    assert!(card.contains("TEL;VALUE=uri;TYPE=cell:tel:+551155550000\r\n"));
+}
+
+#[test]
+fn test_vcard_from_bundled_cv() {
+    let cv = Cv::from_toml(include_str!("../content/cv.toml")).unwrap();
+    let card = vcard(&cv.full().without_private().basics, "https://example.com");
+
+    assert!(card.contains("FN:Taian Feitosa"));
+    assert!(card.contains("TITLE:Fullstack Developer & AI Engineer"));
+}
+
+#[test]
+fn test_qr_svg() {
+    let svg = qr_svg("https://example.com/contact.vcf").unwrap();
+
+    assert!(svg.starts_with("<?xml"));
+    assert!(svg.contains("<svg"));
+    assert!(svg.contains("#0f1116"));
+}
+
+#[test]
+fn test_qr_svg_is_deterministic() {
+    assert_eq!(
+        qr_svg("https://example.com").unwrap(),
+        qr_svg("https://example.com").unwrap()
+    );
+    assert_ne!(
+        qr_svg("https://example.com").unwrap(),
+        qr_svg("https://example.org").unwrap()
+    );
+}
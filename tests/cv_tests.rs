@@ -0,0 +1,148 @@
+use cv::models::cv::Cv;
+
+const CV_TOML: &str = include_str!("../content/cv.toml");
+
+fn bundled_cv() -> Cv {
+    Cv::from_toml(CV_TOML).expect("bundled CV data should be valid")
+}
+
+#[test]
+fn test_bundled_cv_is_valid() {
+    let cv = bundled_cv();
+    assert!(!cv.experience.is_empty());
+    assert!(!cv.skills.is_empty());
+    assert!(!cv.projects.is_empty());
+    assert!(cv.variant("backend").is_some());
+    assert!(cv.variant("ml-engineer").is_some());
+}
+
+#[test]
+fn test_full_cv_keeps_master_order() {
+    let cv = bundled_cv();
+    let full = cv.full();
+
+    assert_eq!(full.variant, None);
+    assert_eq!(full.basics, cv.basics);
+    let ids: Vec<&str> = full.experience.iter().map(|e| e.id.as_str()).collect();
+    let master_ids: Vec<&str> = cv.experience.iter().map(|e| e.id.as_str()).collect();
+    assert_eq!(ids, master_ids);
+}
+
+#[test]
+fn test_tailor_selects_and_reorders_entries() {
+    let cv = bundled_cv();
+    let tailored = cv.tailor("ml-engineer").unwrap();
+
+    assert_eq!(tailored.variant.as_deref(), Some("ml-engineer"));
+    let skills: Vec<&str> = tailored.skills.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(
+        skills,
+        vec!["llm", "machine-learning", "python", "rust", "docker"]
+    );
+    let projects: Vec<&str> = tailored.projects.iter().map(|p| p.id.as_str()).collect();
+    assert_eq!(projects, vec!["ai-experiments", "cv"]);
+    assert_eq!(tailored.experience.len(), 1);
+}
+
+#[test]
+fn test_tailor_applies_wording_overrides() {
+    let cv = bundled_cv();
+    let tailored = cv.tailor("backend").unwrap();
+
+    assert_eq!(tailored.basics.label, "Backend Engineer (Rust)");
+    assert_eq!(tailored.experience[0].position, "Backend Engineer");
+    assert_ne!(
+        tailored.experience[0].highlights,
+        cv.experience[0].highlights
+    );
+    // Entries without overrides keep the master wording
+    let open_source = tailored
+        .experience
+        .iter()
+        .find(|e| e.id == "open-source")
+        .unwrap();
+    let master = cv
+        .experience
+        .iter()
+        .find(|e| e.id == "open-source")
+        .unwrap();
+    assert_eq!(open_source, master);
+}
+
+#[test]
+fn test_tailor_unknown_variant() {
+    let cv = bundled_cv();
+    assert!(cv.tailor("does-not-exist").is_none());
+}
+
+#[test]
+fn test_validate_rejects_unknown_references() {
+    let source = CV_TOML.replace(
+        "skills = [\"llm\", \"machine-learning\", \"python\", \"rust\", \"docker\"]",
+        "skills = [\"llm\", \"cobol\"]",
+    );
+    let err = Cv::from_toml(&source).unwrap_err();
+    assert!(err.contains("cobol"));
+}
+
+#[test]
+fn test_validate_rejects_duplicate_variants() {
+    let source = CV_TOML.replace("slug = \"ml-engineer\"", "slug = \"backend\"");
+    let err = Cv::from_toml(&source).unwrap_err();
+    assert!(err.contains("Duplicate variant"));
+}
+
+#[test]
+fn test_skills_grouped_by_category() {
+    let cv = bundled_cv();
+    let groups = cv.tailor("backend").unwrap().skills_by_category();
+
+    assert_eq!(groups[0].0, "Languages");
+    let languages: Vec<&str> = groups[0].1.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(languages, vec!["rust", "python"]);
+}
+
+#[test]
+fn test_json_resume_export() {
+    let cv = bundled_cv();
+    let json = cv.tailor("backend").unwrap().to_json_resume();
+
+    assert_eq!(json["basics"]["name"], "Taian Feitosa");
+    assert_eq!(json["basics"]["label"], "Backend Engineer (Rust)");
+    assert_eq!(json["work"][0]["position"], "Backend Engineer");
+    assert_eq!(json["skills"][0]["name"], "Rust");
+}
+
+#[test]
+fn test_markdown_export() {
+    let cv = bundled_cv();
+    let markdown = cv.tailor("ml-engineer").unwrap().to_markdown();
+
+    assert!(markdown.starts_with("# Taian Feitosa\n"));
+    assert!(markdown.contains("**Machine Learning Engineer**"));
+    assert!(markdown.contains("## Experience"));
+    assert!(markdown.contains("### AI Engineer — Independent"));
+    assert!(markdown.contains("- **AI:** Large Language Models, Machine Learning"));
+    assert!(markdown.ends_with('\n'));
+}
+
+#[test]
+fn test_private_contact_details_are_removed() {
+    let source = CV_TOML.replace("# phone = \"+55 ...\"", "phone = \"+55 11 5555-0000\"");
+    let cv = Cv::from_toml(&source).unwrap();
+
+    let shared = cv.tailor("backend").unwrap();
+    assert_eq!(shared.basics.phone.as_deref(), Some("+55 11 5555-0000"));
+    assert!(shared.to_markdown().contains("+55 11 5555-0000"));
+
+    let public = shared.without_private();
+    assert_eq!(public.basics.phone, None);
+    assert!(!public.to_markdown().contains("+55 11 5555-0000"));
+}
+
+#[test]
+fn test_variants_default_to_public() {
+    let cv = bundled_cv();
+    assert!(cv.variant("backend").unwrap().public);
+    assert!(!cv.variant("ml-engineer").unwrap().public);
+}
@@ -0,0 +1,193 @@
+use chrono::NaiveDate;
+use cv::models::blog::{merge_posts, parse_post, PostStatus};
+use cv::models::editor::PostDraft;
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+}
+
+#[test]
+fn test_draft_round_trips_through_front_matter() {
+    let source = "---\ntitle: \"Rust: the \\\"good\\\" parts\"\ndate: 2025-02-03\ndescription: 'It''s a # test'\ntags: [Rust, C++]\n---\n\n# Hello\n\nBody\n";
+    let post = parse_post("rust-parts", source).unwrap();
+
+    let draft = PostDraft::from_post(&post);
+    assert_eq!(draft.tags, "Rust, C++");
+    let reparsed = draft.parse().unwrap();
+    assert_eq!(reparsed.meta, post.meta);
+    assert_eq!(reparsed.markdown, post.markdown);
+}
+
+#[test]
+fn test_scheduled_draft_keeps_publish_time() {
+    let mut draft = PostDraft::new("later", today());
+    draft.title = "Later".to_string();
+    draft.status = PostStatus::Scheduled;
+    draft.publish_at = "2026-11-02T09:00".to_string();
+
+    let meta = draft.parse().unwrap().meta;
+    assert_eq!(meta.status, PostStatus::Scheduled);
+    assert_eq!(
+        meta.publish_at.map(|at| at.to_rfc3339()),
+        Some("2026-11-02T09:00:00+00:00".to_string())
+    );
+    assert_eq!(PostDraft::from_post(&draft.parse().unwrap()), draft);
+
+    // Publishing now drops the schedule
+    let published = draft.with_status(PostStatus::Published);
+    assert_eq!(published.publish_at, "");
+    assert_eq!(published.parse().unwrap().meta.publish_at, None);
+}
+
+#[test]
+fn test_draft_keeps_series() {
+    let mut draft = PostDraft::new("part-two", today());
+    draft.title = "Part two".to_string();
+    draft.series = " Building a Blog ".to_string();
+    draft.series_part = "2".to_string();
+
+    let meta = draft.parse().unwrap().meta;
+    assert_eq!(meta.series.as_deref(), Some("Building a Blog"));
+    assert_eq!(meta.series_part, Some(2));
+    assert!(draft
+        .to_source()
+        .contains("series: Building a Blog\nseries_part: 2\n"));
+
+    draft.series_part = "two".to_string();
+    assert!(draft.parse().is_err());
+}
+
+#[test]
+fn test_draft_keeps_cross_posting_links() {
+    let mut draft = PostDraft::new("cross-posted", today());
+    draft.title = "Cross-posted".to_string();
+    draft.canonical_url = "https://dev.to/me/cross-posted-1a2b".to_string();
+
+    let post = draft.parse().unwrap();
+    assert_eq!(
+        post.meta.canonical_url.as_deref(),
+        Some("https://dev.to/me/cross-posted-1a2b")
+    );
+    assert!(draft
+        .to_source()
+        .contains("canonical_url: https://dev.to/me/cross-posted-1a2b\n"));
+    assert_eq!(PostDraft::from_post(&post), draft);
+
+    draft.syndication =
+        "https://dev.to/me/cross-posted-1a2b\n\n https://lobste.rs/s/abc ".to_string();
+    let post = draft.parse().unwrap();
+    assert_eq!(
+        post.meta.syndication,
+        vec![
+            "https://dev.to/me/cross-posted-1a2b",
+            "https://lobste.rs/s/abc"
+        ]
+    );
+    assert_eq!(
+        PostDraft::from_post(&post).syndication,
+        "https://dev.to/me/cross-posted-1a2b\nhttps://lobste.rs/s/abc"
+    );
+
+    draft.image = "/media/cover-800.png".to_string();
+    let post = draft.parse().unwrap();
+    assert_eq!(post.meta.image.as_deref(), Some("/media/cover-800.png"));
+    assert_eq!(PostDraft::from_post(&post).image, draft.image);
+
+    draft.canonical_url = "not a url".to_string();
+    assert!(draft.parse().is_err());
+}
+
+#[test]
+fn test_new_draft_needs_a_title() {
+    let draft = PostDraft::new("empty", today());
+    assert_eq!(draft.status, PostStatus::Draft);
+    assert_eq!(draft.date, "2026-10-19");
+    assert!(draft.parse().is_err());
+
+    let bad_slug = PostDraft {
+        title: "Hi".to_string(),
+        ..PostDraft::new("Not A Slug", today())
+    };
+    assert!(bad_slug.parse().is_err());
+}
+
+#[test]
+fn test_insert_block_at_cursor() {
+    let mut draft = PostDraft::new("images", today());
+    draft.insert_block(None, "![a](/a.png)");
+    assert_eq!(draft.body, "![a](/a.png)\n");
+
+    // The cursor is in UTF-16 units: \u{1F980} counts twice
+    draft.body = "Crab \u{1F980}\nTwo\n".to_string();
+    draft.insert_block(Some(7), "![b](/b.png)");
+    assert_eq!(draft.body, "Crab \u{1F980}\n\n![b](/b.png)\n\nTwo\n");
+
+    draft.insert_block(Some(10_000), "![c](/c.png)");
+    assert!(draft.body.ends_with("Two\n\n![c](/c.png)\n"));
+}
+
+#[test]
+fn test_saved_posts_replace_files() {
+    let post = |slug: &str, title: &str, date: &str| {
+        parse_post(
+            slug,
+            &format!("---\ntitle: {}\ndate: {}\n---\n", title, date),
+        )
+        .unwrap()
+    };
+    let files = vec![
+        post("a", "File A", "2025-01-01"),
+        post("b", "File B", "2025-02-01"),
+    ];
+    let saved = vec![
+        post("a", "Edited A", "2025-03-01"),
+        post("c", "New C", "2024-12-01"),
+    ];
+
+    let merged = merge_posts(files, saved);
+    let titles: Vec<&str> = merged.iter().map(|p| p.meta.title.as_str()).collect();
+    assert_eq!(titles, vec!["Edited A", "File B", "New C"]);
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use cv::backend::{db, posts};
+    use rusqlite::Connection;
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_save_updates_in_place() {
+        let conn = conn();
+        posts::save(&conn, "a", "one", "me@example.com", 100).unwrap();
+        posts::save(&conn, "a", "two", "me@example.com", 200).unwrap();
+
+        let saved = posts::saved(&conn).unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].source, "two");
+        assert_eq!((saved[0].created_at, saved[0].updated_at), (100, 200));
+        assert!(posts::get(&conn, "b").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_save_clears_autosave() {
+        let conn = conn();
+        posts::autosave(&conn, "a", "{}", 100).unwrap();
+        posts::autosave(&conn, "a", "{\"x\":1}", 150).unwrap();
+        assert_eq!(
+            posts::autosave_for(&conn, "a").unwrap(),
+            Some(("{\"x\":1}".to_string(), 150))
+        );
+        assert_eq!(
+            posts::autosaves(&conn).unwrap(),
+            vec![("a".to_string(), 150)]
+        );
+
+        posts::save(&conn, "a", "source", "me@example.com", 200).unwrap();
+        assert_eq!(posts::autosave_for(&conn, "a").unwrap(), None);
+    }
+}
@@ -2,7 +2,6 @@ use cv::components::{BlogPostCard, SectionCard, SocialLink};
 use cv::models::AuthUser;
 use cv::Route;
 use dioxus::prelude::*;
-use serde_json;
 
 #[test]
 fn test_auth_user_invalid_json_deserialization() {
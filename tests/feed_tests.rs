@@ -0,0 +1,221 @@
+use chrono::DateTime;
+use cv::models::blog::{load_posts, parse_post, Post};
+use cv::models::feed::{atom, escape_xml, rss, FeedInfo, FEED_LENGTH};
+use std::path::Path;
+
+const ATOM_NS: &str = "http://www.w3.org/2005/Atom";
+const CONTENT_NS: &str = "http://purl.org/rss/1.0/modules/content/";
+
+fn info() -> FeedInfo {
+    FeedInfo {
+        title: "Taian Feitosa — Blog".to_string(),
+        description: "Posts & notes".to_string(),
+        author: "Taian Feitosa".to_string(),
+        site_url: "https://example.com".to_string(),
+        page_url: "https://example.com/blog".to_string(),
+        feed_url: "https://example.com/feed.xml".to_string(),
+    }
+}
+
+fn posts() -> Vec<Post> {
+    load_posts(Path::new("content/posts"))
+        .unwrap()
+        .into_iter()
+        .filter(|p| p.meta.is_listed(chrono::Utc::now()))
+        .collect()
+}
+
+fn tricky_post() -> Post {
+    parse_post(
+        "tricky",
+        "---\ntitle: \"Fish & <Chips>\"\ndate: 2025-06-01\ndescription: \"Quotes ' and \\\" and ]]>\"\ntags: [\"C++\"]\n---\n[home](/blog) ![img](/media/a.png) [ext](https://example.org) [proto](//cdn.example.com/x)\n",
+    )
+    .unwrap()
+}
+
+/// Child element by name; `ns` is empty for RSS elements without a namespace.
+fn child<'a>(node: roxmltree::Node<'a, 'a>, (ns, name): (&str, &str)) -> roxmltree::Node<'a, 'a> {
+    node.children()
+        .find(|n| n.tag_name().name() == name && n.tag_name().namespace().unwrap_or("") == ns)
+        .unwrap_or_else(|| panic!("missing <{}>", name))
+}
+
+fn text<'a>(node: roxmltree::Node<'a, 'a>, name: (&str, &str)) -> &'a str {
+    child(node, name).text().unwrap_or_default()
+}
+
+#[test]
+fn test_rss_is_valid() {
+    let posts = posts();
+    let xml = rss(&info(), &posts);
+    let doc = roxmltree::Document::parse(&xml).expect("RSS should be well-formed XML");
+
+    let root = doc.root_element();
+    assert!(root.has_tag_name("rss"));
+    assert_eq!(root.attribute("version"), Some("2.0"));
+    let channel = child(root, ("", "channel"));
+    assert_eq!(text(channel, ("", "title")), "Taian Feitosa — Blog");
+    assert_eq!(
+        child(channel, (ATOM_NS, "link")).attribute("href"),
+        Some("https://example.com/feed.xml")
+    );
+
+    let items: Vec<_> = channel
+        .children()
+        .filter(|n| n.has_tag_name("item"))
+        .collect();
+    assert_eq!(items.len(), posts.len());
+    for (item, post) in items.iter().zip(&posts) {
+        let url = format!("https://example.com/blog/{}", post.meta.slug);
+        assert_eq!(text(*item, ("", "guid")), url);
+        assert_eq!(
+            child(*item, ("", "guid")).attribute("isPermaLink"),
+            Some("true")
+        );
+        assert_eq!(text(*item, ("", "link")), url);
+        let date = DateTime::parse_from_rfc2822(text(*item, ("", "pubDate"))).unwrap();
+        assert_eq!(date.date_naive(), post.meta.date);
+        assert_eq!(text(*item, (CONTENT_NS, "encoded")), post.html());
+    }
+}
+
+#[test]
+fn test_atom_is_valid() {
+    let posts = posts();
+    let xml = atom(&info(), &posts);
+    let doc = roxmltree::Document::parse(&xml).expect("Atom should be well-formed XML");
+
+    let feed = doc.root_element();
+    assert!(feed.has_tag_name((ATOM_NS, "feed")));
+    for required in ["title", "id", "updated", "author"] {
+        child(feed, (ATOM_NS, required));
+    }
+    let updated = DateTime::parse_from_rfc3339(text(feed, (ATOM_NS, "updated"))).unwrap();
+    assert_eq!(updated.date_naive(), posts[0].meta.date);
+
+    let entries: Vec<_> = feed
+        .children()
+        .filter(|n| n.has_tag_name((ATOM_NS, "entry")))
+        .collect();
+    assert_eq!(entries.len(), posts.len());
+    for (entry, post) in entries.iter().zip(&posts) {
+        assert_eq!(
+            text(*entry, (ATOM_NS, "id")),
+            format!("https://example.com/blog/{}", post.meta.slug)
+        );
+        DateTime::parse_from_rfc3339(text(*entry, (ATOM_NS, "published"))).unwrap();
+        DateTime::parse_from_rfc3339(text(*entry, (ATOM_NS, "updated"))).unwrap();
+        let content = child(*entry, (ATOM_NS, "content"));
+        assert_eq!(content.attribute("type"), Some("html"));
+        assert_eq!(content.text().unwrap(), post.html());
+    }
+}
+
+#[test]
+fn test_feeds_escape_content() {
+    let posts = vec![tricky_post()];
+    for xml in [rss(&info(), &posts), atom(&info(), &posts)] {
+        let doc = roxmltree::Document::parse(&xml).unwrap();
+        let titles: Vec<&str> = doc
+            .descendants()
+            .filter(|n| n.tag_name().name() == "title")
+            .filter_map(|n| n.text())
+            .collect();
+        assert!(titles.contains(&"Fish & <Chips>"));
+    }
+}
+
+#[test]
+fn test_feed_content_links_are_absolute() {
+    let xml = rss(&info(), &[tricky_post()]);
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let content = doc
+        .descendants()
+        .find(|n| n.has_tag_name((CONTENT_NS, "encoded")))
+        .unwrap()
+        .text()
+        .unwrap();
+
+    assert!(content.contains("href=\"https://example.com/blog\""));
+    assert!(content.contains("src=\"https://example.com/media/a.png\""));
+    assert!(content.contains("href=\"https://example.org\""));
+    assert!(content.contains("href=\"//cdn.example.com/x\""));
+}
+
+#[test]
+fn test_guids_are_stable() {
+    let posts = posts();
+    let first = rss(&info(), &posts);
+    let reversed: Vec<Post> = posts.iter().rev().cloned().collect();
+    let second = rss(&info(), &reversed);
+
+    let guids = |xml: &str| {
+        let doc = roxmltree::Document::parse(xml).unwrap();
+        let mut guids: Vec<String> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("guid"))
+            .map(|n| n.text().unwrap().to_string())
+            .collect();
+        guids.sort();
+        guids
+    };
+    assert_eq!(guids(&first), guids(&second));
+}
+
+#[test]
+fn test_feed_length_is_capped() {
+    let post = tricky_post();
+    let posts = vec![post; FEED_LENGTH + 5];
+    let xml = atom(&info(), &posts);
+
+    assert_eq!(xml.matches("<entry>").count(), FEED_LENGTH);
+}
+
+#[test]
+fn test_escape_xml_drops_control_characters() {
+    assert_eq!(escape_xml("a\u{1}b\tc"), "ab\tc");
+}
+
+#[test]
+fn test_entries_link_to_the_canonical_url() {
+    let imported = parse_post(
+        "imported",
+        "---\ntitle: Imported\ndate: 2025-06-01\ncanonical_url: https://dev.to/me/imported-1a2b\n---\nBody\n",
+    )
+    .unwrap();
+    let posts = vec![imported, tricky_post()];
+
+    let xml = rss(&info(), &posts);
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let items: Vec<_> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("item"))
+        .collect();
+    assert_eq!(
+        text(items[0], ("", "link")),
+        "https://dev.to/me/imported-1a2b"
+    );
+    assert_eq!(
+        text(items[0], ("", "guid")),
+        "https://example.com/blog/imported"
+    );
+    assert_eq!(
+        text(items[1], ("", "link")),
+        "https://example.com/blog/tricky"
+    );
+
+    let xml = atom(&info(), &posts);
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let entry = doc
+        .descendants()
+        .find(|n| n.has_tag_name((ATOM_NS, "entry")))
+        .unwrap();
+    assert_eq!(
+        child(entry, (ATOM_NS, "link")).attribute("href"),
+        Some("https://dev.to/me/imported-1a2b")
+    );
+    assert_eq!(
+        text(entry, (ATOM_NS, "id")),
+        "https://example.com/blog/imported"
+    );
+}
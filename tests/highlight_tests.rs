@@ -0,0 +1,117 @@
+use cv::models::blog::{render_blocks, render_markdown, PostBlock};
+use cv::models::highlight::{CodeBlock, FenceInfo};
+
+#[test]
+fn test_fence_info_language_and_lines() {
+    let fence = FenceInfo::parse("rust {3,5-7}");
+
+    assert_eq!(fence.language.as_deref(), Some("rust"));
+    assert_eq!(fence.highlighted, vec![(3, 3), (5, 7)]);
+    assert!(fence.is_highlighted(6));
+    assert!(!fence.is_highlighted(4));
+}
+
+#[test]
+fn test_fence_info_variants() {
+    assert_eq!(
+        FenceInfo::parse("Rust,ignore").language.as_deref(),
+        Some("rust")
+    );
+    assert_eq!(FenceInfo::parse("").language, None);
+    assert_eq!(FenceInfo::parse("{2}").highlighted, vec![(2, 2)]);
+    // Malformed and reversed ranges are dropped
+    assert_eq!(
+        FenceInfo::parse("rust {x,0,4-2,9}").highlighted,
+        vec![(9, 9)]
+    );
+}
+
+#[test]
+fn test_code_block_lines() {
+    let block = CodeBlock::new("text {2}", "a < b\nsecond\nthird\n".to_string());
+
+    assert_eq!(block.html.matches("class=\"line-number\"").count(), 3);
+    assert_eq!(block.html.matches("line highlighted").count(), 1);
+    assert!(block.html.contains("<span class=\"line-number\">3</span>"));
+    assert!(block.html.contains("a &lt; b"));
+    assert_eq!(block.code, "a < b\nsecond\nthird\n");
+}
+
+#[test]
+fn test_render_splits_code_blocks() {
+    let blocks = render_blocks("Intro\n\n```rust {1}\nfn main() {}\n```\n\nOutro\n");
+
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(blocks[0], PostBlock::Html("<p>Intro</p>\n".to_string()));
+    match &blocks[1] {
+        PostBlock::Code(code) => {
+            assert_eq!(code.language.as_deref(), Some("rust"));
+            assert_eq!(code.code, "fn main() {}\n");
+        }
+        other => panic!("expected code block, got {:?}", other),
+    }
+    assert_eq!(blocks[2], PostBlock::Html("<p>Outro</p>\n".to_string()));
+}
+
+#[test]
+fn test_nested_code_blocks_stay_inline() {
+    let blocks = render_blocks("- item\n\n  ```rust\n  let x = 1;\n  ```\n- next\n\nAfter\n");
+
+    assert_eq!(blocks.len(), 1);
+    let PostBlock::Html(html) = &blocks[0] else {
+        panic!("expected HTML, got {:?}", blocks[0]);
+    };
+    let code = html.find("class=\"code-block\"").unwrap();
+    assert!(html.find("<li>").unwrap() < code);
+    assert!(code < html.find("</ul>").unwrap());
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    assert!(text.contains("let x = 1;"));
+}
+
+#[test]
+fn test_code_marker_cannot_be_forged() {
+    let html = render_markdown("<!--code:0-->\n\n```\nx\n```\n");
+
+    assert!(html.contains("&lt;!--code:0--&gt;"));
+    assert_eq!(html.matches("class=\"code-block\"").count(), 1);
+}
+
+#[test]
+fn test_footnotes_span_code_blocks() {
+    let html = render_markdown("One[^a]\n\n```\nx\n```\n\nTwo[^b]\n\n[^a]: A\n[^b]: B\n");
+
+    assert!(html.contains(">1</a>"));
+    assert!(html.contains(">2</a>"));
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use cv::models::highlight::CodeBlock;
+
+    #[test]
+    fn test_rust_is_highlighted() {
+        let block = CodeBlock::new("rust", "fn main() {\n    let x = 1;\n}\n".to_string());
+
+        assert!(block.html.contains("style=\"color:#c4b5fd;\">main</span>"));
+        assert!(block.html.contains("style=\"color:#fbbf24;\">1</span>"));
+        assert_eq!(block.html.matches("class=\"line\"").count(), 3);
+        assert!(!block.html.contains('\n'));
+    }
+
+    #[test]
+    fn test_unknown_language_falls_back_to_plain() {
+        let block = CodeBlock::new("nope", "<x>\n".to_string());
+
+        assert!(!block.html.contains("style="));
+        assert!(block.html.contains("&lt;x&gt;"));
+    }
+}
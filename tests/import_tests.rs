@@ -0,0 +1,364 @@
+#[cfg(feature = "server")]
+mod server {
+    use chrono::NaiveDate;
+    use cv::backend::import::{
+        assign_slugs, convert_liquid_tags, image_urls, localize_images, read_export, slugify,
+        Export, ImportError,
+    };
+    use cv::backend::{db, import, posts, webmentions};
+    use cv::models::blog::PostStatus;
+    use cv::models::editor::PostDraft;
+    use rusqlite::Connection;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+    }
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        conn
+    }
+
+    fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, contents) in files {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn draft(slug: &str, title: &str) -> PostDraft {
+        PostDraft {
+            title: title.to_string(),
+            ..PostDraft::new(slug, today())
+        }
+    }
+
+    const DEVTO: &str = r#"[
+        {
+            "title": "Parsing JSON in Rust",
+            "description": "serde all the way",
+            "body_markdown": "Intro.\n\n{% embed https://github.com/serde-rs/json %}\n\n{% youtube dQw4w9WgXcQ %}",
+            "published": true,
+            "published_at": "2021-03-04T10:00:00.000Z",
+            "tag_list": "rust, json",
+            "canonical_url": "https://dev.to/me/parsing-json-in-rust-4k2j",
+            "cover_image": "https://cdn.example/cover.png"
+        },
+        {
+            "title": "",
+            "body_markdown": "---\ntitle: Old Editor Post\ntags: webdev, css\ncanonical_url: https://me.example/old\n---\nBody text",
+            "published": false,
+            "created_at": "2020-01-02T08:00:00Z",
+            "url": "https://dev.to/me/old-editor-post-1a2b"
+        },
+        { "title": "  ", "body_markdown": "Nothing" }
+    ]"#;
+
+    #[test]
+    fn test_devto_export() {
+        let export = read_export(DEVTO.as_bytes(), today()).unwrap();
+        assert_eq!(export.drafts.len(), 2);
+        assert_eq!(export.skipped.len(), 1);
+
+        let first = &export.drafts[0];
+        assert_eq!(first.slug, "parsing-json-in-rust");
+        assert_eq!(first.date, "2021-03-04");
+        assert_eq!(first.tags, "rust, json");
+        assert_eq!(first.status, PostStatus::Published);
+        assert_eq!(
+            first.canonical_url,
+            "https://dev.to/me/parsing-json-in-rust-4k2j"
+        );
+        assert_eq!(
+            first.body,
+            "![](https://cdn.example/cover.png)\n\nIntro.\n\n<https://github.com/serde-rs/json>\n\n<https://www.youtube.com/watch?v=dQw4w9WgXcQ>"
+        );
+        let meta = first.parse().unwrap().meta;
+        assert_eq!(meta.tags, vec!["rust", "json"]);
+        assert_eq!(
+            meta.canonical_url.as_deref(),
+            Some("https://dev.to/me/parsing-json-in-rust-4k2j")
+        );
+
+        // Front matter from dev.to's older editor fills the gaps
+        let second = &export.drafts[1];
+        assert_eq!(second.title, "Old Editor Post");
+        assert_eq!(second.tags, "webdev, css");
+        assert_eq!(second.canonical_url, "https://me.example/old");
+        assert_eq!(second.date, "2020-01-02");
+        assert_eq!(second.status, PostStatus::Draft);
+        assert_eq!(second.body, "Body text");
+
+        // The same export, zipped and wrapped
+        let wrapped = format!("{{\"articles\": {}}}", DEVTO);
+        let zipped = zip(&[("export/articles.json", &wrapped)]);
+        assert_eq!(read_export(&zipped, today()).unwrap().drafts.len(), 2);
+    }
+
+    #[test]
+    fn test_liquid_tags() {
+        assert_eq!(
+            convert_liquid_tags("See {% github rust-lang/rust %} and {% tweet 123 %}."),
+            "See <https://github.com/rust-lang/rust> and <https://twitter.com/i/status/123>."
+        );
+        assert_eq!(
+            convert_liquid_tags("{% codepen abc %} {% raw %}"),
+            "{% codepen abc %} {% raw %}"
+        );
+    }
+
+    const MEDIUM_POST: &str = r#"<!DOCTYPE html><html><head><title>Why I Like &amp; Use Rust</title></head><body>
+<article class="h-entry">
+<header><h1 class="p-name">Why I Like &amp; Use Rust</h1></header>
+<section data-field="subtitle" class="p-summary">Memory safety without a GC</section>
+<section data-field="body" class="e-content">
+<section name="a1" class="section section--body section--first"><div class="section-divider"><hr class="section-divider"></div><div class="section-content"><div class="section-inner sectionLayout--insetColumn">
+<h3 name="t1" class="graf graf--h3 graf--leading graf--title">Why I Like &amp; Use Rust</h3>
+<h4 name="s1" class="graf graf--h4 graf-after--h3 graf--subtitle">Memory safety without a GC</h4>
+<p name="p1" class="graf graf--p">It has <strong class="markup--strong">ownership</strong> and <a href="https://doc.rust-lang.org" class="markup--anchor">docs</a>.</p>
+<figure name="f1" class="graf graf--figure"><img class="graf-image" src="https://cdn-images-1.medium.com/max/800/1*abc.png"><figcaption>A crab</figcaption></figure>
+<h3 name="h1" class="graf graf--h3">Getting started</h3>
+<ul class="postList"><li name="l1" class="graf graf--li">Install rustup</li><li name="l2" class="graf graf--li">Run cargo new</li></ul>
+</div></div></section>
+</section>
+<footer><p>By <a href="https://medium.com/@me" class="p-author h-card">Me</a> on <a href="https://medium.com/p/abc"><time class="dt-published" datetime="2019-05-01T12:34:56.789Z">May 1, 2019</time></a>.</p><p><a href="https://medium.com/@me/why-i-like-rust-abc" class="p-canonical">Canonical link</a></p></footer>
+</article></body></html>"#;
+
+    #[test]
+    fn test_medium_export() {
+        let archive = zip(&[
+            ("README.html", "<p>Your export</p>"),
+            ("posts/2019-05-01_Why-I-Like-Rust-abc.html", MEDIUM_POST),
+            (
+                "posts/draft_Unfinished-def.html",
+                r#"<h1 class="p-name">Unfinished</h1><section data-field="body"><p>Soon</p></section>"#,
+            ),
+            ("posts/broken.html", "<p>No title</p>"),
+            ("profile/profile.html", "<h1 class=\"p-name\">Me</h1>"),
+        ]);
+        let export = read_export(&archive, today()).unwrap();
+        assert_eq!(export.drafts.len(), 2);
+        assert_eq!(export.skipped, vec!["broken.html: no title or body found"]);
+
+        let post = &export.drafts[0];
+        assert_eq!(post.title, "Why I Like & Use Rust");
+        assert_eq!(post.slug, "why-i-like-use-rust");
+        assert_eq!(post.description, "Memory safety without a GC");
+        assert_eq!(post.date, "2019-05-01");
+        assert_eq!(post.status, PostStatus::Published);
+        assert_eq!(
+            post.canonical_url,
+            "https://medium.com/@me/why-i-like-rust-abc"
+        );
+        assert!(!post.body.contains("Why I Like"));
+        assert!(!post.body.contains("Memory safety"));
+        assert!(post
+            .body
+            .contains("It has **ownership** and [docs](https://doc.rust-lang.org)."));
+        assert!(post
+            .body
+            .contains("![](https://cdn-images-1.medium.com/max/800/1*abc.png)"));
+        assert!(post.body.contains("### Getting started"));
+        assert!(post.body.contains("- Install rustup\n- Run cargo new"));
+        assert!(post.parse().is_ok());
+
+        let unfinished = &export.drafts[1];
+        assert_eq!(unfinished.status, PostStatus::Draft);
+        assert_eq!(unfinished.date, today().to_string());
+        assert_eq!(unfinished.body, "Soon");
+    }
+
+    #[test]
+    fn test_unknown_exports() {
+        assert_eq!(
+            read_export(b"hello", today()).unwrap_err(),
+            ImportError::UnknownFormat
+        );
+        assert_eq!(
+            read_export(&zip(&[("notes.txt", "hi")]), today()).unwrap_err(),
+            ImportError::UnknownFormat
+        );
+        assert!(matches!(
+            read_export(b"[{\"title\": 3}]", today()),
+            Err(ImportError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("Don't Panic: A Guide"), "dont-panic-a-guide");
+        assert_eq!(slugify("Rust 2024 — What's new?"), "rust-2024-whats-new");
+        assert_eq!(slugify("日本語"), "imported-post");
+        let long = slugify(&"word ".repeat(40));
+        assert!(long.len() <= 80);
+        assert!(cv::models::blog::is_valid_slug(&long));
+    }
+
+    #[test]
+    fn test_slugs_are_unique() {
+        let mut export = Export {
+            drafts: vec![
+                draft("hello", "Hello"),
+                draft("intro", "Intro"),
+                draft("intro", "Intro again"),
+            ],
+            skipped: Vec::new(),
+        };
+        assign_slugs(&mut export, &["hello".to_string()]);
+        let slugs: Vec<&str> = export.drafts.iter().map(|d| d.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["intro", "intro-2"]);
+        assert_eq!(
+            export.skipped,
+            vec!["Hello: a post with this slug already exists"]
+        );
+    }
+
+    #[test]
+    fn test_image_urls() {
+        let markdown = "![a](https://x.example/a.png)\n\n![b](/media/local.png) ![c](http://x.example/c.jpg \"C\")\n\n`![d](https://x.example/d.png)`\n\n![a again](https://x.example/a.png)";
+        assert_eq!(
+            image_urls(markdown),
+            vec!["https://x.example/a.png", "http://x.example/c.jpg"]
+        );
+        let replaced = import::replace_image_urls(
+            markdown,
+            &HashMap::from([
+                (
+                    "https://x.example/a.png".to_string(),
+                    "/media/a.png".to_string(),
+                ),
+                (
+                    "http://x.example/c.jpg".to_string(),
+                    "/media/c.png".to_string(),
+                ),
+            ]),
+        );
+        assert!(replaced.starts_with("![a](/media/a.png)"));
+        assert!(replaced.contains("![c](/media/c.png \"C\")"));
+        assert!(replaced.contains("![a again](/media/a.png)"));
+    }
+
+    /// A local HTTP server answering `/image.png` with a small PNG and
+    /// anything else with a 404.
+    fn image_server() -> String {
+        let mut png = Vec::new();
+        image::RgbImage::from_pixel(8, 6, image::Rgb([200, 80, 20]))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                let (status, body) = if request_line.contains(" /image.png ") {
+                    (200, png.clone())
+                } else {
+                    (404, Vec::new())
+                };
+                let head = format!(
+                    "HTTP/1.1 {} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+        base
+    }
+
+    #[test]
+    fn test_images_are_copied() {
+        let base = image_server();
+        let dir = std::env::temp_dir().join(format!("cv-import-test-{}", std::process::id()));
+        let mut drafts = vec![
+            PostDraft {
+                body: format!("![one]({base}/image.png)\n\n![gone]({base}/missing.png)"),
+                ..draft("one", "One")
+            },
+            PostDraft {
+                body: format!("![same]({base}/image.png)"),
+                ..draft("two", "Two")
+            },
+        ];
+        let client = reqwest::Client::new();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let warnings = runtime.block_on(localize_images(&client, &mut drafts, &dir, false));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("missing.png"));
+        assert!(image_urls(&drafts[1].body).is_empty());
+        assert!(drafts[0].body.starts_with("![one](/media/"));
+        assert!(drafts[0].body.contains("/missing.png)"));
+        assert_eq!(
+            drafts[0]
+                .body
+                .lines()
+                .next()
+                .unwrap()
+                .replace("one", "same"),
+            drafts[1].body
+        );
+        let stored = drafts[1].body["![same](/media/".len()..drafts[1].body.len() - 1].to_string();
+        assert!(dir.join(&stored).exists());
+
+        // Local addresses are not fetched for real imports
+        let mut drafts = vec![PostDraft {
+            body: format!("![one]({base}/image.png)"),
+            ..draft("three", "Three")
+        }];
+        let warnings = runtime.block_on(localize_images(&client, &mut drafts, &dir, true));
+        assert!(warnings[0].contains("not a public URL"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_save_imported_posts() {
+        let conn = conn();
+        let mut published = draft("imported", "Imported");
+        published.status = PostStatus::Published;
+        published.canonical_url = "https://dev.to/me/imported".to_string();
+        let mut invalid = draft("bad", "Bad");
+        invalid.canonical_url = "dev.to/me/bad".to_string();
+        let mut export = Export {
+            drafts: vec![published, draft("unfinished", "Unfinished"), invalid],
+            skipped: Vec::new(),
+        };
+
+        let saved = import::save(&conn, &mut export, "me@example.com", 100).unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(export.skipped.len(), 1);
+        assert!(export.skipped[0].starts_with("Bad: canonical_url"));
+
+        let source = posts::get(&conn, "imported").unwrap().unwrap().source;
+        assert!(source.contains("canonical_url: https://dev.to/me/imported\n"));
+        assert!(posts::get(&conn, "bad").unwrap().is_none());
+        assert_eq!(posts::revisions(&conn, "imported").unwrap().len(), 1);
+
+        // Imported posts never send webmentions; drafts can once published here
+        assert!(!webmentions::mark_handled(&conn, "imported", 200).unwrap());
+        assert!(webmentions::mark_handled(&conn, "unfinished", 200).unwrap());
+    }
+}
@@ -142,7 +142,7 @@ fn test_route_layout_integration() {
 #[test]
 fn test_view_error_handling() {
     // Test view error handling
-    let _views = vec![rsx! { Home {} }, rsx! { Blog {} }, rsx! { Profile {} }];
+    let _views = [rsx! { Home {} }, rsx! { Blog {} }, rsx! { Profile {} }];
 
     // Test that all views can be created without errors
     // This tests compilation and basic creation
@@ -0,0 +1,139 @@
+use cv::models::cv::Cv;
+use cv::models::job_match::{tokenize, MatchStatus, SkillDictionary};
+
+fn dictionary() -> SkillDictionary {
+    SkillDictionary::from_toml(include_str!("../content/skills.toml"))
+        .expect("bundled skill dictionary should be valid")
+}
+
+fn bundled_cv() -> Cv {
+    Cv::from_toml(include_str!("../content/cv.toml")).unwrap()
+}
+
+#[test]
+fn test_tokenize_keeps_skill_punctuation() {
+    assert_eq!(
+        tokenize("Experience with C++, Node.js and CI/CD."),
+        vec!["experience", "with", "c++", "node.js", "and", "ci/cd"]
+    );
+}
+
+#[test]
+fn test_extract_uses_synonyms_and_counts_mentions() {
+    let dict = dictionary();
+    let found = dict.extract("We use Postgres. Strong PostgreSQL and golang skills; k8s a plus.");
+
+    assert_eq!(found.get("postgresql"), Some(&2));
+    assert_eq!(found.get("go"), Some(&1));
+    assert_eq!(found.get("kubernetes"), Some(&1));
+}
+
+#[test]
+fn test_extract_prefers_longest_phrase() {
+    let dict = dictionary();
+    let found = dict.extract("Background in natural language processing");
+
+    assert_eq!(found.get("nlp"), Some(&1));
+    assert_eq!(found.len(), 1);
+}
+
+#[test]
+fn test_extract_ignores_common_words() {
+    let dict = dictionary();
+    let found = dict.extract("You will go above and beyond with the rest of the team");
+    assert!(found.is_empty());
+}
+
+#[test]
+fn test_report_classifies_skills() {
+    let dict = dictionary();
+    let report = dict.report(
+        "Senior Rust engineer. PostgreSQL experience required. Kubernetes is a must.",
+        &bundled_cv(),
+    );
+
+    assert!(report.matched.iter().any(|s| s.id == "rust"));
+    let postgres = report
+        .partial
+        .iter()
+        .find(|s| s.id == "postgresql")
+        .unwrap();
+    assert_eq!(postgres.status, MatchStatus::Partial);
+    assert!(postgres.via.contains(&"SQLite".to_string()));
+    // Docker is related to Kubernetes, so it is a partial match as well
+    assert!(report.partial.iter().any(|s| s.id == "kubernetes"));
+    assert!(report.missing.is_empty());
+}
+
+#[test]
+fn test_report_score() {
+    let dict = dictionary();
+    let report = dict.report(
+        "Rust, Python and Haskell and COBOL and Terraform",
+        &bundled_cv(),
+    );
+
+    // Rust and Python match, Terraform is missing (Haskell and COBOL are unknown)
+    assert_eq!(report.matched.len(), 2);
+    assert_eq!(report.missing.len(), 1);
+    assert_eq!(report.score, 67);
+}
+
+#[test]
+fn test_report_suggests_best_variant() {
+    let dict = dictionary();
+    let cv = bundled_cv();
+
+    let ml = dict.report(
+        "We are hiring an ML engineer: PyTorch, LLMs, RAG pipelines and Python.",
+        &cv,
+    );
+    assert_eq!(ml.suggested_variant().unwrap().slug, "ml-engineer");
+    assert!(!ml.suggested_variant().unwrap().public);
+
+    let backend = dict.report(
+        "Backend role: Rust services, SQLite/SQL, OAuth2 login, Docker deployments.",
+        &cv,
+    );
+    assert_eq!(backend.suggested_variant().unwrap().slug, "backend");
+    assert!(backend.suggested_variant().unwrap().public);
+}
+
+#[test]
+fn test_report_empty_description() {
+    let dict = dictionary();
+    let report = dict.report("", &bundled_cv());
+
+    assert_eq!(report.score, 0);
+    assert!(report.matched.is_empty());
+    assert!(report.suggested_variant().is_none());
+}
+
+#[test]
+fn test_dictionary_rejects_conflicting_synonyms() {
+    let source = r#"
+        [[skills]]
+        id = "a"
+        name = "A"
+        synonyms = ["same"]
+
+        [[skills]]
+        id = "b"
+        name = "B"
+        synonyms = ["Same"]
+    "#;
+    let err = SkillDictionary::from_toml(source).unwrap_err();
+    assert!(err.contains("same"));
+}
+
+#[test]
+fn test_dictionary_rejects_unknown_related_skill() {
+    let source = r#"
+        [[skills]]
+        id = "a"
+        name = "A"
+        synonyms = ["a"]
+        related = ["missing"]
+    "#;
+    assert!(SkillDictionary::from_toml(source).is_err());
+}
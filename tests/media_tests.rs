@@ -0,0 +1,210 @@
+use cv::models::blog::render_markdown;
+use cv::models::media::{is_media_file, MediaImage, VariantFormat};
+
+fn image(width: u32, height: u32) -> MediaImage {
+    MediaImage {
+        hash: "0123456789abcdef".to_string(),
+        width,
+        height,
+        extension: "jpg".to_string(),
+    }
+}
+
+#[test]
+fn test_media_url_round_trips() {
+    let photo = image(3000, 2000);
+    assert_eq!(photo.url(), "/media/0123456789abcdef-3000x2000.jpg");
+    assert_eq!(MediaImage::from_url(&photo.url()), Some(photo));
+
+    for url in [
+        "/media/0123456789abcdef-3000x2000.gif",
+        "/media/0123456789ABCDEF-3000x2000.jpg",
+        "/media/0123456789abcde-3000x2000.jpg",
+        "/media/0123456789abcdef-03000x2000.jpg",
+        "/media/0123456789abcdef-3000x.jpg",
+        "/media/0123456789abcdef-99999x2000.jpg",
+        "/images/0123456789abcdef-3000x2000.jpg",
+        "https://example.com/media/0123456789abcdef-3000x2000.jpg",
+    ] {
+        assert_eq!(MediaImage::from_url(url), None, "{}", url);
+    }
+}
+
+#[test]
+fn test_variants_are_never_scaled_up() {
+    assert_eq!(image(3000, 2000).widths(), vec![480, 960, 1440, 1920]);
+    assert_eq!(image(1000, 800).widths(), vec![480, 960, 1000]);
+    assert_eq!(image(960, 800).widths(), vec![480, 960]);
+    assert_eq!(image(200, 100).widths(), vec![200]);
+    assert_eq!(
+        image(1000, 800).srcset(VariantFormat::Webp),
+        "/media/0123456789abcdef-480.webp 480w, /media/0123456789abcdef-960.webp 960w, /media/0123456789abcdef-1000.webp 1000w"
+    );
+}
+
+#[test]
+fn test_only_media_files_are_served() {
+    assert!(is_media_file("0123456789abcdef-3000x2000.jpg"));
+    assert!(is_media_file("0123456789abcdef-480.avif"));
+    assert!(is_media_file("0123456789abcdef-480.webp"));
+    assert!(!is_media_file("0123456789abcdef-480.png"));
+    assert!(!is_media_file("../cv.db"));
+    assert!(!is_media_file(".0123456789abcdef-480.webp.part"));
+    assert!(!is_media_file("0123456789abcdef-480.webp/../../cv.db"));
+}
+
+#[test]
+fn test_uploaded_images_render_with_srcset() {
+    let html =
+        render_markdown("![A \"quoted\" *view*](/media/0123456789abcdef-1000x800.jpg \"Title\")");
+    assert!(html.starts_with(
+        "<p><picture><source type=\"image/avif\" srcset=\"/media/0123456789abcdef-480.avif 480w"
+    ));
+    assert!(
+        html.contains("<source type=\"image/webp\" srcset=\"/media/0123456789abcdef-480.webp 480w")
+    );
+    assert!(html.contains("sizes=\""));
+    assert!(html.contains(
+        "<img src=\"/media/0123456789abcdef-1000x800.jpg\" alt=\"A &quot;quoted&quot; view\" width=\"1000\" height=\"800\""
+    ));
+    assert!(html.contains("title=\"Title\""));
+    assert!(html.contains("</picture></p>"));
+
+    // Other images are left alone
+    let html = render_markdown("![Logo](/logo.png)");
+    assert_eq!(html, "<p><img src=\"/logo.png\" alt=\"Logo\" /></p>\n");
+}
+
+#[test]
+fn test_image_markdown_keeps_alt_text_inside_brackets() {
+    assert_eq!(
+        image(10, 10).markdown("photo [1]"),
+        "![photo 1](/media/0123456789abcdef-10x10.jpg)"
+    );
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use cv::backend::media::{process, store, MediaError};
+    use cv::models::media::{MediaImage, MAX_DIMENSION, MAX_UPLOAD_BYTES};
+    use image::codecs::jpeg::JpegEncoder;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use std::io::Cursor;
+
+    const GPS_MARKER: &[u8] = b"GPSLatitude 52.3676N";
+
+    /// A JPEG carrying an EXIF block with `orientation` and some GPS text.
+    fn jpeg_with_exif(width: u32, height: u32, orientation: u16) -> Vec<u8> {
+        let pixels = RgbImage::from_fn(width, height, |x, y| {
+            image::Rgb([(x * 5) as u8, (y * 5) as u8, 128])
+        });
+        let mut jpeg = Vec::new();
+        DynamicImage::ImageRgb8(pixels)
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut jpeg, 90))
+            .unwrap();
+
+        // Big-endian TIFF with one IFD entry: Orientation (SHORT)
+        let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08".to_vec();
+        exif.extend_from_slice(&[0, 1, 0x01, 0x12, 0, 3, 0, 0, 0, 1]);
+        exif.extend_from_slice(&orientation.to_be_bytes());
+        exif.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        exif.extend_from_slice(GPS_MARKER);
+
+        let mut out = jpeg[..2].to_vec();
+        out.extend_from_slice(&[0xff, 0xe1]);
+        out.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+        out.extend_from_slice(&exif);
+        out.extend_from_slice(&jpeg[2..]);
+        out
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn test_upload_is_stripped_and_rotated() {
+        let upload = jpeg_with_exif(40, 20, 6);
+        assert!(contains(&upload, GPS_MARKER));
+
+        let processed = process(&upload).unwrap();
+        let image = &processed.image;
+        // Orientation 6 is a quarter turn, applied to the pixels
+        assert_eq!((image.width, image.height), (20, 40));
+        assert_eq!(image.extension, "jpg");
+        assert_eq!(image.hash.len(), 16);
+
+        let names: Vec<&str> = processed.files.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                image.file_name(),
+                format!("{}-20.avif", image.hash),
+                format!("{}-20.webp", image.hash)
+            ]
+        );
+        for (name, data) in &processed.files {
+            assert!(!contains(data, GPS_MARKER), "{}", name);
+            assert!(!contains(data, b"Exif\0\0"), "{}", name);
+        }
+        let (_, original) = &processed.files[0];
+        assert_eq!(image::guess_format(original).unwrap(), ImageFormat::Jpeg);
+    }
+
+    #[test]
+    fn test_type_is_taken_from_the_content() {
+        assert_eq!(
+            process(b"%PDF-1.7 not an image").err(),
+            Some(MediaError::UnsupportedType)
+        );
+
+        let gif = b"GIF89a\x01\0\x01\0\0\0\0;";
+        assert_eq!(process(gif).err(), Some(MediaError::UnsupportedType));
+
+        let mut png = Vec::new();
+        image::RgbaImage::new(8, 4)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let processed = process(&png).unwrap();
+        assert_eq!(processed.image.extension, "png");
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let mut oversized = jpeg_with_exif(8, 8, 1);
+        oversized.resize(MAX_UPLOAD_BYTES + 1, 0);
+        assert_eq!(process(&oversized).err(), Some(MediaError::TooLarge));
+
+        // Small files can still decode to huge images
+        let mut png = Vec::new();
+        image::GrayImage::new(MAX_DIMENSION + 1, 1)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        assert!(png.len() < 1024);
+        assert_eq!(process(&png).err(), Some(MediaError::TooLarge));
+    }
+
+    #[test]
+    fn test_store_writes_every_file_once() {
+        let dir = std::env::temp_dir().join(format!("cv-media-test-{}", std::process::id()));
+        let upload = jpeg_with_exif(16, 16, 1);
+
+        let image = store(&dir, &upload).unwrap();
+        assert_eq!(store(&dir, &upload).unwrap(), image);
+        assert_eq!(MediaImage::from_url(&image.url()).as_ref(), Some(&image));
+        let mut files: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                format!("{}-16.avif", image.hash),
+                format!("{}-16.webp", image.hash),
+                image.file_name()
+            ]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
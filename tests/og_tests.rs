@@ -0,0 +1,102 @@
+#[cfg(feature = "server")]
+mod server {
+    use cv::backend::og::{cached, card, png, render, Card, BACKGROUND};
+    use cv::models::cv::master;
+    use cv::models::seo::{OgKind, MAIN_CV, OG_HEIGHT, OG_WIDTH};
+
+    fn post(title: &str) -> Card {
+        Card {
+            kind: OgKind::Post,
+            title: title.to_string(),
+            author: "Taian Feitosa".to_string(),
+            date: Some("January 31, 2025".to_string()),
+            tags: vec!["rust".to_string(), "dioxus".to_string()],
+            site: "example.com".to_string(),
+        }
+    }
+
+    fn files(dir: &std::path::Path) -> Vec<String> {
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_render_size_and_theme() {
+        let image = render(&post("Hello, Dioxus"));
+        assert_eq!(image.dimensions(), (OG_WIDTH, OG_HEIGHT));
+        assert_eq!(*image.get_pixel(OG_WIDTH - 1, 0), BACKGROUND);
+        assert_eq!(*image.get_pixel(OG_WIDTH - 1, OG_HEIGHT - 1), BACKGROUND);
+        assert_ne!(*image.get_pixel(0, OG_HEIGHT / 2), BACKGROUND);
+
+        let bytes = png(&post("Hello, Dioxus")).unwrap();
+        let decoded = image::load_from_memory(&bytes).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (OG_WIDTH, OG_HEIGHT));
+    }
+
+    #[test]
+    fn test_text_is_drawn() {
+        let hello = render(&post("Hello"));
+        let world = render(&post("World"));
+        assert_ne!(hello, world);
+        assert!(hello.pixels().any(|pixel| pixel.0 == [0xff, 0xff, 0xff]));
+    }
+
+    #[test]
+    fn test_long_text_stays_inside() {
+        let mut card = post(&"Supercalifragilistic words keep going ".repeat(20));
+        card.tags = (0..40).map(|i| format!("tag{}", i)).collect();
+        card.author = "A".repeat(200);
+        let image = render(&card);
+        // Nothing is drawn in the right margin
+        for y in 0..OG_HEIGHT {
+            for x in OG_WIDTH - 60..OG_WIDTH {
+                assert_eq!(*image.get_pixel(x, y), BACKGROUND, "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cache_is_reused_and_replaced() {
+        let dir = std::env::temp_dir().join(format!("cv-og-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let first = cached(&dir, "hello", &post("Hello")).unwrap();
+        assert_eq!(files(&dir).len(), 1);
+        assert_eq!(cached(&dir, "hello", &post("Hello")).unwrap(), first);
+        assert_eq!(files(&dir).len(), 1);
+        cached(&dir, "hello-world", &post("Hello, world")).unwrap();
+
+        // A new title renders again and only this post's old file goes away
+        let edited = cached(&dir, "hello", &post("Hello again")).unwrap();
+        assert_ne!(edited, first);
+        let names = files(&dir);
+        assert_eq!(names.len(), 2, "{:?}", names);
+        assert!(names[0].starts_with("post-hello-") && names[0].ends_with(".png"));
+        assert!(names[1].starts_with("post-hello-world-"));
+        assert!(names.iter().all(|name| !name.starts_with('.')));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cv_cards() {
+        let cv = master();
+        let main = card(OgKind::Cv, MAIN_CV).unwrap();
+        assert_eq!(main.title, cv.basics.label);
+        assert_eq!(main.author, cv.basics.full_name());
+        assert_eq!(main.date, None);
+        assert!(main.tags.len() <= 5);
+
+        if let Some(variant) = cv.variants.iter().find(|variant| variant.public) {
+            assert!(card(OgKind::Cv, &variant.slug).is_some());
+        }
+        if let Some(variant) = cv.variants.iter().find(|variant| !variant.public) {
+            assert_eq!(card(OgKind::Cv, &variant.slug), None);
+        }
+        assert_eq!(card(OgKind::Cv, "no-such-variant"), None);
+    }
+}
@@ -0,0 +1,195 @@
+use cv::models::reactions::{PostStats, Reaction};
+
+#[test]
+fn test_reaction_round_trips() {
+    for reaction in Reaction::ALL {
+        assert_eq!(Reaction::parse(reaction.as_str()), Some(reaction));
+        assert_eq!(
+            serde_json::to_string(&reaction).unwrap(),
+            format!("\"{}\"", reaction.as_str())
+        );
+    }
+    assert_eq!(Reaction::parse("thumbs-down"), None);
+}
+
+#[test]
+fn test_stats_counts_and_summary() {
+    let stats = PostStats {
+        slug: "hello".to_string(),
+        views: 12,
+        reactions: vec![(Reaction::Rocket, 3), (Reaction::Crab, 1)],
+        mine: None,
+    };
+    assert_eq!(stats.count(Reaction::Rocket), 3);
+    assert_eq!(stats.count(Reaction::Heart), 0);
+    assert_eq!(stats.reactions_summary(), "🚀 3 🦀 1");
+    assert_eq!(PostStats::default().reactions_summary(), "");
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use cv::backend::db;
+    use cv::backend::reactions::{
+        load_stats, reaction_of, reader_key, record_view, set_reaction, StatsCache,
+    };
+    use cv::backend::session::{client_ip_from, decode_reader, encode_reader, visitor_id};
+    use cv::backend::throttle::Throttle;
+    use cv::models::reactions::{PostStats, Reaction};
+    use dioxus::fullstack::http::{Extensions, HeaderMap};
+    use dioxus::server::axum::extract::ConnectInfo;
+    use rusqlite::Connection;
+    use std::net::SocketAddr;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_views_count_once_per_reader_and_day() {
+        let conn = conn();
+        let reader = reader_key("visitor:abc");
+        let other = reader_key("user:reader@example.com");
+
+        assert!(record_view(&conn, "views", &reader, 10 * DAY).unwrap());
+        assert!(!record_view(&conn, "views", &reader, 10 * DAY + 3_600).unwrap());
+        assert!(record_view(&conn, "views", &other, 10 * DAY + 3_600).unwrap());
+        assert!(record_view(&conn, "views", &reader, 11 * DAY).unwrap());
+        record_view(&conn, "other-post", &reader, 11 * DAY).unwrap();
+
+        assert_eq!(load_stats(&conn, "views").unwrap().views, 3);
+        assert_eq!(load_stats(&conn, "other-post").unwrap().views, 1);
+    }
+
+    #[test]
+    fn test_one_reaction_per_reader() {
+        let conn = conn();
+        let reader = reader_key("visitor:abc");
+        let other = reader_key("visitor:def");
+
+        set_reaction(&conn, "reacted", &reader, Some(Reaction::Rocket), 100).unwrap();
+        set_reaction(&conn, "reacted", &other, Some(Reaction::Rocket), 110).unwrap();
+        // A new reaction replaces the old one
+        set_reaction(&conn, "reacted", &reader, Some(Reaction::Crab), 120).unwrap();
+        assert_eq!(
+            reaction_of(&conn, "reacted", &reader).unwrap(),
+            Some(Reaction::Crab)
+        );
+        assert_eq!(
+            load_stats(&conn, "reacted").unwrap().reactions,
+            vec![(Reaction::Rocket, 1), (Reaction::Crab, 1)]
+        );
+
+        set_reaction(&conn, "reacted", &other, None, 130).unwrap();
+        assert_eq!(reaction_of(&conn, "reacted", &other).unwrap(), None);
+        assert_eq!(
+            load_stats(&conn, "reacted").unwrap().reactions,
+            vec![(Reaction::Crab, 1)]
+        );
+    }
+
+    #[test]
+    fn test_readers_are_hashed() {
+        let key = reader_key("user:reader@example.com");
+        assert_eq!(key.len(), 64);
+        assert!(!key.contains("reader"));
+        assert_eq!(key, reader_key("user:reader@example.com"));
+        assert_ne!(key, reader_key("visitor:reader@example.com"));
+    }
+
+    #[test]
+    fn test_cache_serves_counts_until_they_expire() {
+        let cache = StatsCache::new(60);
+        let counts = |views| PostStats {
+            slug: "cached".to_string(),
+            views,
+            ..PostStats::default()
+        };
+        let load = |views| move || Ok::<_, ()>(counts(views));
+
+        assert_eq!(
+            cache.get_or_load("cached", 1_000, load(5)).unwrap().views,
+            5
+        );
+        assert_eq!(
+            cache.get_or_load("cached", 1_059, load(9)).unwrap().views,
+            5
+        );
+        cache.add_view("cached");
+        assert_eq!(
+            cache.get_or_load("cached", 1_059, load(9)).unwrap().views,
+            6
+        );
+        assert_eq!(
+            cache.get_or_load("cached", 1_060, load(9)).unwrap().views,
+            9
+        );
+
+        cache.invalidate("cached");
+        assert_eq!(
+            cache.get_or_load("cached", 1_061, load(2)).unwrap().views,
+            2
+        );
+        // Failed loads are not cached
+        assert_eq!(cache.get_or_load("failing", 1_000, || Err(())), Err(()));
+
+        // Posts nobody has seen yet are read every time
+        assert_eq!(cache.get_or_load("new", 1_000, load(0)).unwrap().views, 0);
+        assert_eq!(cache.get_or_load("new", 1_001, load(1)).unwrap().views, 1);
+    }
+
+    #[test]
+    fn test_visitor_ids_ignore_cookies_and_change_daily() {
+        let secret = b"secret";
+        let id = visitor_id(secret, "203.0.113.7", "curl/8.0", 100);
+
+        assert_eq!(id, visitor_id(secret, "203.0.113.7", "curl/8.0", 100));
+        assert!(!id.contains("203.0.113.7"));
+        assert_ne!(id, visitor_id(secret, "203.0.113.7", "curl/8.0", 101));
+        assert_ne!(id, visitor_id(secret, "203.0.113.8", "curl/8.0", 100));
+        assert_ne!(id, visitor_id(b"other", "203.0.113.7", "curl/8.0", 100));
+    }
+
+    #[test]
+    fn test_reader_cookies_are_signed() {
+        let secret = b"secret";
+        let value = encode_reader(secret, "0123abcd");
+
+        assert_eq!(decode_reader(secret, &value).as_deref(), Some("0123abcd"));
+        assert_eq!(decode_reader(b"other", &value), None);
+        assert_eq!(
+            decode_reader(secret, &value.replace("0123abcd", "0123abce")),
+            None
+        );
+        assert_eq!(decode_reader(secret, "0123abcd"), None);
+    }
+
+    #[test]
+    fn test_client_ip_ignores_forwarding_headers_without_a_trusted_proxy() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "198.51.100.1".parse().unwrap());
+        headers.insert("x-real-ip", "198.51.100.2".parse().unwrap());
+        let mut extensions = Extensions::new();
+
+        assert_eq!(client_ip_from(&headers, &extensions), None);
+        let peer: SocketAddr = "203.0.113.7:51234".parse().unwrap();
+        extensions.insert(ConnectInfo(peer));
+        assert_eq!(client_ip_from(&headers, &extensions), Some(peer.ip()));
+    }
+
+    #[test]
+    fn test_throttle_limits_hits_per_window() {
+        let throttle = Throttle::new(2, 60);
+
+        assert!(throttle.allow("203.0.113.7", 1_000));
+        assert!(throttle.allow("203.0.113.7", 1_010));
+        assert!(!throttle.allow("203.0.113.7", 1_020));
+        assert!(throttle.allow("203.0.113.8", 1_020));
+        // The first hit leaves the window
+        assert!(throttle.allow("203.0.113.7", 1_060));
+        assert!(!throttle.allow("203.0.113.7", 1_061));
+    }
+}
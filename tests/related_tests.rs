@@ -0,0 +1,95 @@
+use cv::models::blog::{parse_post, Post};
+use cv::models::related::{related_posts, terms};
+
+fn post(slug: &str, front: &str, body: &str) -> Post {
+    let source = format!(
+        "---\ntitle: {}\ndate: 2026-10-01\n{}---\n{}\n",
+        slug, front, body
+    );
+    parse_post(slug, &source).unwrap()
+}
+
+fn slugs(posts: &[cv::models::blog::PostMeta]) -> Vec<&str> {
+    posts.iter().map(|p| p.slug.as_str()).collect()
+}
+
+#[test]
+fn test_terms_skip_short_and_common_words() {
+    assert_eq!(
+        terms("The borrow checker, and YOU: a Rust/WASM story in 2026"),
+        vec!["borrow", "checker", "rust", "wasm", "story", "2026"]
+    );
+}
+
+#[test]
+fn test_related_by_tags_and_text() {
+    let current = post(
+        "axum-routing",
+        "tags: [Rust, Web]\n",
+        "Routing requests in axum with extractors and handlers.",
+    );
+    let candidates = vec![
+        current.clone(),
+        post(
+            "baking",
+            "tags: [Food]\n",
+            "Sourdough bread needs a starter.",
+        ),
+        post(
+            "axum-state",
+            "tags: [rust]\n",
+            "Sharing state between axum handlers and extractors.",
+        ),
+        post(
+            "rust-cli",
+            "tags: [Rust]\n",
+            "Parsing arguments for a command line tool.",
+        ),
+        post(
+            "tower-layers",
+            "",
+            "Middleware layers wrap axum handlers: extractors and routing stay the same.",
+        ),
+    ];
+
+    let related = related_posts(&current, &candidates, 3);
+    // Shared tags and shared words beat either one alone; unrelated posts
+    // and the post itself never show up
+    assert_eq!(
+        slugs(&related),
+        vec!["axum-state", "rust-cli", "tower-layers"]
+    );
+    assert_eq!(
+        slugs(&related_posts(&current, &candidates, 1)),
+        vec!["axum-state"]
+    );
+}
+
+#[test]
+fn test_related_leaves_out_the_series() {
+    let current = post(
+        "part-one",
+        "tags: [Rust]\nseries: Rust Web\n",
+        "Axum handlers",
+    );
+    let candidates = vec![
+        post(
+            "part-two",
+            "tags: [Rust]\nseries: rust web\n",
+            "Axum handlers",
+        ),
+        post("other", "tags: [Rust]\n", "Something else entirely"),
+    ];
+    assert_eq!(
+        slugs(&related_posts(&current, &candidates, 3)),
+        vec!["other"]
+    );
+}
+
+#[test]
+fn test_nothing_related() {
+    let current = post("alone", "", "Quantum gardening");
+    let candidates = vec![post("other", "tags: [Food]\n", "Sourdough bread")];
+    assert!(related_posts(&current, &candidates, 3).is_empty());
+    assert!(related_posts(&current, &[], 3).is_empty());
+}
@@ -0,0 +1,141 @@
+use cv::models::revisions::{diff_lines, DiffKind, DiffLine};
+
+/// The diff in unified-diff shorthand: one `' '`, `'+'` or `'-'` prefixed
+/// line per entry.
+fn render(lines: &[DiffLine]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            let sign = match line.kind {
+                DiffKind::Same => ' ',
+                DiffKind::Added => '+',
+                DiffKind::Removed => '-',
+            };
+            format!("{}{}", sign, line.text)
+        })
+        .collect()
+}
+
+#[test]
+fn test_identical_texts_have_no_changes() {
+    let lines = diff_lines("a\nb\n", "a\nb\n");
+    assert_eq!(render(&lines), vec![" a", " b"]);
+}
+
+#[test]
+fn test_insertions_and_deletions() {
+    assert_eq!(
+        render(&diff_lines("a\nc", "a\nb\nc")),
+        vec![" a", "+b", " c"]
+    );
+    assert_eq!(
+        render(&diff_lines("a\nb\nc", "a\nc")),
+        vec![" a", "-b", " c"]
+    );
+    assert_eq!(render(&diff_lines("", "a\nb")), vec!["+a", "+b"]);
+    assert_eq!(render(&diff_lines("a\nb", "")), vec!["-a", "-b"]);
+}
+
+#[test]
+fn test_changed_lines_remove_before_adding() {
+    let lines = diff_lines("title\nold one\nold two\nend", "title\nnew one\nend");
+    assert_eq!(
+        render(&lines),
+        vec![" title", "-old one", "-old two", "+new one", " end"]
+    );
+}
+
+#[test]
+fn test_diff_keeps_the_longest_common_subsequence() {
+    let lines = diff_lines("a\nb\nc\nd\ne", "b\nx\nd\ne\nf");
+    assert_eq!(
+        render(&lines),
+        vec!["-a", " b", "-c", "+x", " d", " e", "+f"]
+    );
+}
+
+#[test]
+fn test_diff_line_numbers() {
+    let lines = diff_lines("a\nb\nc", "a\nx\nc");
+    let numbers: Vec<(Option<usize>, Option<usize>)> =
+        lines.iter().map(|l| (l.old_line, l.new_line)).collect();
+    assert_eq!(
+        numbers,
+        vec![
+            (Some(1), Some(1)),
+            (Some(2), None),
+            (None, Some(2)),
+            (Some(3), Some(3))
+        ]
+    );
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use cv::backend::{db, posts};
+    use cv::models::revisions::FILE_AUTHOR;
+    use rusqlite::Connection;
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_every_save_is_a_revision() {
+        let conn = conn();
+        posts::save(&conn, "a", "one", "me@example.com", 100).unwrap();
+        posts::save(&conn, "a", "two", "you@example.com", 200).unwrap();
+        posts::save(&conn, "b", "other", "me@example.com", 300).unwrap();
+
+        let revisions = posts::revisions(&conn, "a").unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].author, "you@example.com");
+        assert_eq!(revisions[0].created_at, 200);
+        assert_eq!(revisions[0].hash, posts::content_hash("two"));
+        assert_eq!(revisions[0].hash.len(), 64);
+        assert_eq!(
+            posts::revision_source(&conn, "a", revisions[1].id).unwrap(),
+            Some("one".to_string())
+        );
+        // Revisions belong to one post
+        assert_eq!(
+            posts::revision_source(&conn, "b", revisions[1].id).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_file_version_is_kept_once() {
+        let conn = conn();
+        posts::record_file_revision(&conn, "a", "from file", 100).unwrap();
+        posts::save(&conn, "a", "edited", "me@example.com", 200).unwrap();
+        posts::record_file_revision(&conn, "a", "from file again", 300).unwrap();
+
+        let revisions = posts::revisions(&conn, "a").unwrap();
+        let authors: Vec<&str> = revisions.iter().map(|r| r.author.as_str()).collect();
+        assert_eq!(authors, vec!["me@example.com", FILE_AUTHOR]);
+    }
+}
+
+#[test]
+fn test_large_rewrites_fall_back_to_block_replace() {
+    let old: Vec<String> = (0..1500).map(|i| format!("old {}", i)).collect();
+    let mut new: Vec<String> = (0..1500).map(|i| format!("new {}", i)).collect();
+    new[700] = "old 700".to_string();
+    let lines = diff_lines(
+        &format!("intro\n{}\nend", old.join("\n")),
+        &format!("intro\n{}\nend", new.join("\n")),
+    );
+
+    let kinds: Vec<DiffKind> = lines.iter().map(|line| line.kind).collect();
+    assert_eq!(lines.len(), 2 + 1500 + 1500);
+    assert_eq!(kinds[0], DiffKind::Same);
+    assert!(kinds[1..1501].iter().all(|&kind| kind == DiffKind::Removed));
+    assert!(kinds[1501..3001]
+        .iter()
+        .all(|&kind| kind == DiffKind::Added));
+    assert_eq!(kinds[3001], DiffKind::Same);
+    assert_eq!(lines[3001].old_line, Some(1502));
+}
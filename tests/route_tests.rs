@@ -24,6 +24,89 @@ fn test_route_auth_callback_creation() {
     assert_eq!(format!("{:?}", route), "AuthCallback");
 }
 
+#[test]
+fn test_route_cv_variant_creation() {
+    let route = Route::CvVariant {
+        variant: "backend".to_string(),
+    };
+    assert_eq!(route.to_string(), "/cv/backend");
+}
+
+#[test]
+fn test_route_shared_cv_creation() {
+    let route = Route::SharedCv {
+        token: "abc.1700000000.sig".to_string(),
+    };
+    assert_eq!(route.to_string(), "/s/abc.1700000000.sig");
+}
+
+#[test]
+fn test_route_blog_post_creation() {
+    let route = Route::BlogPost {
+        slug: "hello-dioxus".to_string(),
+    };
+    assert_eq!(route.to_string(), "/blog/hello-dioxus");
+    assert_eq!(
+        "/blog/hello-dioxus".parse::<Route>().unwrap(),
+        Route::BlogPost {
+            slug: "hello-dioxus".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_route_blog_tag_creation() {
+    let route = Route::BlogTag {
+        tag: "rust".to_string(),
+    };
+    assert_eq!(route.to_string(), "/blog/tag/rust");
+    assert_eq!("/blog/tag/rust".parse::<Route>().unwrap(), route);
+}
+
+#[test]
+fn test_route_blog_pagination_and_archives() {
+    assert_eq!(Route::BlogPage { page: 2 }.to_string(), "/blog/page/2");
+    assert_eq!(
+        Route::BlogArchive {
+            year: 2026,
+            month: 10
+        }
+        .to_string(),
+        "/blog/2026/10"
+    );
+    assert_eq!(
+        "/blog/page/2".parse::<Route>().unwrap(),
+        Route::BlogPage { page: 2 }
+    );
+    assert_eq!(
+        "/blog/2026/10".parse::<Route>().unwrap(),
+        Route::BlogArchive {
+            year: 2026,
+            month: 10
+        }
+    );
+    // Tag archives share the shape of date archives
+    assert_eq!(
+        "/blog/tag/rust".parse::<Route>().unwrap(),
+        Route::BlogTag {
+            tag: "rust".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_route_search_query() {
+    let route = Route::Search {
+        q: "rust wasm".to_string(),
+    };
+    assert!(route.to_string().starts_with("/search?q=rust"));
+    assert_eq!(route.to_string().parse::<Route>().unwrap(), route);
+    assert_eq!(
+        "/search".parse::<Route>().unwrap(),
+        Route::Search { q: String::new() }
+    );
+}
+
 #[test]
 fn test_route_equality() {
     let route1 = Route::Home {};
@@ -47,11 +130,41 @@ fn test_route_clone() {
 
 #[test]
 fn test_all_routes_are_unique() {
-    let routes = vec![
+    let routes = [
         Route::Home {},
         Route::Blog {},
+        Route::BlogPost {
+            slug: "hello".to_string(),
+        },
+        Route::BlogTag {
+            tag: "hello".to_string(),
+        },
+        Route::BlogPage { page: 2 },
+        Route::BlogArchive {
+            year: 2026,
+            month: 10,
+        },
+        Route::Search {
+            q: "hello".to_string(),
+        },
         Route::Profile {},
         Route::AuthCallback {},
+        Route::CvVariant {
+            variant: "backend".to_string(),
+        },
+        Route::SharedCv {
+            token: "token".to_string(),
+        },
+        Route::AdminShareLinks {},
+        Route::JobMatch {},
+        Route::AdminPosts {},
+        Route::EditPost {
+            slug: "hello".to_string(),
+        },
+        Route::PostHistory {
+            slug: "hello".to_string(),
+        },
+        Route::AdminComments {},
     ];
 
     // Verify each route is different from the others
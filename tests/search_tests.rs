@@ -0,0 +1,125 @@
+use cv::models::blog::{parse_post, plain_text, Post};
+use cv::models::cv::{Cv, TailoredCv};
+use cv::models::search::{documents, fts_query, marked_html, SearchKind};
+
+fn post(slug: &str, title: &str, body: &str) -> Post {
+    let source = format!(
+        "---\ntitle: {}\ndate: 2025-01-01\ndescription: About {}\ntags: [Rust]\n---\n{}",
+        title, slug, body
+    );
+    parse_post(slug, &source).unwrap()
+}
+
+fn cv() -> TailoredCv {
+    Cv::from_toml(include_str!("../content/cv.toml"))
+        .unwrap()
+        .full()
+}
+
+#[test]
+fn test_fts_query_quotes_terms() {
+    assert_eq!(
+        fts_query("Rust wasm"),
+        Some("\"rust\" \"wasm\"*".to_string())
+    );
+    // Operators and quotes are searched for as words, not interpreted
+    assert_eq!(
+        fts_query("title:\"x\" OR NEAR(y)"),
+        Some("\"title\" \"x\" \"or\" \"near\" \"y\"*".to_string())
+    );
+    assert_eq!(fts_query("  ***  "), None);
+    assert_eq!(fts_query(""), None);
+}
+
+#[test]
+fn test_marked_html_escapes_before_marking() {
+    assert_eq!(
+        marked_html("<b>\u{2}Rust\u{3}</b>"),
+        "&lt;b&gt;<mark>Rust</mark>&lt;/b&gt;"
+    );
+}
+
+#[test]
+fn test_plain_text_strips_markdown() {
+    let text = plain_text("# Title\n\nSome *emphasis* and `code`.\n\n- one\n- two\n");
+    assert_eq!(text, "Title\nSome emphasis and code.\none\ntwo");
+}
+
+#[test]
+fn test_documents_cover_posts_projects_and_experience() {
+    let cv = cv();
+    let docs = documents(&[post("hello", "Hello", "Body **text**")], &cv);
+
+    let first = &docs[0];
+    assert_eq!(first.kind, SearchKind::Post);
+    assert_eq!(first.path, "/blog/hello");
+    assert!(first.body.contains("About hello"));
+    assert!(first.body.contains("Body text"));
+
+    let count = |kind| docs.iter().filter(|d| d.kind == kind).count();
+    assert_eq!(count(SearchKind::Project), cv.projects.len());
+    assert_eq!(count(SearchKind::Experience), cv.experience.len());
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use super::*;
+    use cv::backend::{db, search};
+    use rusqlite::Connection;
+
+    /// A CV with nothing indexable, so only posts are searched.
+    fn posts_only() -> TailoredCv {
+        TailoredCv {
+            experience: Vec::new(),
+            projects: Vec::new(),
+            ..cv()
+        }
+    }
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_index_is_rebuilt_only_on_change() {
+        let conn = conn();
+        let mut posts = vec![post("a", "Alpha", "first")];
+        assert!(search::ensure_index(&conn, &documents(&posts, &cv())).unwrap());
+        assert!(!search::ensure_index(&conn, &documents(&posts, &cv())).unwrap());
+
+        posts.push(post("b", "Beta", "second"));
+        assert!(search::ensure_index(&conn, &documents(&posts, &cv())).unwrap());
+        let query = fts_query("second").unwrap();
+        assert_eq!(search::search(&conn, &query, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_search_ranks_titles_and_highlights() {
+        let conn = conn();
+        let posts = vec![
+            post("body", "Notes", "Something about lifetimes in passing."),
+            post("title", "Lifetimes explained", "A long read."),
+        ];
+        search::ensure_index(&conn, &documents(&posts, &posts_only())).unwrap();
+
+        let results = search::search(&conn, &fts_query("lifetime").unwrap(), 10).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].path, "/blog/title");
+        assert_eq!(results[0].title_html, "<mark>Lifetimes</mark> explained");
+        assert!(results[1].snippet_html.contains("<mark>lifetimes</mark>"));
+    }
+
+    #[test]
+    fn test_search_is_stemmed_and_prefix_matched() {
+        let conn = conn();
+        let posts = vec![post("a", "Testing", "We were running benchmarks.")];
+        search::ensure_index(&conn, &documents(&posts, &posts_only())).unwrap();
+
+        let find = |q: &str| search::search(&conn, &fts_query(q).unwrap(), 10).unwrap();
+        assert_eq!(find("runs").len(), 1);
+        assert_eq!(find("bench").len(), 1);
+        assert!(find("python").is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+use chrono::NaiveDate;
+use cv::models::cv::{Cv, Profile};
+use cv::models::seo::{
+    full_title, meta_tags, og_image_path, MetaTag, OgKind, PageKind, PageMeta, SeoDefaults, MAIN_CV,
+};
+
+const SITE: &str = "https://example.com";
+
+fn bundled_cv() -> Cv {
+    Cv::from_toml(include_str!("../content/cv.toml")).unwrap()
+}
+
+fn defaults() -> SeoDefaults {
+    SeoDefaults {
+        site_name: "Taian Feitosa".to_string(),
+        description: "Fullstack Developer".to_string(),
+        image: "https://cdn.example/me.jpg".to_string(),
+        twitter: None,
+    }
+}
+
+/// The content of the tag named `key`, whichever attribute it uses.
+fn content<'a>(tags: &'a [MetaTag], key: &str) -> Vec<&'a str> {
+    tags.iter()
+        .filter_map(|tag| match tag {
+            MetaTag::Name(name, content) | MetaTag::Property(name, content) if *name == key => {
+                Some(content.as_str())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_defaults_from_cv() {
+    let mut basics = bundled_cv().basics;
+    let defaults = SeoDefaults::from_basics(&basics);
+    assert_eq!(defaults.site_name, "Taian Feitosa");
+    assert!(defaults.description.starts_with(&basics.label));
+    assert_eq!(defaults.image, "/og/cv/main.png");
+    assert_eq!(defaults.twitter, None);
+
+    basics.profiles.push(Profile {
+        network: "X".to_string(),
+        url: "https://x.com/taianf/".to_string(),
+        icon: "fa-x-twitter".to_string(),
+    });
+    assert_eq!(
+        SeoDefaults::from_basics(&basics).twitter.as_deref(),
+        Some("@taianf")
+    );
+}
+
+#[test]
+fn test_full_title() {
+    assert_eq!(full_title("Blog", "Taian Feitosa"), "Blog · Taian Feitosa");
+    assert_eq!(full_title("  ", "Taian Feitosa"), "Taian Feitosa");
+    assert_eq!(
+        full_title("Taian Feitosa", "Taian Feitosa"),
+        "Taian Feitosa"
+    );
+}
+
+#[test]
+fn test_page_without_details_uses_defaults() {
+    let page = PageMeta {
+        url: "https://example.com/".to_string(),
+        ..Default::default()
+    };
+    let tags = meta_tags(&page, &defaults(), SITE);
+
+    assert_eq!(content(&tags, "description"), vec!["Fullstack Developer"]);
+    assert_eq!(content(&tags, "og:type"), vec!["website"]);
+    assert_eq!(content(&tags, "og:title"), vec!["Taian Feitosa"]);
+    assert_eq!(content(&tags, "og:url"), vec!["https://example.com/"]);
+    assert_eq!(
+        content(&tags, "og:image"),
+        vec!["https://cdn.example/me.jpg"]
+    );
+    assert_eq!(content(&tags, "twitter:card"), vec!["summary_large_image"]);
+    assert!(content(&tags, "og:image:width").is_empty());
+    assert!(content(&tags, "article:author").is_empty());
+    assert!(content(&tags, "twitter:site").is_empty());
+    assert!(tags
+        .iter()
+        .all(|tag| !matches!(tag, MetaTag::Name(name, _) if name.starts_with("og:"))));
+}
+
+#[test]
+fn test_article_tags() {
+    let page = PageMeta {
+        title: "Hello".to_string(),
+        description: "First post".to_string(),
+        url: "https://dev.to/me/hello-1a2b".to_string(),
+        image: Some("/media/cover.png".to_string()),
+        kind: PageKind::Article,
+        published: NaiveDate::from_ymd_opt(2025, 1, 31),
+        tags: vec!["Rust".to_string(), "Dioxus".to_string()],
+    };
+    let defaults = SeoDefaults {
+        twitter: Some("@taianf".to_string()),
+        ..defaults()
+    };
+    let tags = meta_tags(&page, &defaults, SITE);
+
+    assert_eq!(content(&tags, "og:title"), vec!["Hello"]);
+    assert_eq!(content(&tags, "twitter:description"), vec!["First post"]);
+    assert_eq!(
+        content(&tags, "og:url"),
+        vec!["https://dev.to/me/hello-1a2b"]
+    );
+    assert_eq!(
+        content(&tags, "og:image"),
+        vec!["https://example.com/media/cover.png"]
+    );
+    assert_eq!(
+        content(&tags, "twitter:image"),
+        vec!["https://example.com/media/cover.png"]
+    );
+    assert_eq!(content(&tags, "twitter:card"), vec!["summary_large_image"]);
+    assert_eq!(content(&tags, "article:published_time"), vec!["2025-01-31"]);
+    assert_eq!(content(&tags, "article:author"), vec!["Taian Feitosa"]);
+    assert_eq!(content(&tags, "article:tag"), vec!["Rust", "Dioxus"]);
+    assert_eq!(content(&tags, "twitter:site"), vec!["@taianf"]);
+}
+
+#[test]
+fn test_og_image_paths() {
+    assert_eq!(og_image_path(OgKind::Post, "hello"), "/og/post/hello.png");
+    assert_eq!(og_image_path(OgKind::Cv, MAIN_CV), "/og/cv/main.png");
+    for kind in OgKind::ALL {
+        assert_eq!(OgKind::parse(kind.as_str()), Some(kind));
+    }
+    assert_eq!(OgKind::parse("page"), None);
+}
+
+#[test]
+fn test_generated_image_tags() {
+    let page = PageMeta {
+        title: "Hello".to_string(),
+        url: "https://example.com/blog/hello".to_string(),
+        image: Some(og_image_path(OgKind::Post, "hello")),
+        kind: PageKind::Article,
+        ..Default::default()
+    };
+    let tags = meta_tags(&page, &defaults(), SITE);
+
+    assert_eq!(
+        content(&tags, "og:image"),
+        vec!["https://example.com/og/post/hello.png"]
+    );
+    assert_eq!(content(&tags, "og:image:type"), vec!["image/png"]);
+    assert_eq!(content(&tags, "og:image:width"), vec!["1200"]);
+    assert_eq!(content(&tags, "og:image:height"), vec!["630"]);
+    assert_eq!(content(&tags, "og:image:alt"), vec!["Hello"]);
+    assert_eq!(content(&tags, "twitter:image:alt"), vec!["Hello"]);
+}
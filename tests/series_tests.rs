@@ -0,0 +1,65 @@
+use cv::models::blog::{parse_post, PostMeta};
+use cv::models::series::{in_series, series_of, series_parts};
+
+fn post(slug: &str, date: &str, series: &str) -> PostMeta {
+    let source = format!("---\ntitle: Post {}\ndate: {}\n{}---\n", slug, date, series);
+    parse_post(slug, &source).unwrap().meta
+}
+
+/// Posts newest first, as the index provides them.
+fn posts() -> Vec<PostMeta> {
+    vec![
+        post("e", "2026-10-20", "series: rust web\n"),
+        post("d", "2026-10-02", ""),
+        post("c", "2026-09-15", "series: Rust Web\nseries_part: 1\n"),
+        post("b", "2026-09-01", "series: Rust Web\nseries_part: 2\n"),
+        post("a", "2025-01-01", "series: Rust Web\n"),
+        post("x", "2025-01-01", "series: Other\n"),
+    ]
+}
+
+fn slugs(posts: &[&PostMeta]) -> Vec<String> {
+    posts.iter().map(|p| p.slug.clone()).collect()
+}
+
+#[test]
+fn test_series_names_match_by_slug() {
+    let posts = posts();
+    assert!(in_series(&posts[0], "Rust Web"));
+    assert!(in_series(&posts[0], "RUST-WEB"));
+    assert!(!in_series(&posts[1], "Rust Web"));
+    assert!(!in_series(&posts[5], "Rust Web"));
+}
+
+#[test]
+fn test_parts_are_numbered_first_then_by_date() {
+    let posts = posts();
+    assert_eq!(
+        slugs(&series_parts(&posts, "Rust Web")),
+        vec!["c", "b", "a", "e"]
+    );
+    assert_eq!(slugs(&series_parts(&posts, "Other")), vec!["x"]);
+    assert!(series_parts(&posts, "Nothing").is_empty());
+}
+
+#[test]
+fn test_series_of_a_post() {
+    let posts = posts();
+
+    let series = series_of(&posts, "b").unwrap();
+    assert_eq!(series.name, "Rust Web");
+    assert_eq!(series.current, 1);
+    assert_eq!(series.parts.len(), 4);
+    assert_eq!(series.prev().map(|p| p.slug.as_str()), Some("c"));
+    assert_eq!(series.next().map(|p| p.slug.as_str()), Some("a"));
+    assert_eq!(series.parts[0].title, "Post c");
+
+    let first = series_of(&posts, "c").unwrap();
+    assert_eq!(first.prev(), None);
+    let last = series_of(&posts, "e").unwrap();
+    assert_eq!(last.current, 3);
+    assert_eq!(last.next(), None);
+
+    assert_eq!(series_of(&posts, "d"), None);
+    assert_eq!(series_of(&posts, "missing"), None);
+}
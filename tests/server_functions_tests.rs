@@ -110,7 +110,7 @@ fn test_server_functions_client_only() {
     // The actual server functions should return errors when called on client-only
 
     // Basic compilation test for client-only builds
-    assert!(true, "Client-only build test passes");
+    let _ = cv::models::get_google_auth_url;
 }
 
 #[test]
@@ -0,0 +1,160 @@
+use cv::models::share::ShareLink;
+
+#[test]
+fn test_share_link_path() {
+    let link = ShareLink {
+        id: "abc".to_string(),
+        token: "abc.1700000000.sig".to_string(),
+        variant: "backend".to_string(),
+        label: "Acme".to_string(),
+        created_at: 0,
+        expires_at: 1_700_000_000,
+        revoked: false,
+        views: 0,
+    };
+    assert_eq!(link.path(), "/s/abc.1700000000.sig");
+}
+
+#[test]
+fn test_share_link_serialization() {
+    let link = ShareLink {
+        id: "abc".to_string(),
+        token: "token".to_string(),
+        variant: "backend".to_string(),
+        label: "Acme".to_string(),
+        created_at: 1,
+        expires_at: 2,
+        revoked: true,
+        views: 3,
+    };
+    let json = serde_json::to_string(&link).unwrap();
+    let deserialized: ShareLink = serde_json::from_str(&json).unwrap();
+    assert_eq!(link, deserialized);
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use cv::backend::share::{self, ShareError};
+    use cv::backend::{db, session};
+    use rusqlite::Connection;
+
+    const SECRET: &[u8] = b"test-secret";
+    const NOW: i64 = 1_700_000_000;
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_open_valid_link_counts_views() {
+        let conn = conn();
+        let link = share::create(&conn, SECRET, "backend", "Acme", NOW, NOW + DAY).unwrap();
+
+        let variant = share::open(
+            &conn,
+            SECRET,
+            &link.token,
+            NOW + 10,
+            Some("https://mail.example.com"),
+        )
+        .unwrap();
+        assert_eq!(variant, "backend");
+        share::open(&conn, SECRET, &link.token, NOW + 20, None).unwrap();
+
+        let links = share::list(&conn, SECRET).unwrap();
+        assert_eq!(links[0].views, 2);
+        assert_eq!(links[0].token, link.token);
+
+        let views = share::views(&conn, &link.id).unwrap();
+        assert_eq!(views.len(), 2);
+        assert_eq!(views[0], (NOW + 20, None));
+        assert_eq!(
+            views[1],
+            (NOW + 10, Some("https://mail.example.com".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_expired_link_is_rejected() {
+        let conn = conn();
+        let link = share::create(&conn, SECRET, "backend", "Acme", NOW, NOW + DAY).unwrap();
+
+        let result = share::open(&conn, SECRET, &link.token, NOW + 2 * DAY, None);
+        assert_eq!(result, Err(ShareError::Expired));
+    }
+
+    #[test]
+    fn test_revoked_link_is_rejected() {
+        let conn = conn();
+        let link = share::create(&conn, SECRET, "backend", "Acme", NOW, NOW + DAY).unwrap();
+        share::revoke(&conn, &link.id).unwrap();
+
+        let result = share::open(&conn, SECRET, &link.token, NOW, None);
+        assert_eq!(result, Err(ShareError::Revoked));
+        assert!(share::list(&conn, SECRET).unwrap()[0].revoked);
+    }
+
+    #[test]
+    fn test_tampered_token_is_rejected() {
+        let conn = conn();
+        let link = share::create(&conn, SECRET, "backend", "Acme", NOW, NOW + DAY).unwrap();
+
+        // Extending the expiry invalidates the signature
+        let (id, rest) = link.token.split_once('.').unwrap();
+        let (_, signature) = rest.split_once('.').unwrap();
+        let forged = format!("{}.{}.{}", id, NOW + 100 * DAY, signature);
+        assert_eq!(
+            share::open(&conn, SECRET, &forged, NOW, None),
+            Err(ShareError::Invalid)
+        );
+
+        // Tokens signed with another key are rejected
+        assert_eq!(
+            share::open(&conn, b"other-secret", &link.token, NOW, None),
+            Err(ShareError::Invalid)
+        );
+        assert_eq!(
+            share::open(&conn, SECRET, "garbage", NOW, None),
+            Err(ShareError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_revoke_unknown_link() {
+        let conn = conn();
+        assert_eq!(share::revoke(&conn, "missing"), Err(ShareError::Invalid));
+    }
+
+    #[test]
+    fn test_session_cookie_roundtrip() {
+        let value = session::encode(SECRET, "owner@example.com", NOW + DAY);
+        assert_eq!(
+            session::decode(SECRET, &value, NOW),
+            Some("owner@example.com".to_string())
+        );
+        assert_eq!(session::decode(SECRET, &value, NOW + 2 * DAY), None);
+        assert_eq!(session::decode(b"other-secret", &value, NOW), None);
+    }
+
+    #[test]
+    fn test_session_cookie_cannot_change_email() {
+        let value = session::encode(SECRET, "user@example.com", NOW + DAY);
+        let (_, signature) = value.rsplit_once('.').unwrap();
+        let forged = session::encode(SECRET, "owner@example.com", NOW + DAY);
+        let (payload, _) = forged.rsplit_once('.').unwrap();
+        assert_eq!(
+            session::decode(SECRET, &format!("{}.{}", payload, signature), NOW),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cookie_value_parsing() {
+        let header = "theme=dark; cv_session=abc.def; other=1";
+        assert_eq!(session::cookie_value(header, "cv_session"), Some("abc.def"));
+        assert_eq!(session::cookie_value(header, "missing"), None);
+    }
+}
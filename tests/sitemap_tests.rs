@@ -0,0 +1,160 @@
+use cv::models::blog::{parse_post, PostMeta};
+use cv::models::sitemap::{disallowed_paths, entries, robots_txt, sitemap_xml};
+use cv::Route;
+
+fn posts() -> Vec<PostMeta> {
+    [
+        ("newer", "2026-10-02", "Rust"),
+        ("older", "2026-09-15", "rust, web"),
+    ]
+    .iter()
+    .map(|(slug, date, tags)| {
+        let source = format!(
+            "---\ntitle: {}\ndate: {}\ntags: [{}]\n---\n",
+            slug, date, tags
+        );
+        parse_post(slug, &source).unwrap().meta
+    })
+    .collect()
+}
+
+fn paths(posts: &[PostMeta]) -> Vec<String> {
+    entries(posts, &["backend".to_string()])
+        .iter()
+        .map(|entry| entry.route.to_string())
+        .collect()
+}
+
+#[test]
+fn test_sitemap_excludes_protected_routes() {
+    let paths = paths(&posts());
+
+    assert!(paths.contains(&"/".to_string()));
+    assert!(paths.contains(&"/blog".to_string()));
+    for protected in [
+        "/profile",
+        "/auth/callback",
+        "/admin/share-links",
+        "/admin/job-match",
+    ] {
+        assert!(
+            !paths.contains(&protected.to_string()),
+            "{} listed",
+            protected
+        );
+    }
+    assert!(!paths.iter().any(|path| path.starts_with("/s/")));
+}
+
+#[test]
+fn test_sitemap_includes_dynamic_entries() {
+    let paths = paths(&posts());
+
+    for expected in [
+        "/blog/newer",
+        "/blog/older",
+        "/blog/tag/rust",
+        "/blog/tag/web",
+        "/blog/2026/10",
+        "/blog/2026/9",
+        "/cv/backend",
+    ] {
+        assert!(
+            paths.contains(&expected.to_string()),
+            "{} missing",
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_sitemap_lastmod_from_content() {
+    let entries = entries(&posts(), &[]);
+    let lastmod = |route: Route| {
+        entries
+            .iter()
+            .find(|entry| entry.route == route)
+            .and_then(|entry| entry.lastmod)
+            .map(|date| date.to_string())
+    };
+
+    assert_eq!(lastmod(Route::Blog {}).as_deref(), Some("2026-10-02"));
+    assert_eq!(
+        lastmod(Route::BlogTag {
+            tag: "web".to_string()
+        })
+        .as_deref(),
+        Some("2026-09-15")
+    );
+    assert_eq!(lastmod(Route::Home {}), None);
+}
+
+#[test]
+fn test_sitemap_xml() {
+    let xml = sitemap_xml("https://example.com", &entries(&posts(), &[]));
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let root = doc.root_element();
+
+    assert_eq!(
+        root.tag_name().namespace(),
+        Some("http://www.sitemaps.org/schemas/sitemap/0.9")
+    );
+    let locs: Vec<&str> = doc
+        .descendants()
+        .filter(|n| n.tag_name().name() == "loc")
+        .filter_map(|n| n.text())
+        .collect();
+    assert!(locs.contains(&"https://example.com/blog/newer"));
+    assert!(xml.contains("<lastmod>2026-10-02</lastmod>"));
+}
+
+#[test]
+fn test_disallowed_paths_follow_route_visibility() {
+    assert_eq!(
+        disallowed_paths(),
+        vec![
+            "/admin/comments",
+            "/admin/job-match",
+            "/admin/posts",
+            "/admin/posts/",
+            "/admin/share-links",
+            "/auth/callback",
+            "/profile",
+            "/s/",
+            "/search"
+        ]
+    );
+}
+
+#[test]
+fn test_robots_txt() {
+    let robots = robots_txt(
+        "https://example.com",
+        false,
+        Some("User-agent: GPTBot\nDisallow: /\n"),
+    );
+
+    assert!(robots.starts_with("User-agent: *\n"));
+    assert!(robots.contains("Disallow: /profile\n"));
+    let default_group = robots.split("\n\n").next().unwrap();
+    assert!(!default_group.contains("Disallow: /\n"));
+    assert!(robots.contains("User-agent: GPTBot\nDisallow: /\n"));
+    assert!(robots.ends_with("Sitemap: https://example.com/sitemap.xml\n"));
+
+    let staging = robots_txt("https://example.com", true, None);
+    assert!(staging.contains("User-agent: *\nDisallow: /\n"));
+    assert!(!staging.contains("/profile"));
+}
+
+#[test]
+fn test_sitemap_leaves_out_posts_published_elsewhere() {
+    let mut posts = posts();
+    posts[1].canonical_url = Some("https://dev.to/me/older-1a2b".to_string());
+    let paths = paths(&posts);
+
+    assert!(paths.contains(&"/blog/newer".to_string()));
+    assert!(!paths.contains(&"/blog/older".to_string()));
+    // Its tags and month still have pages here
+    assert!(paths.contains(&"/blog/tag/web".to_string()));
+    assert!(paths.contains(&"/blog/2026/9".to_string()));
+}
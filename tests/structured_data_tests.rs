@@ -0,0 +1,104 @@
+use cv::models::cv::Cv;
+use cv::models::site::SiteUrl;
+use cv::models::structured_data::{blog_posting, person, profile_page, to_script, Article};
+
+fn bundled_cv() -> Cv {
+    Cv::from_toml(include_str!("../content/cv.toml")).unwrap()
+}
+
+#[test]
+fn test_person_same_as_social_profiles() {
+    let cv = bundled_cv();
+    let person = person(&cv.basics, "https://example.com");
+
+    assert_eq!(person["@type"], "Person");
+    assert_eq!(person["name"], "Taian Feitosa");
+    assert_eq!(person["url"], "https://example.com");
+    assert_eq!(
+        person["sameAs"],
+        serde_json::json!([
+            "https://github.com/taianf",
+            "https://www.linkedin.com/in/taian-feitosa/"
+        ])
+    );
+}
+
+#[test]
+fn test_profile_page_wraps_person() {
+    let cv = bundled_cv();
+    let tailored = cv.tailor("backend").unwrap();
+    let page = profile_page(
+        &tailored,
+        "https://example.com",
+        "https://example.com/cv/backend",
+    );
+
+    assert_eq!(page["@context"], "https://schema.org");
+    assert_eq!(page["@type"], "ProfilePage");
+    assert_eq!(page["url"], "https://example.com/cv/backend");
+    assert_eq!(page["mainEntity"]["@type"], "Person");
+    assert_eq!(page["mainEntity"]["jobTitle"], "Backend Engineer (Rust)");
+    assert_eq!(page["mainEntity"]["knowsAbout"][0], "Rust");
+}
+
+#[test]
+fn test_blog_posting() {
+    let cv = bundled_cv();
+    let article = Article {
+        title: "Hello".to_string(),
+        description: "First post".to_string(),
+        url: "https://example.com/blog/hello".to_string(),
+        date_published: "2025-01-31".to_string(),
+        date_modified: None,
+        tags: vec!["rust".to_string(), "dioxus".to_string()],
+        syndication: Vec::new(),
+    };
+    let posting = blog_posting(&article, &cv.basics, "https://example.com");
+
+    assert_eq!(posting["@type"], "BlogPosting");
+    assert_eq!(posting["headline"], "Hello");
+    assert_eq!(posting["dateModified"], "2025-01-31");
+    assert_eq!(posting["keywords"], "rust, dioxus");
+    assert_eq!(posting["author"]["name"], "Taian Feitosa");
+    assert!(posting.get("sameAs").is_none());
+}
+
+#[test]
+fn test_blog_posting_links_copies_elsewhere() {
+    let cv = bundled_cv();
+    let article = Article {
+        title: "Hello".to_string(),
+        url: "https://medium.com/@me/hello-abc".to_string(),
+        date_published: "2025-01-31".to_string(),
+        syndication: vec!["https://dev.to/me/hello-1a2b".to_string()],
+        ..Default::default()
+    };
+    let posting = blog_posting(&article, &cv.basics, "https://example.com");
+
+    assert_eq!(posting["url"], "https://medium.com/@me/hello-abc");
+    assert_eq!(
+        posting["mainEntityOfPage"],
+        "https://medium.com/@me/hello-abc"
+    );
+    assert_eq!(posting["sameAs"][0], "https://dev.to/me/hello-1a2b");
+}
+
+#[test]
+fn test_script_cannot_close_tag() {
+    let document = serde_json::json!({ "headline": "</script><script>alert(1)</script>" });
+    let script = to_script(&document);
+
+    assert!(!script.contains("</script>"));
+    let parsed: serde_json::Value = serde_json::from_str(&script).unwrap();
+    assert_eq!(parsed, document);
+}
+
+#[test]
+fn test_site_url_absolute() {
+    let site = SiteUrl("https://example.com/".to_string());
+    assert_eq!(site.absolute("/blog"), "https://example.com/blog");
+    assert_eq!(
+        site.absolute("cv/backend"),
+        "https://example.com/cv/backend"
+    );
+}
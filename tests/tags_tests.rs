@@ -0,0 +1,58 @@
+use cv::models::blog::{parse_post, PostMeta};
+use cv::models::tags::{normalize_tags, tag_counts, tag_slug};
+
+fn post(slug: &str, tags: &[&str]) -> PostMeta {
+    let source = format!(
+        "---\ntitle: {}\ndate: 2025-01-01\ntags: [{}]\n---\n",
+        slug,
+        tags.join(", ")
+    );
+    parse_post(slug, &source).unwrap().meta
+}
+
+#[test]
+fn test_tag_slug() {
+    assert_eq!(tag_slug("Rust"), "rust");
+    assert_eq!(tag_slug("  Machine Learning "), "machine-learning");
+    assert_eq!(tag_slug("Node.js"), "node-js");
+    assert_eq!(tag_slug("C++"), "c-plus-plus");
+    assert_eq!(tag_slug("C#"), "c-sharp");
+    assert_eq!(tag_slug("Café"), "café");
+    assert_eq!(tag_slug("--"), "");
+}
+
+#[test]
+fn test_normalize_tags_merges_case() {
+    let tags = normalize_tags(vec![
+        "Rust".to_string(),
+        " rust ".to_string(),
+        "web".to_string(),
+        " ".to_string(),
+    ]);
+    assert_eq!(tags, vec!["Rust", "web"]);
+}
+
+#[test]
+fn test_tag_counts_merge_spellings() {
+    let posts = [
+        post("a", &["Rust", "web"]),
+        post("b", &["rust"]),
+        post("c", &["rust", "career"]),
+    ];
+    let counts = tag_counts(&posts);
+
+    assert_eq!(counts[0].slug, "rust");
+    assert_eq!(counts[0].count, 3);
+    // Most common spelling wins
+    assert_eq!(counts[0].name, "rust");
+    // Equal counts are ordered by slug
+    let rest: Vec<&str> = counts[1..].iter().map(|t| t.slug.as_str()).collect();
+    assert_eq!(rest, vec!["career", "web"]);
+}
+
+#[test]
+fn test_has_tag() {
+    let meta = post("a", &["Machine Learning"]);
+    assert!(meta.has_tag("machine-learning"));
+    assert!(!meta.has_tag("machine"));
+}
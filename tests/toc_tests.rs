@@ -0,0 +1,86 @@
+use cv::models::blog::{render_markdown, render_post};
+use cv::models::toc::{build_toc, heading_slug, HeadingIds, TocEntry};
+
+#[test]
+fn test_heading_slug() {
+    assert_eq!(heading_slug("Getting Started"), "getting-started");
+    assert_eq!(heading_slug("  What's new in 2.0?  "), "whats-new-in-20");
+    assert_eq!(heading_slug("C++ & Rust -- together"), "c-rust-together");
+    assert_eq!(heading_slug("snake_case stays"), "snake_case-stays");
+    assert_eq!(heading_slug("Café Crème"), "café-crème");
+    assert_eq!(heading_slug("!!!"), "section");
+}
+
+#[test]
+fn test_repeated_headings_are_numbered() {
+    let mut ids = HeadingIds::default();
+    assert_eq!(ids.id_for("Setup"), "setup");
+    assert_eq!(ids.id_for("Setup"), "setup-1");
+    assert_eq!(ids.id_for("setup"), "setup-2");
+    assert_eq!(ids.id_for("Setup 1"), "setup-1-1");
+}
+
+fn titles(entries: &[TocEntry]) -> Vec<String> {
+    entries
+        .iter()
+        .map(|entry| {
+            if entry.children.is_empty() {
+                entry.title.clone()
+            } else {
+                format!("{} {:?}", entry.title, titles(&entry.children))
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_toc_nests_by_level() {
+    let heading = |level: u8, title: &str| (level, heading_slug(title), title.to_string());
+    let toc = build_toc(&[
+        heading(2, "Intro"),
+        heading(3, "Why"),
+        heading(3, "How"),
+        heading(4, "Details"),
+        heading(2, "Setup"),
+        // A skipped level still nests under the heading before it
+        heading(4, "Deep"),
+        heading(2, "End"),
+    ]);
+    assert_eq!(
+        titles(&toc),
+        vec![
+            "Intro [\"Why\", \"How [\\\"Details\\\"]\"]",
+            "Setup [\"Deep\"]",
+            "End"
+        ]
+    );
+    assert_eq!(toc[0].ids(), vec!["intro", "why", "how", "details"]);
+
+    // Headings above the first level are roots too
+    let toc = build_toc(&[heading(3, "Lead"), heading(2, "Main")]);
+    assert_eq!(titles(&toc), vec!["Lead", "Main"]);
+}
+
+#[test]
+fn test_headings_get_ids_and_anchors() {
+    let html = render_markdown("## Hello `code` *world*\n\ntext\n\n## Hello code world\n");
+    assert!(html.contains("<h2 id=\"hello-code-world\">Hello <code>code</code> <em>world</em><a class=\"heading-anchor\" href=\"#hello-code-world\""));
+    assert!(html.contains("<h2 id=\"hello-code-world-1\">"));
+
+    // Raw HTML in a heading cannot inject attributes
+    let html = render_markdown("## <b onclick=x>\"quoted\"</b>\n");
+    assert!(html.contains("<h2 id=\"quoted\">&lt;b onclick=x&gt;"));
+}
+
+#[test]
+fn test_render_post_collects_toc_and_words() {
+    let rendered = render_post(
+        "Intro words here.\n\n## First\n\nOne two three.\n\n### Sub\n\n```rust\nfn not_counted() {}\n```\n\n## Second `x`\n",
+    );
+    let ids: Vec<String> = rendered.toc.iter().flat_map(TocEntry::ids).collect();
+    assert_eq!(ids, vec!["first", "sub", "second-x"]);
+    assert_eq!(rendered.toc[1].title, "Second x");
+    assert_eq!(rendered.toc[0].level, 2);
+    // Prose and headings count; the code block does not
+    assert_eq!(rendered.word_count, 3 + 1 + 3 + 1 + 2);
+}
@@ -0,0 +1,512 @@
+use cv::models::webmentions::{
+    discover_endpoint, links, links_to, page_title, parse_http_url, Webmention, WebmentionStatus,
+};
+use reqwest::Url;
+
+fn url(url: &str) -> Url {
+    Url::parse(url).unwrap()
+}
+
+#[test]
+fn test_endpoint_from_link_header_comes_first() {
+    let target = url("https://example.com/posts/hello");
+    let html = r#"<link rel="webmention" href="/from-html">"#;
+    assert_eq!(
+        discover_endpoint(
+            &target,
+            &[
+                r#"<https://example.com/feed>; rel="alternate""#.to_string(),
+                r#"</mentions?x=1>; rel="other webmention""#.to_string(),
+            ],
+            html,
+        ),
+        Some(url("https://example.com/mentions?x=1"))
+    );
+    assert_eq!(
+        discover_endpoint(
+            &target,
+            &[r#"<https://a.example/one>; rel=webmention, <https://a.example/two>; rel="webmention""#.to_string()],
+            "",
+        ),
+        Some(url("https://a.example/one"))
+    );
+    assert_eq!(
+        discover_endpoint(&target, &[], html),
+        Some(url("https://example.com/from-html"))
+    );
+}
+
+#[test]
+fn test_endpoint_from_html() {
+    let target = url("https://example.com/posts/hello");
+    // The first of <link> and <a> in document order, comments skipped
+    let html = r#"<html><head>
+        <!-- <link rel="webmention" href="/commented-out"> -->
+        <link rel="stylesheet" href="/style.css">
+        <link href='endpoint?version=1&amp;x=2' REL='WebMention'>
+        </head><body><a rel="webmention" href="/later">x</a></body></html>"#;
+    assert_eq!(
+        discover_endpoint(&target, &[], html),
+        Some(url("https://example.com/posts/endpoint?version=1&x=2"))
+    );
+    // An empty href is the page itself
+    assert_eq!(
+        discover_endpoint(&target, &[], r#"<a rel=webmention href="">me</a>"#),
+        Some(target.clone())
+    );
+    assert_eq!(
+        discover_endpoint(&target, &[], r#"<a href="/webmention">no rel</a>"#),
+        None
+    );
+}
+
+#[test]
+fn test_links_to_target() {
+    let source = url("https://blog.example/2026/notes");
+    let target = url("https://me.example/blog/hello");
+    let html = r#"<p>See <a class="x" href="https://me.example/blog/hello#part-2">this</a>.</p>"#;
+    assert!(links_to(html, &source, &target));
+    assert!(!links_to(
+        r#"<a href="https://me.example/blog/hello-world">close</a>"#,
+        &source,
+        &target
+    ));
+    // Text mentioning the URL is not a link
+    assert!(!links_to("https://me.example/blog/hello", &source, &target));
+    assert_eq!(
+        links(
+            r#"<a href="../other">a</a><a href=/abs>b</a><a>c</a>"#,
+            &source
+        ),
+        vec![
+            url("https://blog.example/other"),
+            url("https://blog.example/abs")
+        ]
+    );
+}
+
+#[test]
+fn test_page_title() {
+    assert_eq!(
+        page_title("<html><head><TITLE>\n  Notes &amp; links\n</TITLE></head></html>"),
+        Some("Notes & links".to_string())
+    );
+    assert_eq!(page_title("<title> </title>"), None);
+    assert_eq!(page_title("<p>No title</p>"), None);
+    assert_eq!(
+        page_title(&format!("<title>{}</title>", "x".repeat(500)))
+            .unwrap()
+            .len(),
+        200
+    );
+}
+
+#[test]
+fn test_only_http_urls_are_accepted() {
+    assert!(parse_http_url("https://example.com/a").is_some());
+    assert!(parse_http_url(" http://example.com ").is_some());
+    assert!(parse_http_url("ftp://example.com/a").is_none());
+    assert!(parse_http_url("javascript:alert(1)").is_none());
+    assert!(parse_http_url("/relative").is_none());
+}
+
+#[test]
+fn test_mention_host_and_status() {
+    let mention = Webmention {
+        source: "https://blog.example/notes".to_string(),
+        title: String::new(),
+        verified_at: 0,
+    };
+    assert_eq!(mention.host(), "blog.example");
+    for status in WebmentionStatus::ALL {
+        assert_eq!(WebmentionStatus::parse(status.as_str()), Some(status));
+    }
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use super::url;
+    use cv::backend::db;
+    use cv::backend::webmentions::{
+        for_post, get, is_public, is_public_ip, log_send, mark_handled, outgoing_links,
+        public_client_builder, receive, send, set_verified, target_slug, unsent, validate, verify,
+        WebmentionError,
+    };
+    use cv::models::blog::{parse_post, Post};
+    use cv::models::webmentions::WebmentionStatus;
+    use rusqlite::Connection;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    const SITE: &str = "https://me.example";
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        conn
+    }
+
+    fn post(slug: &str, body: &str) -> Post {
+        parse_post(
+            slug,
+            &format!("---\ntitle: {}\ndate: 2026-10-01\n---\n{}\n", slug, body),
+        )
+        .unwrap()
+    }
+
+    /// A request the stand-in server received: method, path and body.
+    type Request = (String, String, String);
+
+    /// A local HTTP server answering each path with a canned response, for
+    /// as many requests as are made. `{base}` in a body or header is
+    /// replaced with the server's own address.
+    fn stand_in(routes: Vec<(&str, u16, Vec<&str>, &str)>) -> (String, Arc<Mutex<Vec<Request>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let routes: Vec<(String, u16, Vec<String>, String)> = routes
+            .into_iter()
+            .map(|(path, status, headers, body)| {
+                (
+                    path.to_string(),
+                    status,
+                    headers.iter().map(|h| h.replace("{base}", &base)).collect(),
+                    body.replace("{base}", &base),
+                )
+            })
+            .collect();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_string();
+                let path = parts.next().unwrap_or_default().to_string();
+                seen.lock()
+                    .unwrap()
+                    .push((method, path.clone(), String::from_utf8(body).unwrap()));
+
+                let (status, headers, body) = routes
+                    .iter()
+                    .find(|(route, ..)| *route == path)
+                    .map(|(_, status, headers, body)| (*status, headers.clone(), body.clone()))
+                    .unwrap_or((404, Vec::new(), String::new()));
+                let mut response = format!(
+                    "HTTP/1.1 {} Stand-in\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n",
+                    status,
+                    body.len()
+                );
+                for header in headers {
+                    response.push_str(&format!("{}\r\n", header));
+                }
+                response.push_str("\r\n");
+                response.push_str(&body);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (base, requests)
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_targets_must_be_posts_on_this_site() {
+        let posts = vec![post("hello", "Hi")];
+        assert_eq!(
+            target_slug(SITE, &url("https://me.example/blog/hello/")),
+            Some("hello".to_string())
+        );
+        assert_eq!(
+            target_slug(SITE, &url("https://other.example/blog/hello")),
+            None
+        );
+        assert_eq!(
+            target_slug(SITE, &url("http://me.example/blog/hello")),
+            None
+        );
+        assert_eq!(target_slug(SITE, &url("https://me.example/blog/a/b")), None);
+        assert_eq!(target_slug(SITE, &url("https://me.example/about")), None);
+
+        let (source, _, slug) = validate(
+            "https://blog.example/notes",
+            "https://me.example/blog/hello#comments",
+            SITE,
+            &posts,
+        )
+        .unwrap();
+        assert_eq!(source.as_str(), "https://blog.example/notes");
+        assert_eq!(slug, "hello");
+        assert_eq!(
+            validate(
+                "https://blog.example/notes",
+                "https://me.example/blog/missing",
+                SITE,
+                &posts
+            ),
+            Err(WebmentionError::UnknownTarget)
+        );
+        assert_eq!(
+            validate("notes", "https://me.example/blog/hello", SITE, &posts),
+            Err(WebmentionError::InvalidUrl)
+        );
+        assert_eq!(
+            validate(
+                "https://me.example/blog/hello",
+                "https://me.example/blog/hello",
+                SITE,
+                &posts
+            ),
+            Err(WebmentionError::SameUrl)
+        );
+    }
+
+    #[test]
+    fn test_received_mentions_show_once_verified() {
+        let conn = conn();
+        let source = "https://blog.example/notes";
+        let target = "https://me.example/blog/hello";
+        let id = receive(&conn, source, target, "hello", 100).unwrap();
+        assert_eq!(
+            get(&conn, id).unwrap().unwrap().status,
+            WebmentionStatus::Pending
+        );
+        assert!(for_post(&conn, "hello").unwrap().is_empty());
+
+        set_verified(&conn, id, Some("Notes"), 110).unwrap();
+        let shown = for_post(&conn, "hello").unwrap();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].title, "Notes");
+        assert_eq!(shown[0].verified_at, 110);
+
+        // Sending it again updates the same mention, which goes away once
+        // the source stops linking
+        assert_eq!(receive(&conn, source, target, "hello", 200).unwrap(), id);
+        set_verified(&conn, id, None, 210).unwrap();
+        assert_eq!(
+            get(&conn, id).unwrap().unwrap().status,
+            WebmentionStatus::Rejected
+        );
+        assert!(for_post(&conn, "hello").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_posts_listed_before_the_first_run_are_not_sent() {
+        let conn = conn();
+        let old = vec![post("old", "Old")];
+        assert!(unsent(&conn, &old, 100).unwrap().is_empty());
+
+        let listed = vec![post("new", "New"), post("old", "Old")];
+        let pending = unsent(&conn, &listed, 200).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].meta.slug, "new");
+        assert!(mark_handled(&conn, "new", 200).unwrap());
+        assert!(!mark_handled(&conn, "new", 210).unwrap());
+        assert!(unsent(&conn, &listed, 300).unwrap().is_empty());
+
+        log_send(&conn, "new", "https://other.example/", "no endpoint", 200).unwrap();
+        log_send(&conn, "new", "https://other.example/", "sent (202)", 300).unwrap();
+    }
+
+    #[test]
+    fn test_outgoing_links_are_external_and_unique() {
+        let post = post(
+            "hello",
+            "[a](https://other.example/a#x) [b](https://other.example/a) [own](/blog/other) \
+             [mail](mailto:me@example.com) [c](http://third.example/)",
+        );
+        let links = outgoing_links(&post, &url("https://me.example/blog/hello"));
+        assert_eq!(
+            links,
+            vec![url("https://other.example/a"), url("http://third.example/")]
+        );
+    }
+
+    #[test]
+    fn test_local_addresses_are_not_public() {
+        assert!(is_public(&url("https://example.com/")));
+        assert!(is_public(&url("http://93.184.216.34/")));
+        for local in [
+            "http://localhost:8080/",
+            "http://app.localhost/",
+            "http://127.0.0.1/",
+            "http://10.1.2.3/",
+            "http://192.168.0.1/",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/",
+            "http://[fd00::1]/",
+            "http://[::ffff:127.0.0.1]/",
+            "http://0.0.0.0/",
+        ] {
+            assert!(!is_public(&url(local)), "{}", local);
+        }
+    }
+
+    #[test]
+    fn test_reserved_addresses_are_not_public() {
+        for ip in [
+            "100.64.0.1",
+            "100.127.255.254",
+            "0.1.2.3",
+            "192.0.0.8",
+            "198.18.0.1",
+            "224.0.0.1",
+            "240.0.0.1",
+            "ff02::1",
+            "64:ff9b::a9fe:a9fe",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        assert!(is_public_ip("100.128.0.1".parse().unwrap()));
+        assert!(is_public_ip("64:ff9b::5db8:d822".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_public_client_refuses_local_redirects_and_names() {
+        let (base, requests) = stand_in(vec![
+            ("/hop", 302, vec!["Location: {base}/inner"], ""),
+            ("/inner", 200, vec![], "<title>Internal</title>"),
+        ]);
+        let client = public_client_builder().build().unwrap();
+        let target = url("https://me.example/blog/hello");
+
+        // The first hop is a literal address, which the endpoint checks
+        // before fetching; the redirect into the local network is refused
+        assert!(block_on(verify(&client, &url(&format!("{}/hop", base)), &target)).is_err());
+        // Names only resolve to public addresses
+        let by_name = base.replace("127.0.0.1", "localhost");
+        assert!(block_on(verify(
+            &client,
+            &url(&format!("{}/inner", by_name)),
+            &target
+        ))
+        .is_err());
+        let paths: Vec<String> = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.1.clone())
+            .collect();
+        assert_eq!(paths, vec!["/hop"]);
+    }
+
+    #[test]
+    fn test_verify_fetches_the_source() {
+        let (base, _) = stand_in(vec![
+            (
+                "/links",
+                200,
+                vec![],
+                r#"<title>Reply</title><a href="https://me.example/blog/hello">post</a>"#,
+            ),
+            (
+                "/no-link",
+                200,
+                vec![],
+                "<title>Other</title><p>Nothing here</p>",
+            ),
+            (
+                "/gone",
+                410,
+                vec![],
+                r#"<a href="https://me.example/blog/hello">post</a>"#,
+            ),
+        ]);
+        let target = url("https://me.example/blog/hello");
+        let client = reqwest::Client::new();
+        let check = |path: &str| {
+            let source = url(&format!("{}{}", base, path));
+            block_on(verify(&client, &source, &target)).unwrap()
+        };
+        assert_eq!(check("/links"), Some("Reply".to_string()));
+        assert_eq!(check("/no-link"), None);
+        assert_eq!(check("/gone"), None);
+    }
+
+    #[test]
+    fn test_send_discovers_the_endpoint() {
+        let (base, requests) = stand_in(vec![
+            (
+                "/by-header",
+                200,
+                vec!["Link: <{base}/endpoint>; rel=\"webmention\""],
+                "<p>Hi</p>",
+            ),
+            (
+                "/by-html",
+                200,
+                vec![],
+                r#"<link rel="webmention" href="/endpoint?from=html">"#,
+            ),
+            ("/none", 200, vec![], "<p>No endpoint</p>"),
+            ("/endpoint", 202, vec![], ""),
+            ("/endpoint?from=html", 201, vec![], ""),
+        ]);
+        let source = url("https://me.example/blog/hello");
+        let client = reqwest::Client::new();
+        let send_to = |path: &str| {
+            let target = url(&format!("{}{}", base, path));
+            block_on(send(&client, &source, &target, false))
+        };
+
+        assert_eq!(send_to("/by-header"), "sent (202)");
+        assert_eq!(send_to("/by-html"), "sent (201)");
+        assert_eq!(send_to("/none"), "no endpoint");
+        // Local endpoints are refused outside tests
+        assert_eq!(
+            block_on(send(
+                &client,
+                &source,
+                &url(&format!("{}/by-header", base)),
+                true
+            )),
+            "endpoint not public"
+        );
+
+        let posts: Vec<Request> = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(method, ..)| method == "POST")
+            .cloned()
+            .collect();
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].1, "/endpoint");
+        let form: Vec<(String, String)> = url(&format!("http://x/?{}", posts[0].2))
+            .query_pairs()
+            .into_owned()
+            .collect();
+        assert_eq!(
+            form,
+            vec![
+                ("source".to_string(), source.to_string()),
+                ("target".to_string(), format!("{}/by-header", base)),
+            ]
+        );
+        assert_eq!(posts[1].1, "/endpoint?from=html");
+    }
+}